@@ -1,5 +1,5 @@
 use std::string::FromUtf8Error;
-use hyper_tls::native_tls;
+use native_tls;
 use passivized_test_support::http_errors::HttpError;
 
 use passivized_docker_engine_client::errors::{DecCreateError, DecUseError};
@@ -1,24 +1,31 @@
 mod base;
+mod builder;
 mod container;
 mod containers;
 mod container_files;
+mod container_readiness;
 mod exec;
 mod images;
 mod network;
 mod networks;
+mod session;
+mod transport;
 mod volume;
 mod volumes;
 
 pub use base::{DOCKER_ENGINE_VERSION, DockerEngineClient};
-pub use container::DecContainer;
-pub use containers::DecContainers;
+pub use builder::DockerEngineClientBuilder;
+pub use container::{ContainerHandle, DecContainer};
+pub use containers::{ContainersHandle, DecContainers};
 pub use container_files::DecContainerFiles;
-pub use exec::DecExec;
-pub use images::DecImages;
-pub use network::DecNetwork;
-pub use networks::DecNetworks;
-pub use volume::DecVolume;
-pub use volumes::DecVolumes;
+pub use exec::{DecExec, ExecHandle};
+pub use images::{DecImages, ImagesHandle};
+pub use network::{DecNetwork, NetworkHandle};
+pub use networks::{DecNetworks, NetworksHandle};
+pub use session::{DecSession, SESSION_CREATED_LABEL, SESSION_LABEL, SessionHandle};
+pub use transport::{DockerEngineTransport, TransportError, TransportFuture};
+pub use volume::{DecVolume, VolumeHandle};
+pub use volumes::{DecVolumes, VolumesHandle};
 
 // Internal only
 pub(crate) mod shared;
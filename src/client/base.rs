@@ -1,14 +1,18 @@
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::time::Duration;
 
-use hyper_tls::native_tls::TlsConnector;
-
-use crate::client::{DecContainer, DecContainers, DecExec, DecImages, DecNetwork, DecNetworks, DecVolume, DecVolumes};
+use crate::client::{DecContainer, DecContainers, DecExec, DecImages, DecNetwork, DecNetworks, DecSession, DecVolume, DecVolumes, DockerEngineClientBuilder};
+use crate::client::builder::DockerEngineEndpoint;
+use crate::client::session;
 use crate::errors::{DecCreateError, DecUseError};
-use crate::imp::api::{DockerEngineApi, DockerEngineServer, SchemedUrl};
+use crate::imp::api::DockerEngineApi;
+use crate::imp::context::{ContextStore, DEFAULT_CONTEXT, DockerContext};
 use crate::imp::http_proxy::DockerEngineHttpClient;
-use crate::imp::hyper_proxy::HyperHttpClient;
-use crate::model::RegistryAuth;
-use crate::responses::VersionResponse;
+use crate::imp::tls::tls_config;
+use crate::model::{Cancellation, ContainerId, ExecId, NetworkId, RegistryAuth, RetryPolicy, TlsConfig, TlsPemFiles, VolumeName};
+use crate::responses::{CleanupReport, VersionResponse};
 
 /// Docker Engine REST api version that this version of the Rust library uses when talking to Docker Engine.
 pub const DOCKER_ENGINE_VERSION: &str = "v1.41";
@@ -49,6 +53,14 @@ impl DockerEngineClient {
 
     /// Configure a client. No connection is made to server until first request.
     ///
+    /// Like the docker cli, uses the first of:
+    ///
+    /// 1. The DOCKER_HOST environment variable, secured with TLS only if DOCKER_TLS_VERIFY
+    ///    or DOCKER_TLS is set. DOCKER_CERT_PATH only sets where the PEM files are found.
+    /// 2. The Docker context named by the DOCKER_CONTEXT environment variable
+    /// 3. The current Docker context, as selected by `docker context use`
+    /// 4. The platform's default Docker Engine socket
+    ///
     /// Because named pipes are not yet supported by this library, this method
     /// will fail on a default Windows configuration.
     ///
@@ -70,17 +82,155 @@ impl DockerEngineClient {
     /// }
     /// ```
     pub fn new() -> Result<DockerEngineClient, DecCreateError> {
-        match crate::imp::env::docker_host() {
-            Some(host) => {
-                Self::with_server(host)
-            },
-            #[cfg(not(windows))]
-            None => {
-                Self::with_server(crate::imp::env::default_server())
-            }
-            #[cfg(windows)]
-            None => {
-                Err(DecCreateError::NamedPipesNotSupported)
+        DockerEngineClientBuilder::new().build()
+    }
+
+    /// Configure a client with timeouts, connection pool settings, default headers,
+    /// or a different Docker Engine api version.
+    pub fn builder() -> DockerEngineClientBuilder {
+        DockerEngineClientBuilder::new()
+    }
+
+    pub(super) fn environment_endpoint() -> Result<DockerEngineEndpoint, DecCreateError> {
+        if let Some(host) = crate::imp::env::docker_host() {
+            return Self::env_host_endpoint(host);
+        }
+
+        match Self::current_context_name()? {
+            Some(name) if name != DEFAULT_CONTEXT =>
+                Self::context_endpoint(&name),
+            _ =>
+                Self::default_endpoint()
+        }
+    }
+
+    fn env_host_endpoint(host: String) -> Result<DockerEngineEndpoint, DecCreateError> {
+        let tls = crate::imp::env::docker_tls_verify() || crate::imp::env::docker_tls();
+
+        match Self::env_tls_files(&host, tls, crate::imp::env::docker_cert_path())? {
+            None => Ok(DockerEngineEndpoint::plain(host)),
+            Some(files) => Self::tls_files_endpoint(host, files)
+        }
+    }
+
+    /// Like the docker cli, TLS is only used when DOCKER_TLS_VERIFY or DOCKER_TLS is set.
+    /// DOCKER_CERT_PATH alone does not enable it. The Docker Engine's certificate is always verified.
+    fn env_tls_files(host: &str, tls: bool, cert_path: Option<PathBuf>) -> Result<Option<TlsPemFiles>, DecCreateError> {
+        let tls_capable = host.starts_with("tcp://") || host.starts_with("https://");
+
+        if !tls_capable || !tls {
+            return Ok(None);
+        }
+
+        let files = cert_path
+            .or_else(crate::imp::env::docker_config_dir)
+            .map(TlsPemFiles::from_dir)
+            .transpose()?
+            .unwrap_or_default()
+            .verify(true);
+
+        Ok(Some(files))
+    }
+
+    fn current_context_name() -> Result<Option<String>, DecCreateError> {
+        if let Some(name) = crate::imp::env::docker_context() {
+            return Ok(Some(name));
+        }
+
+        match crate::imp::env::docker_config_dir() {
+            None => Ok(None),
+            Some(dir) => ContextStore::new(dir).current_context_name()
+        }
+    }
+
+    fn default_endpoint() -> Result<DockerEngineEndpoint, DecCreateError> {
+        #[cfg(not(windows))]
+        {
+            Ok(DockerEngineEndpoint::plain(crate::imp::env::default_server()))
+        }
+        #[cfg(windows)]
+        {
+            Err(DecCreateError::NamedPipesNotSupported)
+        }
+    }
+
+    /// Connect to the Docker Engine of a Docker cli context, as created by
+    /// `docker context create` or by tools such as Colima and rootless Docker.
+    ///
+    /// Contexts are read from $DOCKER_CONFIG/contexts, or ~/.docker/contexts. TLS
+    /// certificates stored with the context are used; a client key must be in PKCS #8
+    /// format.
+    ///
+    /// The context named "default" refers to DOCKER_HOST, or the platform's default socket.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::errors::DecError;
+    ///
+    /// async fn example() -> Result<(), DecError> {
+    ///     let dec = DockerEngineClient::from_context("colima")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_context<N: AsRef<str>>(name: N) -> Result<DockerEngineClient, DecCreateError> {
+        DockerEngineClientBuilder::new()
+            .context(name.as_ref())
+            .build()
+    }
+
+    pub(super) fn context_endpoint(name: &str) -> Result<DockerEngineEndpoint, DecCreateError> {
+        if name == DEFAULT_CONTEXT {
+            return match crate::imp::env::docker_host() {
+                Some(host) => Self::env_host_endpoint(host),
+                None => Self::default_endpoint()
+            };
+        }
+
+        let config_dir = crate::imp::env::docker_config_dir()
+            .ok_or_else(|| DecCreateError::ContextNotFound(name.to_string()))?;
+
+        let context = ContextStore::new(config_dir).load(name)?;
+
+        Self::endpoint_of_context(context)
+    }
+
+    fn endpoint_of_context(context: DockerContext) -> Result<DockerEngineEndpoint, DecCreateError> {
+        let secured = context.tls.is_some() || context.skip_tls_verify;
+
+        let https_url = if context.host.starts_with("https://") {
+            Some(context.host.clone())
+        }
+        else if secured {
+            context.host
+                .strip_prefix("tcp://")
+                .map(|stripped| format!("https://{}", stripped))
+        }
+        else {
+            None
+        };
+
+        match https_url {
+            None => Ok(DockerEngineEndpoint::plain(context.host)),
+            Some(url) => {
+                let tls = context.tls.unwrap_or_default();
+
+                let cert_and_key = match (&tls.cert, &tls.key) {
+                    (Some(cert), Some(key)) => Some((cert.as_slice(), key.as_slice())),
+                    (None, None) => None,
+                    _ => return Err(DecCreateError::TlsClientIdentity(
+                        format!("context {} has only one of cert.pem and key.pem", context.name)
+                    ))
+                };
+
+                let config = tls_config(tls.ca.as_deref(), cert_and_key, !context.skip_tls_verify)?;
+
+                Ok(DockerEngineEndpoint {
+                    url,
+                    tls: Some(config)
+                })
             }
         }
     }
@@ -116,8 +266,50 @@ impl DockerEngineClient {
         self
     }
 
+    /// Use a different retry policy for idempotent requests, for example to disable
+    /// retries for a single call. See DockerEngineClientBuilder::retry_policy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::errors::DecError;
+    /// use passivized_docker_engine_client::model::RetryPolicy;
+    ///
+    /// async fn example(dec: &DockerEngineClient) -> Result<(), DecError> {
+    ///     let images = dec.clone()
+    ///         .with_retry_policy(RetryPolicy::none())
+    ///         .images()
+    ///         .list()
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_retry_policy(mut self, v: RetryPolicy) -> Self {
+        self.http = self.http.with_retry_policy(v);
+        self
+    }
+
+    /// A copy of this client that opens its own connections, for use from another Tokio runtime,
+    /// such as one created in a Drop implementation.
+    ///
+    /// Connections are owned by the runtime that opened them, so a clone of a client would
+    /// stall if it reused a connection of a runtime that is blocked waiting for it.
+    pub fn detached(&self) -> DockerEngineClient {
+        Self {
+            http: self.http.detached(),
+            registry_auth: self.registry_auth.clone(),
+            url: self.url.clone()
+        }
+    }
+
     /// Connect to a specific Docker Engine.
     ///
+    /// Accepts http://, https://, tcp://, unix:// (or a socket path), and ssh://[user@]host[:port]
+    /// urls. For ssh urls, each connection runs `ssh ... docker system dial-stdio` using the ssh
+    /// program on the PATH, which requires the docker cli to be installed on the remote host.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -131,91 +323,123 @@ impl DockerEngineClient {
     /// }
     /// ```
     pub fn with_server<U: ToString>(uri_or_unix_socket: U) -> Result<DockerEngineClient, DecCreateError> {
-        let server = DockerEngineServer::new(uri_or_unix_socket.to_string())?;
-
-        let hyper_client = match server.base.implied_url() {
-            SchemedUrl::Http(_) =>
-                HyperHttpClient::http(),
-
-            SchemedUrl::Https(_) => {
-                let tls = TlsConnector::new()?;
-
-                HyperHttpClient::https(tls)
-            },
-
-            #[cfg(unix)]
-            SchemedUrl::Unix(_) =>
-                HyperHttpClient::unix(),
-
-        };
-
-        let result = DockerEngineClient {
-            http: DockerEngineHttpClient::new(hyper_client),
-            registry_auth: None,
-            url: DockerEngineApi::new(server)
-        };
-
-        Ok(result)
+        DockerEngineClientBuilder::new()
+            .server(uri_or_unix_socket)
+            .build()
     }
 
     /// Connect to a TLS-secured Docker Engine, using a specific TLS configuration on the client.
     ///
     /// See test_registry.rs for a complex example.
-    pub fn with_tls_config<U: ToString>(https_url: U, tls: TlsConnector) -> Result<DockerEngineClient, DecCreateError> {
-        let result = DockerEngineClient {
-            http: DockerEngineHttpClient::new(HyperHttpClient::https(tls)),
-            registry_auth: None,
-            url: DockerEngineApi::with_server(https_url.to_string())?
+    ///
+    /// Accepts a native_tls::TlsConnector when the native-tls feature is enabled, or a
+    /// rustls::ClientConfig when the rustls feature is enabled.
+    pub fn with_tls_config<U: ToString, T: Into<TlsConfig>>(https_url: U, tls: T) -> Result<DockerEngineClient, DecCreateError> {
+        DockerEngineClientBuilder::new()
+            .tls_config(https_url, tls)
+            .build()
+    }
+
+    /// Connect to a TLS-secured Docker Engine, using PEM files for the certificate
+    /// authority and, for mutual TLS, a client certificate and key.
+    ///
+    /// A tcp:// url is treated as https://
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::errors::DecCreateError;
+    /// use passivized_docker_engine_client::model::TlsPemFiles;
+    ///
+    /// fn example() -> Result<(), DecCreateError> {
+    ///     let dec = DockerEngineClient::with_tls_files(
+    ///         "tcp://builder:2376",
+    ///         TlsPemFiles::from_dir("/home/me/.docker/builder")?
+    ///     )?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_tls_files<U: ToString>(url: U, files: TlsPemFiles) -> Result<DockerEngineClient, DecCreateError> {
+        DockerEngineClientBuilder::new()
+            .tls_files(url, files)
+            .build()
+    }
+
+    pub(super) fn tls_files_endpoint(url: String, files: TlsPemFiles) -> Result<DockerEngineEndpoint, DecCreateError> {
+        let https_url = match url.strip_prefix("tcp://") {
+            None => url,
+            Some(stripped) => format!("https://{}", stripped)
         };
 
-        Ok(result)
+        Ok(DockerEngineEndpoint {
+            url: https_url,
+            tls: Some(files.config()?)
+        })
     }
 
     /// Work with a specific existing container, referenced by its container ID or container name.
-    pub fn container<C: Into<String>>(&'_ self, name_or_id: C) -> DecContainer<'_> {
+    ///
+    /// Accepts a ContainerId, or a name or ID as a string, but not an ImageId.
+    pub fn container<C: Into<ContainerId>>(&'_ self, name_or_id: C) -> DecContainer<'_> {
         DecContainer {
-            client: self,
-            container_id: name_or_id.into()
+            cancellation: Cancellation::default(),
+            client: Cow::Borrowed(self),
+            container_id: name_or_id.into().into_string()
         }
     }
 
     /// Work with containers as a group, or create a new container.
     pub fn containers(&'_ self) -> DecContainers<'_> {
         DecContainers {
-            client: self
+            client: Cow::Borrowed(self)
         }
     }
 
     /// Work with a specific existing container exec, referenced by its exec ID.
-    pub fn exec<E: Into<String>>(&'_ self, id: E) -> DecExec<'_> {
+    pub fn exec<E: Into<ExecId>>(&'_ self, id: E) -> DecExec<'_> {
         DecExec {
-            client: self,
-            exec_id: id.into()
+            cancellation: Cancellation::default(),
+            client: Cow::Borrowed(self),
+            exec_id: id.into().into_string()
         }
     }
 
     /// Work with images.
     pub fn images(&'_ self) -> DecImages<'_> {
         DecImages {
-            client: self
+            cancellation: Cancellation::default(),
+            client: Cow::Borrowed(self)
         }
     }
 
     /// Work with a specific existing network.
-    pub fn network<N: Into<String>>(&'_ self, id: N) -> DecNetwork<'_> {
+    pub fn network<N: Into<NetworkId>>(&'_ self, id: N) -> DecNetwork<'_> {
         DecNetwork {
-            client: self,
-            network_id: id.into()
+            client: Cow::Borrowed(self),
+            network_id: id.into().into_string()
         }
     }
 
     /// Work with networks as a collection/group, or create a new network.
     pub fn networks(&'_ self) -> DecNetworks<'_> {
         DecNetworks {
-            client: self
+            client: Cow::Borrowed(self)
         }
     }
 
+    /// Start a session, which labels everything created through it so it can be cleaned up.
+    pub fn session(&'_ self) -> DecSession<'_> {
+        DecSession::new(Cow::Borrowed(self))
+    }
+
+    /// Remove containers, networks, volumes and images of sessions started at least ttl ago,
+    /// such as those left behind by a process that exited without cleaning up.
+    pub async fn sweep_sessions(&self, ttl: Duration) -> Result<CleanupReport, DecUseError> {
+        session::sweep_sessions(self, ttl).await
+    }
+
     pub async fn version(&self) -> Result<VersionResponse, DecUseError> {
         let url = self.url.version();
 
@@ -227,17 +451,17 @@ impl DockerEngineClient {
     }
 
     /// Work with a specific existing volume.
-    pub fn volume<V: Into<String>>(&'_ self, id: V) -> DecVolume<'_> {
+    pub fn volume<V: Into<VolumeName>>(&'_ self, id: V) -> DecVolume<'_> {
         DecVolume {
-            client: self,
-            volume_id: id.into()
+            client: Cow::Borrowed(self),
+            volume_id: id.into().into_string()
         }
     }
 
     /// Work with volumes as a collection/group, or create a new volume.
     pub fn volumes(&'_ self) -> DecVolumes<'_> {
         DecVolumes {
-            client: self
+            client: Cow::Borrowed(self)
         }
     }
 
@@ -245,9 +469,36 @@ impl DockerEngineClient {
 
 #[cfg(test)]
 mod test_docker_engine_client {
-    use hyper_tls::native_tls::TlsConnector;
+    use std::path::PathBuf;
 
     use crate::DockerEngineClient;
+    use crate::errors::DecCreateError;
+    use crate::imp::context::{DockerContext, DockerContextTls};
+
+    #[test]
+    fn cert_path_alone_does_not_enable_tls() {
+        let actual = DockerEngineClient::env_tls_files("tcp://docker:2376", false, Some(PathBuf::from("/certs")))
+            .unwrap();
+
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn tls_is_always_verified() {
+        let actual = DockerEngineClient::env_tls_files("tcp://docker:2376", true, Some(PathBuf::from("/certs")))
+            .unwrap()
+            .unwrap();
+
+        assert!(actual.verify);
+    }
+
+    #[test]
+    fn tls_only_for_tcp_and_https() {
+        let actual = DockerEngineClient::env_tls_files("unix:///var/run/docker.sock", true, None)
+            .unwrap();
+
+        assert_eq!(None, actual);
+    }
 
     #[test]
     fn display_http() {
@@ -258,10 +509,10 @@ mod test_docker_engine_client {
         assert_eq!("Docker engine at http://foo".to_string(), actual);
     }
 
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
     #[test]
     fn display_https() {
-        let tls = TlsConnector::builder()
-            .build()
+        let tls = crate::imp::tls::default_tls_config()
             .unwrap();
         let dec = DockerEngineClient::with_tls_config("https://foo", tls)
             .unwrap();
@@ -281,6 +532,67 @@ mod test_docker_engine_client {
         assert_eq!("Docker engine at tcp://foo".to_string(), actual);
     }
 
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    #[test]
+    fn context_with_skipped_tls_verify() {
+        let context = DockerContext {
+            name: "remote".into(),
+            host: "tcp://builder:2376".into(),
+            skip_tls_verify: true,
+            tls: None
+        };
+
+        let actual = DockerEngineClient::endpoint_of_context(context)
+            .unwrap();
+
+        assert_eq!("https://builder:2376", actual.url);
+        assert!(actual.tls.is_some());
+    }
+
+    #[test]
+    fn context_with_cert_without_key() {
+        let context = DockerContext {
+            name: "remote".into(),
+            host: "tcp://builder:2376".into(),
+            skip_tls_verify: false,
+            tls: Some(DockerContextTls {
+                cert: Some(b"CERT".to_vec()),
+                ..DockerContextTls::default()
+            })
+        };
+
+        match DockerEngineClient::endpoint_of_context(context) {
+            Err(DecCreateError::TlsClientIdentity(_)) => {},
+            Err(other) => panic!("Unexpected error: {}", other),
+            Ok(_) => panic!("Expected an error")
+        }
+    }
+
+    #[test]
+    fn context_without_tls() {
+        let context = DockerContext {
+            name: "insecure".into(),
+            host: "tcp://builder:2375".into(),
+            skip_tls_verify: false,
+            tls: None
+        };
+
+        let actual = DockerEngineClient::endpoint_of_context(context)
+            .unwrap();
+
+        assert_eq!("tcp://builder:2375", actual.url);
+        assert!(actual.tls.is_none());
+    }
+
+    #[test]
+    fn display_ssh() {
+        let dec = DockerEngineClient::with_server("ssh://builder@remote-host")
+            .unwrap();
+        let actual = format!("{}", dec);
+
+        assert_eq!("Docker engine at ssh://builder@remote-host".to_string(), actual);
+    }
+
     #[test]
     #[cfg(unix)]
     fn display_unix() {
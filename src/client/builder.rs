@@ -0,0 +1,529 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyper::Uri;
+use hyper::http::{HeaderMap, HeaderName, HeaderValue};
+use hyper::http::header::{PROXY_AUTHORIZATION, USER_AGENT};
+
+use crate::client::{DockerEngineClient, DockerEngineTransport};
+use crate::errors::DecCreateError;
+use crate::imp::api::{DockerEngineApi, DockerEngineServer, SchemedUrl};
+use crate::imp::http_proxy::{DockerEngineHttpClient, DockerEngineHttpSettings};
+use crate::imp::hyper_proxy::{HyperHttpClient, HyperHttpClientSettings};
+use crate::imp::tls::default_tls_config;
+use crate::model::{Proxy, RetryPolicy, TlsConfig, TlsPemFiles};
+
+/// User-Agent sent when none is configured.
+const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Docker Engine url of a client with a custom transport, when none is configured.
+const TRANSPORT_URL: &str = "http://localhost";
+
+/// Which Docker Engine to connect to.
+#[derive(Debug)]
+enum DockerEngineTarget {
+    Environment,
+    Context(String),
+    Server(String),
+    TlsConfig(String, TlsConfig),
+    TlsFiles(String, TlsPemFiles)
+}
+
+/// A Docker Engine url, and the TLS configuration to use if it is secured.
+pub(super) struct DockerEngineEndpoint {
+    pub url: String,
+    pub tls: Option<TlsConfig>
+}
+
+impl DockerEngineEndpoint {
+
+    pub fn plain(url: String) -> Self {
+        Self {
+            url,
+            tls: None
+        }
+    }
+
+}
+
+/// Configure a DockerEngineClient with timeouts, connection pool settings, default headers,
+/// and the Docker Engine api version.
+///
+/// Without a timeout, a request to a Docker Engine that stops responding waits forever.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use passivized_docker_engine_client::DockerEngineClient;
+/// use passivized_docker_engine_client::errors::DecCreateError;
+///
+/// fn example() -> Result<(), DecCreateError> {
+///     let dec = DockerEngineClient::builder()
+///         .server("http://localhost:2375")
+///         .connect_timeout(Duration::from_secs(5))
+///         .request_timeout(Duration::from_secs(30))
+///         .streaming_timeout(Duration::from_secs(600))
+///         .user_agent("my-app/1.0")
+///         .build()?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct DockerEngineClientBuilder {
+    api_version: Option<String>,
+    connection: HyperHttpClientSettings,
+    headers: Vec<(String, String)>,
+    proxy: Option<Proxy>,
+    #[cfg(any(test, feature = "test-util"))]
+    record: Option<std::path::PathBuf>,
+    request_timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    streaming_timeout: Option<Duration>,
+    target: DockerEngineTarget,
+    transport: Option<Arc<dyn DockerEngineTransport>>,
+    user_agent: Option<String>
+}
+
+impl Default for DockerEngineClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DockerEngineClientBuilder {
+
+    /// Connect to the Docker Engine chosen by the environment, the same way as
+    /// DockerEngineClient::new, unless a server or context is set.
+    pub fn new() -> Self {
+        Self {
+            api_version: None,
+            connection: HyperHttpClientSettings::default(),
+            headers: Vec::new(),
+            proxy: None,
+            #[cfg(any(test, feature = "test-util"))]
+            record: None,
+            request_timeout: None,
+            retry_policy: RetryPolicy::none(),
+            streaming_timeout: None,
+            target: DockerEngineTarget::Environment,
+            transport: None,
+            user_agent: None
+        }
+    }
+
+    /// Docker Engine api version to request, such as "v1.43". Defaults to DOCKER_ENGINE_VERSION.
+    ///
+    /// Other versions may return responses that this library does not understand.
+    pub fn api_version<V: Into<String>>(mut self, v: V) -> Self {
+        let version = v.into();
+
+        self.api_version = Some(
+            if version.starts_with('v') {
+                version
+            }
+            else {
+                format!("v{}", version)
+            }
+        );
+
+        self
+    }
+
+    /// Time limit for establishing a TCP connection to the Docker Engine.
+    ///
+    /// Applies to http and https urls; unix sockets and ssh are bounded by the request timeouts.
+    pub fn connect_timeout(mut self, v: Duration) -> Self {
+        self.connection.connect_timeout = Some(v);
+        self
+    }
+
+    /// Connect to the Docker Engine of a Docker cli context. See DockerEngineClient::from_context.
+    pub fn context<N: Into<String>>(mut self, name: N) -> Self {
+        self.target = DockerEngineTarget::Context(name.into());
+        self
+    }
+
+    /// Add a header to every request. Headers set by a request itself take precedence.
+    pub fn header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Only use HTTP/2, without negotiation. The Docker Engine, or a proxy in front of it,
+    /// must accept HTTP/2 with prior knowledge.
+    pub fn http2_only(mut self, v: bool) -> Self {
+        self.connection.http2_only = v;
+        self
+    }
+
+    /// Close connections that stay idle in the pool for longer than this.
+    pub fn pool_idle_timeout(mut self, v: Duration) -> Self {
+        self.connection.pool_idle_timeout = Some(v);
+        self
+    }
+
+    /// Maximum number of idle connections to keep in the pool. Defaults to 10.
+    pub fn pool_max_idle_per_host(mut self, v: usize) -> Self {
+        self.connection.pool_max_idle_per_host = v;
+        self
+    }
+
+    /// Forward proxy to reach the Docker Engine through. Defaults to Proxy::from_env(),
+    /// which uses HTTP_PROXY, HTTPS_PROXY and NO_PROXY.
+    pub fn proxy(mut self, v: Proxy) -> Self {
+        self.proxy = Some(v);
+        self
+    }
+
+    /// Time limit for a request, including receiving the whole response.
+    ///
+    /// Does not apply to requests that last as long as a task in the Docker Engine, such as
+    /// pulling, pushing or building an image, committing a container, reading container logs,
+    /// waiting for a container, or running an exec; see streaming_timeout.
+    pub fn request_timeout(mut self, v: Duration) -> Self {
+        self.request_timeout = Some(v);
+        self
+    }
+
+    /// Retry idempotent requests that fail with a connection error or a 5xx response.
+    /// Defaults to RetryPolicy::none().
+    ///
+    /// Retries happen within the request timeout.
+    pub fn retry_policy(mut self, v: RetryPolicy) -> Self {
+        self.retry_policy = v;
+        self
+    }
+
+    /// Connect to a specific Docker Engine. See DockerEngineClient::with_server.
+    pub fn server<U: ToString>(mut self, uri_or_unix_socket: U) -> Self {
+        self.target = DockerEngineTarget::Server(uri_or_unix_socket.to_string());
+        self
+    }
+
+    /// Time limit for requests that last as long as a task in the Docker Engine, such as
+    /// pulling, pushing or building an image, committing a container, reading container logs,
+    /// waiting for a container, or running an exec.
+    pub fn streaming_timeout(mut self, v: Duration) -> Self {
+        self.streaming_timeout = Some(v);
+        self
+    }
+
+    /// Connect to a TLS-secured Docker Engine. See DockerEngineClient::with_tls_config.
+    #[cfg_attr(not(any(feature = "native-tls", feature = "rustls")), allow(unreachable_code, unused_mut, unused_variables))]
+    pub fn tls_config<U: ToString, T: Into<TlsConfig>>(mut self, https_url: U, tls: T) -> Self {
+        self.target = DockerEngineTarget::TlsConfig(https_url.to_string(), tls.into());
+        self
+    }
+
+    /// Connect to a TLS-secured Docker Engine. See DockerEngineClient::with_tls_files.
+    pub fn tls_files<U: ToString>(mut self, url: U, files: TlsPemFiles) -> Self {
+        self.target = DockerEngineTarget::TlsFiles(url.to_string(), files);
+        self
+    }
+
+    /// Save every request and its response to a cassette file, to be played back later by
+    /// fake::ReplayTransport. Applies to the custom transport too, if one is set.
+    ///
+    /// Requires the test-util cargo feature.
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn record<P: Into<std::path::PathBuf>>(mut self, cassette: P) -> Self {
+        self.record = Some(cassette.into());
+        self
+    }
+
+    /// Send requests with a custom transport, instead of connecting with Hyper. Settings of
+    /// the connection, including the proxy and TLS, then do not apply.
+    ///
+    /// Unless a server or context is set, request urls use http://localhost.
+    pub fn transport<T: DockerEngineTransport + 'static>(mut self, v: T) -> Self {
+        self.transport = Some(Arc::new(v));
+        self
+    }
+
+    /// User-Agent header to send. Defaults to the name and version of this library.
+    pub fn user_agent<V: Into<String>>(mut self, v: V) -> Self {
+        self.user_agent = Some(v.into());
+        self
+    }
+
+    fn default_headers(&self) -> Result<HeaderMap, DecCreateError> {
+        let mut result = HeaderMap::new();
+
+        let user_agent = self.user_agent
+            .as_deref()
+            .unwrap_or(DEFAULT_USER_AGENT);
+
+        result.insert(USER_AGENT, Self::header_value(USER_AGENT.as_str(), user_agent)?);
+
+        for (name, value) in &self.headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| DecCreateError::InvalidHeader {
+                    name: name.clone(),
+                    message: e.to_string()
+                })?;
+
+            result.insert(header_name, Self::header_value(name, value)?);
+        }
+
+        Ok(result)
+    }
+
+    fn header_value(name: &str, value: &str) -> Result<HeaderValue, DecCreateError> {
+        HeaderValue::from_str(value)
+            .map_err(|e| DecCreateError::InvalidHeader {
+                name: name.to_string(),
+                message: e.to_string()
+            })
+    }
+
+    /// Configure the client. No connection is made to server until first request.
+    pub fn build(mut self) -> Result<DockerEngineClient, DecCreateError> {
+        let mut default_headers = self.default_headers()?;
+
+        let endpoint = match self.target {
+            DockerEngineTarget::Environment if self.transport.is_some() =>
+                DockerEngineEndpoint::plain(TRANSPORT_URL.to_string()),
+            DockerEngineTarget::Environment =>
+                DockerEngineClient::environment_endpoint()?,
+            DockerEngineTarget::Context(name) =>
+                DockerEngineClient::context_endpoint(&name)?,
+            DockerEngineTarget::Server(url) =>
+                DockerEngineEndpoint::plain(url),
+            DockerEngineTarget::TlsConfig(url, tls) =>
+                DockerEngineEndpoint {
+                    url,
+                    tls: Some(tls)
+                },
+            DockerEngineTarget::TlsFiles(url, files) =>
+                DockerEngineClient::tls_files_endpoint(url, files)?
+        };
+
+        let server = DockerEngineServer::new(endpoint.url)?;
+
+        let transport: Arc<dyn DockerEngineTransport> = match self.transport {
+            Some(transport) =>
+                transport,
+            None => {
+                let proxy = self.proxy
+                    .unwrap_or_else(Proxy::from_env)
+                    .settings()?;
+
+                self.connection.proxy = Arc::new(proxy);
+
+                Arc::new(Self::hyper_client(endpoint.tls, &server, &self.connection, &mut default_headers)?)
+            }
+        };
+
+        #[cfg(any(test, feature = "test-util"))]
+        let transport: Arc<dyn DockerEngineTransport> = match self.record {
+            Some(cassette) => Arc::new(crate::fake::RecordingTransport::new(transport, cassette)),
+            None => transport
+        };
+
+        let settings = DockerEngineHttpSettings {
+            connect_timeout: self.connection.connect_timeout,
+            request_timeout: self.request_timeout,
+            streaming_timeout: self.streaming_timeout,
+            default_headers,
+            retry_policy: self.retry_policy
+        };
+
+        let url = match &self.api_version {
+            None => DockerEngineApi::new(server),
+            Some(version) => DockerEngineApi::with_version(server, version)
+        };
+
+        Ok(DockerEngineClient {
+            http: DockerEngineHttpClient::new(transport, settings),
+            registry_auth: None,
+            url
+        })
+    }
+
+    // Without a TLS feature, TlsConfig has no variants and a TLS endpoint cannot exist.
+    #[cfg_attr(not(any(feature = "native-tls", feature = "rustls")), allow(unreachable_code, unused_variables))]
+    fn hyper_client(
+        tls: Option<TlsConfig>,
+        server: &DockerEngineServer,
+        connection: &HyperHttpClientSettings,
+        default_headers: &mut HeaderMap
+    ) -> Result<HyperHttpClient, DecCreateError>
+    {
+        let result = match tls {
+            Some(tls) =>
+                HyperHttpClient::https(tls, connection),
+
+            None => match server.base.implied_url() {
+                SchemedUrl::Http(url) => {
+                    // A forwarding proxy authenticates each request; a tunnel only the CONNECT.
+                    let proxy_authorization = url.parse::<Uri>()
+                        .ok()
+                        .and_then(|uri| connection.proxy.for_uri(&uri).cloned())
+                        .and_then(|server| server.authorization);
+
+                    if let Some(authorization) = proxy_authorization {
+                        default_headers.insert(PROXY_AUTHORIZATION, Self::header_value(PROXY_AUTHORIZATION.as_str(), &authorization)?);
+                    }
+
+                    HyperHttpClient::http(connection)
+                },
+
+                SchemedUrl::Https(_) =>
+                    HyperHttpClient::https(default_tls_config()?, connection),
+
+                SchemedUrl::Ssh(destination) =>
+                    HyperHttpClient::ssh(destination, connection),
+
+                #[cfg(unix)]
+                SchemedUrl::Unix(_) =>
+                    HyperHttpClient::unix(connection),
+            }
+        };
+
+        Ok(result)
+    }
+
+}
+
+#[cfg(test)]
+mod test_docker_engine_client_builder {
+    use std::time::Duration;
+
+    use hyper::StatusCode;
+
+    use super::DockerEngineClientBuilder;
+    use crate::errors::DecUseError;
+    use crate::model::Proxy;
+
+    #[test]
+    fn api_version_adds_prefix() {
+        let builder = DockerEngineClientBuilder::new()
+            .api_version("1.43");
+
+        assert_eq!(Some("v1.43".to_string()), builder.api_version);
+    }
+
+    #[test]
+    fn fails_with_invalid_header() {
+        let actual = DockerEngineClientBuilder::new()
+            .server("http://foo")
+            .header("X-Custom", "line\nbreak")
+            .build()
+            .unwrap_err();
+
+        assert_eq!("Invalid HTTP header X-Custom: failed to parse header value", actual.to_string());
+    }
+
+    #[test]
+    fn fails_with_invalid_proxy() {
+        let actual = DockerEngineClientBuilder::new()
+            .server("http://foo")
+            .proxy(Proxy::all("socks5://proxy.locallan"))
+            .build()
+            .unwrap_err();
+
+        assert_eq!("Invalid proxy url socks5://proxy.locallan: only http:// proxies are supported", actual.to_string());
+    }
+
+    #[tokio::test]
+    async fn sends_through_proxy() {
+        // Stands in for a forward proxy; it matches on the path of the absolute url.
+        let mut proxy = mockito::Server::new_async().await;
+
+        let mock = proxy.mock("GET", "/v1.43/version")
+            .match_header("host", "engine.locallan:2375")
+            // alice:secret
+            .match_header("proxy-authorization", "Basic YWxpY2U6c2VjcmV0")
+            .with_status(200)
+            .create_async()
+            .await;
+
+        let dec = DockerEngineClientBuilder::new()
+            .server("http://engine.locallan:2375")
+            .api_version("v1.43")
+            .proxy(Proxy::all(format!("http://alice:secret@{}", proxy.host_with_port())))
+            .build()
+            .unwrap();
+
+        let response = dec.http
+            .get(dec.url.version())
+            .unwrap()
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(StatusCode::OK, response.status);
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn sends_api_version_and_headers() {
+        let mut server = mockito::Server::new_async().await;
+
+        let mock = server.mock("GET", "/v1.43/version")
+            .match_header("user-agent", "test-agent/2")
+            .match_header("x-custom", "abc")
+            .with_status(200)
+            .with_header("Content-Type", "application/json")
+            .with_body(r#"{"Version": "24.0.0", "ApiVersion": "1.43", "MinAPIVersion": "1.12", "Os": "linux", "Arch": "amd64"}"#)
+            .create_async()
+            .await;
+
+        let dec = DockerEngineClientBuilder::new()
+            .server(server.url())
+            .api_version("v1.43")
+            .user_agent("test-agent/2")
+            .header("X-Custom", "abc")
+            .build()
+            .unwrap();
+
+        let response = dec.http
+            .get(dec.url.version())
+            .unwrap()
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(StatusCode::OK, response.status);
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn times_out() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap();
+
+        let address = listener.local_addr()
+            .unwrap();
+
+        // Accept the connection, but never respond.
+        let _server = tokio::spawn(async move {
+            let (_socket, _) = listener.accept().await.unwrap();
+
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+
+        let dec = DockerEngineClientBuilder::new()
+            .server(format!("http://{}", address))
+            .request_timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+
+        let actual = dec.version()
+            .await
+            .unwrap_err();
+
+        match actual {
+            DecUseError::Timeout { timeout, .. } =>
+                assert_eq!(Duration::from_millis(100), timeout),
+            other =>
+                panic!("Unexpected error: {}", other)
+        }
+    }
+}
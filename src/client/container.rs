@@ -1,20 +1,74 @@
+use std::borrow::Cow;
+use std::io::ErrorKind;
+
 use hyper::StatusCode;
+use time::OffsetDateTime;
 
 use crate::client::container_files::DecContainerFiles;
 use crate::client::shared::parse_container_log;
 use crate::DockerEngineClient;
 use crate::errors::DecUseError;
-use crate::requests::{CreateExecRequest, InspectContainerArgs, LogsArgs, RemoveContainerArgs, WaitCondition};
-use crate::responses::{CreateExecResponse, InspectContainerResponse, TopResponse, WaitResponse};
-use crate::model::{StreamLine, TsStreamLine};
+use crate::requests::{CommitRequest, CreateExecRequest, InspectContainerArgs, LogsArgs, RemoveContainerArgs, WaitCondition};
+use crate::responses::{CommitResponse, CreateExecResponse, InspectContainerResponse, TopResponse, WaitResponse};
+use crate::model::{Cancellation, StreamLine, StreamLineReadError, TsStreamLine};
 
+#[derive(Clone)]
 pub struct DecContainer<'a> {
-    pub(super) client: &'a DockerEngineClient,
+    pub(super) cancellation: Cancellation,
+    pub(super) client: Cow<'a, DockerEngineClient>,
     pub(super) container_id: String
 }
 
+/// A DecContainer that owns a clone of its client, instead of borrowing it, so that it
+/// can be moved into a spawned task or stored in a struct. See DecContainer::into_owned.
+pub type ContainerHandle = DecContainer<'static>;
+
 impl <'a> DecContainer<'a> {
 
+    /// Create a new image from the current state of a container.
+    ///
+    /// Committing copies the container's filesystem, so it is subject to the streaming timeout
+    /// rather than the request timeout, and stops when the container's cancellation does.
+    ///
+    /// See https://docs.docker.com/engine/api/v1.41/#tag/Image/operation/ImageCommit
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::errors::DecError;
+    /// use passivized_docker_engine_client::requests::CommitRequest;
+    ///
+    /// async fn example() -> Result<(), DecError> {
+    ///     let dec = DockerEngineClient::new()?;
+    ///
+    ///     let request = CommitRequest::default()
+    ///         .repo("seeded-db")
+    ///         .tag("latest")
+    ///         .comment("Database with test data loaded");
+    ///
+    ///     let committed = dec.container("example").commit(request).await?;
+    ///
+    ///     println!("New image id: {}", committed.id);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn commit(&self, request: CommitRequest) -> Result<CommitResponse, DecUseError> {
+        let uri = self.client.url.containers().commit(&self.container_id, &request)?;
+
+        let http_request = match &request.config {
+            None => self.client.http.post(uri)?,
+            Some(config) => self.client.http.post_json(uri, config)?
+        };
+
+        let response = http_request.execute_streaming(&self.cancellation).await?;
+
+        response
+            .assert_item_status(StatusCode::CREATED)?
+            .parse()
+    }
+
     /// Create a command to run within a container, but don't start or execute the command.
     pub async fn create_exec(&self, request: CreateExecRequest) -> Result<CreateExecResponse, DecUseError> {
         let uri = self.client.url.containers().create_exec(&self.container_id);
@@ -28,7 +82,7 @@ impl <'a> DecContainer<'a> {
     /// Work with files inside a container.
     pub fn files(&'_ self) -> DecContainerFiles<'_> {
         DecContainerFiles {
-            client: self.client,
+            client: &self.client,
             container_id: &self.container_id
         }
     }
@@ -68,15 +122,62 @@ impl <'a> DecContainer<'a> {
         Ok(results)
     }
 
+    /// Read the lines logged after those already read through a tail, without their timestamps.
+    pub(crate) async fn logs_after(&self, tail: &mut LogTail) -> Result<Vec<StreamLine>, DecUseError> {
+        let lines = self.logs_with(LogsArgs::default().timestamps().since(tail.since)).await?;
+
+        let mut repeated = tail.read_at_since;
+        let mut result = Vec::new();
+
+        for line in &lines {
+            let line = TsStreamLine::try_from(line)
+                .map_err(|message| StreamLineReadError::Io(std::io::Error::new(ErrorKind::InvalidData, message)))?;
+
+            if Some(line.timestamp) == tail.since && repeated > 0 {
+                repeated -= 1;
+                continue;
+            }
+
+            if Some(line.timestamp) == tail.since {
+                tail.read_at_since += 1;
+            }
+            else {
+                tail.since = Some(line.timestamp);
+                tail.read_at_since = 1;
+            }
+
+            result.push(StreamLine {
+                kind: line.kind,
+                text: line.text
+            });
+        }
+
+        Ok(result)
+    }
+
     async fn logs_with(&self, args: LogsArgs) -> Result<Vec<StreamLine>, DecUseError> {
         let uri = self.client.url.containers().logs(&self.container_id, args)?;
-        let response = self.client.http.get(uri)?.execute().await?;
+        let response = self.client.http.get(uri)?.execute_streaming(&self.cancellation).await?;
 
         response
             .assert_item_status(StatusCode::OK)?
             .parse_with(parse_container_log)
     }
 
+    /// ID or name of the container.
+    pub fn id(&self) -> &str {
+        &self.container_id
+    }
+
+    /// Convert into a ContainerHandle.
+    pub fn into_owned(self) -> ContainerHandle {
+        DecContainer {
+            cancellation: self.cancellation,
+            client: Cow::Owned(self.client.into_owned()),
+            container_id: self.container_id
+        }
+    }
+
     /// Inspect a container.
     ///
     /// # Example
@@ -264,7 +365,7 @@ impl <'a> DecContainer<'a> {
 
     /// Start an existing container.
     ///
-    /// This is idempotent.
+    /// This is idempotent. Use start_checked to find out whether the container was already started.
     ///
     /// # Example
     ///
@@ -281,22 +382,41 @@ impl <'a> DecContainer<'a> {
     /// }
     /// ```
     pub async fn start(&self) -> Result<(), DecUseError> {
+        ignore_not_modified(self.start_checked().await)
+    }
+
+    /// Start an existing container, failing with DecUseError::NotModified if it was already started.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::errors::{DecError, DecUseError};
+    ///
+    /// async fn example() -> Result<(), DecError> {
+    ///     let dec = DockerEngineClient::new()?;
+    ///
+    ///     match dec.container("example").start_checked().await {
+    ///         Ok(()) => println!("Started"),
+    ///         Err(DecUseError::NotModified { .. }) => println!("Already started"),
+    ///         Err(other) => return Err(other.into())
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn start_checked(&self) -> Result<(), DecUseError> {
         let uri = self.client.url.containers().start(&self.container_id);
         let response = self.client.http.post(uri)?.execute().await?;
 
+        // See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerStart
         response
-            // See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerStart
-            .assert_unit_status_in(&[
-                // No error
-                StatusCode::NO_CONTENT,
-                // Already started
-                StatusCode::NOT_MODIFIED
-            ])
+            .assert_unit_status(StatusCode::NO_CONTENT)
     }
 
     /// Stop a running container.
     ///
-    /// This is idempotent.
+    /// This is idempotent. Use stop_checked to find out whether the container was already stopped.
     ///
     /// # Example
     ///
@@ -313,17 +433,17 @@ impl <'a> DecContainer<'a> {
     /// }
     /// ```
     pub async fn stop(&self) -> Result<(), DecUseError> {
+        ignore_not_modified(self.stop_checked().await)
+    }
+
+    /// Stop a running container, failing with DecUseError::NotModified if it was already stopped.
+    pub async fn stop_checked(&self) -> Result<(), DecUseError> {
         let uri = self.client.url.containers().stop(&self.container_id);
         let response = self.client.http.post(uri)?.execute().await?;
 
+        // See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerStop
         response
-            // See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerStop
-            .assert_unit_status_in(&[
-                // No error
-                StatusCode::NO_CONTENT,
-                // Already stopped
-                StatusCode::NOT_MODIFIED
-            ])
+            .assert_unit_status(StatusCode::NO_CONTENT)
     }
 
     /// Get a list of processes running inside the container.
@@ -394,11 +514,101 @@ impl <'a> DecContainer<'a> {
     /// ```
     pub async fn wait(&self, condition: WaitCondition) -> Result<WaitResponse, DecUseError> {
         let uri = self.client.url.containers().wait(&self.container_id, condition);
-        let response = self.client.http.post(uri)?.execute().await?;
+        let response = self.client.http.post(uri)?.execute_streaming(&self.cancellation).await?;
 
         response
             .assert_item_status(StatusCode::OK)?
             .parse()
     }
 
-}
\ No newline at end of file
+    /// Stop reading logs, or waiting, at a deadline or when a token is cancelled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::errors::DecError;
+    /// use passivized_docker_engine_client::model::Cancellation;
+    /// use passivized_docker_engine_client::requests::WaitCondition;
+    ///
+    /// async fn example() -> Result<(), DecError> {
+    ///     let dec = DockerEngineClient::new()?;
+    ///
+    ///     dec.container("example")
+    ///         .with_cancellation(Cancellation::default().timeout(Duration::from_secs(60)))
+    ///         .wait(WaitCondition::NotRunning)
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_cancellation(mut self, v: Cancellation) -> Self {
+        self.cancellation = v;
+        self
+    }
+
+}
+
+/// Treat DecUseError::NotModified as success, for requests that are idempotent.
+fn ignore_not_modified(result: Result<(), DecUseError>) -> Result<(), DecUseError> {
+    match result {
+        Err(DecUseError::NotModified { .. }) => Ok(()),
+        other => other
+    }
+}
+
+/// Position in a container's log, so that each read through it returns only the lines
+/// logged since the previous read. See DecContainer::logs_after.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct LogTail {
+    /// Timestamp of the last line read.
+    since: Option<OffsetDateTime>,
+
+    /// How many lines already read were logged at `since`. The Docker Engine returns them
+    /// again, because it includes lines logged at the time it is asked for.
+    read_at_since: usize
+}
+
+#[cfg(test)]
+mod test_container {
+
+    mod logs_after {
+        use crate::client::container::LogTail;
+        use crate::fake::FakeDockerEngine;
+        use crate::model::{StreamKind, StreamLine};
+        use crate::requests::CreateContainerRequest;
+
+        fn texts(lines: Vec<StreamLine>) -> Vec<String> {
+            lines
+                .into_iter()
+                .map(|line| line.text)
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn reads_only_new_lines() {
+            let engine = FakeDockerEngine::new().with_image("nginx:latest");
+            let dec = engine.client();
+
+            let created = dec.containers()
+                .create(CreateContainerRequest::default().image("nginx:latest"))
+                .await
+                .unwrap();
+
+            let container = dec.container(&created.id);
+            let mut tail = LogTail::default();
+
+            engine.append_log(&created.id, StreamKind::StdOut, "one\n");
+            engine.append_log(&created.id, StreamKind::StdErr, "two\n");
+
+            assert_eq!(vec!["one\n", "two\n"], texts(container.logs_after(&mut tail).await.unwrap()));
+            assert!(container.logs_after(&mut tail).await.unwrap().is_empty());
+
+            engine.append_log(&created.id, StreamKind::StdOut, "three\n");
+
+            assert_eq!(vec!["three\n"], texts(container.logs_after(&mut tail).await.unwrap()));
+        }
+    }
+}
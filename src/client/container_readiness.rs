@@ -0,0 +1,425 @@
+use std::time::{Duration, Instant};
+
+use hyper::StatusCode;
+use regex::Regex;
+
+use crate::client::DecContainer;
+use crate::client::container::LogTail;
+use crate::errors::DecUseError;
+use crate::imp::probe::{http_status, tcp_connects};
+use crate::model::Readiness;
+use crate::responses::{InspectContainerResponse, State};
+
+/// Delay between checks of a readiness condition.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Longest wait for a single connection or HTTP request of a readiness check.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// What a log condition has read of the container's log, across checks.
+#[derive(Default)]
+struct LogProgress {
+    tail: LogTail,
+
+    /// How many lines have been read.
+    lines: usize,
+
+    /// The last line read.
+    last: Option<String>,
+
+    /// Whether a line read has met the condition.
+    matched: bool
+}
+
+/// Outcome of one check of a readiness condition.
+enum Check {
+    Ready,
+
+    /// Not ready yet, with what was observed.
+    Pending(String),
+
+    /// Will never be ready.
+    Failed(String)
+}
+
+impl <'a> DecContainer<'a> {
+
+    /// Wait until a running container meets a readiness condition, by checking it repeatedly.
+    ///
+    /// Returns the container as inspected when it became ready. Fails with DecUseError::NotReady
+    /// if the container stops running, if the condition cannot be met (such as waiting for a
+    /// health check that the container does not have), or at the timeout. The error includes
+    /// what was last observed.
+    ///
+    /// Each check of a log condition reads only the lines logged since the previous check,
+    /// and tests each of them on its own.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use hyper::StatusCode;
+    ///
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::errors::DecError;
+    /// use passivized_docker_engine_client::model::Readiness;
+    ///
+    /// async fn example() -> Result<(), DecError> {
+    ///     let dec = DockerEngineClient::new()?;
+    ///
+    ///     let ready = Readiness::all(vec![
+    ///         Readiness::LogContains("ready for start up".into()),
+    ///         Readiness::port("5432")
+    ///     ]);
+    ///
+    ///     dec.container("example")
+    ///         .wait_until(&ready, Duration::from_secs(30))
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn wait_until(&self, readiness: &Readiness, timeout: Duration) -> Result<InspectContainerResponse, DecUseError> {
+        let deadline = Instant::now() + timeout;
+
+        let mut conditions = Vec::new();
+        flatten(readiness, &mut conditions);
+
+        let mut progress: Vec<LogProgress> = conditions
+            .iter()
+            .map(|_| LogProgress::default())
+            .collect();
+
+        loop {
+            let inspected = self.inspect().await?;
+
+            let check = if inspected.state.running {
+                self.check_all(&conditions, &mut progress, &inspected, deadline).await?
+            }
+            else {
+                Check::Failed(format!("the container is not running; its exit code is {}", inspected.state.exit_code))
+            };
+
+            match check {
+                Check::Ready =>
+                    return Ok(inspected),
+                Check::Failed(message) =>
+                    return Err(self.not_ready(message, inspected.state)),
+                Check::Pending(message) if Instant::now() >= deadline =>
+                    return Err(self.not_ready(
+                        format!("timed out after {:?} waiting until {}: {}", timeout, readiness, message),
+                        inspected.state
+                    )),
+                Check::Pending(_) =>
+                    tokio::time::sleep(POLL_INTERVAL.min(deadline.saturating_duration_since(Instant::now()))).await
+            }
+        }
+    }
+
+    /// Wait until the container's health check passes. See wait_until.
+    pub async fn wait_until_healthy(&self, timeout: Duration) -> Result<InspectContainerResponse, DecUseError> {
+        self.wait_until(&Readiness::Healthy, timeout).await
+    }
+
+    /// Wait until a line of the container's stdout or stderr matches a regular expression. See wait_until.
+    pub async fn wait_for_log(&self, pattern: Regex, timeout: Duration) -> Result<InspectContainerResponse, DecUseError> {
+        self.wait_until(&Readiness::LogMatches(pattern), timeout).await
+    }
+
+    /// Wait until a published container port, such as "80" or "53/udp", accepts TCP
+    /// connections. See wait_until and Readiness::Port.
+    pub async fn wait_for_port<P: Into<String>>(&self, container_port: P, timeout: Duration) -> Result<InspectContainerResponse, DecUseError> {
+        self.wait_until(&Readiness::port(container_port), timeout).await
+    }
+
+    /// Wait until a GET request for a path on a published container port returns a status.
+    /// See wait_until.
+    pub async fn wait_for_http<P: Into<String>, U: Into<String>>(&self, container_port: P, path: U, status: StatusCode, timeout: Duration) -> Result<InspectContainerResponse, DecUseError> {
+        self.wait_until(&Readiness::http(container_port, path, status), timeout).await
+    }
+
+    /// Check conditions in order, stopping at the first that is not met.
+    async fn check_all(&self, conditions: &[&Readiness], progress: &mut [LogProgress], inspected: &InspectContainerResponse, deadline: Instant) -> Result<Check, DecUseError> {
+        for (condition, progress) in conditions.iter().zip(progress) {
+            let probe_timeout = deadline
+                .saturating_duration_since(Instant::now())
+                .clamp(POLL_INTERVAL, PROBE_TIMEOUT);
+
+            match self.check(condition, progress, inspected, probe_timeout).await? {
+                Check::Ready => {},
+                other => return Ok(other)
+            }
+        }
+
+        Ok(Check::Ready)
+    }
+
+    async fn check(&self, condition: &Readiness, progress: &mut LogProgress, inspected: &InspectContainerResponse, probe_timeout: Duration) -> Result<Check, DecUseError> {
+        let check = match condition {
+            Readiness::Running | Readiness::All(_) =>
+                Check::Ready,
+
+            Readiness::LogContains(text) =>
+                self.check_log(progress, |line| line.contains(text.as_str())).await?,
+
+            Readiness::LogMatches(pattern) =>
+                self.check_log(progress, |line| pattern.is_match(line)).await?,
+
+            Readiness::Healthy =>
+                check_health(&inspected.state),
+
+            Readiness::Port(container_port) => match self.published_host(inspected, container_port) {
+                Err(failed) =>
+                    failed,
+                Ok((host, port)) => match tcp_connects(&host, port, probe_timeout).await {
+                    Ok(()) => Check::Ready,
+                    Err(message) => Check::Pending(message)
+                }
+            },
+
+            Readiness::Http { port: container_port, path, status } => match self.published_host(inspected, container_port) {
+                Err(failed) =>
+                    failed,
+                Ok((host, port)) => {
+                    let host = if host.contains(':') { format!("[{}]", host) } else { host };
+                    let separator = if path.starts_with('/') { "" } else { "/" };
+                    let url = format!("http://{}:{}{}{}", host, port, separator, path);
+
+                    match http_status(&url, probe_timeout).await {
+                        Ok(actual) if actual == *status => Check::Ready,
+                        Ok(actual) => Check::Pending(format!("GET {} returned {}", url, actual.as_u16())),
+                        Err(message) => Check::Pending(message)
+                    }
+                }
+            }
+        };
+
+        Ok(check)
+    }
+
+    async fn check_log<F: Fn(&str) -> bool>(&self, progress: &mut LogProgress, matches: F) -> Result<Check, DecUseError> {
+        if !progress.matched {
+            for line in self.logs_after(&mut progress.tail).await? {
+                progress.lines += 1;
+                progress.matched = progress.matched || matches(&line.text);
+                progress.last = Some(line.text);
+            }
+        }
+
+        let check = if progress.matched {
+            Check::Ready
+        }
+        else {
+            match &progress.last {
+                None => Check::Pending("the container has not logged anything".into()),
+                Some(last) => Check::Pending(format!("the last of {} log lines was {:?}", progress.lines, last.trim_end()))
+            }
+        };
+
+        Ok(check)
+    }
+
+    /// Host and port where a container port is published.
+    fn published_host(&self, inspected: &InspectContainerResponse, container_port: &str) -> Result<(String, u16), Check> {
+        match inspected.network_settings.host_port(container_port) {
+            None => Err(Check::Failed(format!("port {} is not published", container_port))),
+            Some(port) => Ok((self.client.url.published_host().to_string(), port))
+        }
+    }
+
+    fn not_ready(&self, message: String, last_state: State) -> DecUseError {
+        DecUseError::NotReady {
+            id: self.container_id.clone(),
+            message,
+            last_state: Box::new(last_state)
+        }
+    }
+
+}
+
+fn check_health(state: &State) -> Check {
+    let health = match &state.health {
+        None => return Check::Failed("the container has no health check".into()),
+        Some(health) => health
+    };
+
+    let last_output = health.log
+        .last()
+        .map(|result| format!("; the last check output was {:?}", result.output.trim_end()))
+        .unwrap_or_default();
+
+    match health.status.as_str() {
+        "healthy" => Check::Ready,
+        "unhealthy" => Check::Failed(format!("the health check failed{}", last_output)),
+        other => Check::Pending(format!("the health status is {}{}", other, last_output))
+    }
+}
+
+/// Conditions of a Readiness, without Readiness::All.
+fn flatten<'r>(readiness: &'r Readiness, into: &mut Vec<&'r Readiness>) {
+    match readiness {
+        Readiness::All(conditions) => {
+            for condition in conditions {
+                flatten(condition, into);
+            }
+        },
+        other =>
+            into.push(other)
+    }
+}
+
+#[cfg(test)]
+mod test_container_readiness {
+
+    mod wait_until {
+        use std::time::Duration;
+
+        use hyper::StatusCode;
+        use regex::Regex;
+
+        use crate::DockerEngineClient;
+        use crate::errors::DecUseError;
+        use crate::fake::FakeDockerEngine;
+        use crate::model::{ContainerId, Readiness, StreamKind};
+        use crate::requests::{CreateContainerRequest, HostConfig};
+
+        const TIMEOUT: Duration = Duration::from_secs(5);
+
+        async fn started(dec: &DockerEngineClient, host_config: HostConfig) -> ContainerId {
+            let created = dec.containers()
+                .create(CreateContainerRequest::default()
+                    .image("nginx:latest")
+                    .host_config(host_config)
+                )
+                .await
+                .unwrap();
+
+            dec.container(&created.id).start().await.unwrap();
+
+            created.id
+        }
+
+        fn not_ready(error: DecUseError) -> (String, bool) {
+            match error {
+                DecUseError::NotReady { message, last_state, .. } => (message, last_state.running),
+                other => panic!("Unexpected error: {:?}", other)
+            }
+        }
+
+        #[tokio::test]
+        async fn fails_when_exited() {
+            let engine = FakeDockerEngine::new().with_image("nginx:latest");
+            let dec = engine.client();
+            let id = started(&dec, HostConfig::default()).await;
+
+            engine.exit_container(&id, 2);
+
+            let (message, running) = not_ready(dec.container(&id).wait_until(&Readiness::Running, TIMEOUT).await.unwrap_err());
+
+            assert_eq!("the container is not running; its exit code is 2", message);
+            assert!(!running);
+        }
+
+        #[tokio::test]
+        async fn fails_without_health_check() {
+            let engine = FakeDockerEngine::new().with_image("nginx:latest");
+            let dec = engine.client();
+            let id = started(&dec, HostConfig::default()).await;
+
+            let (message, running) = not_ready(dec.container(&id).wait_until_healthy(TIMEOUT).await.unwrap_err());
+
+            assert_eq!("the container has no health check", message);
+            assert!(running);
+        }
+
+        #[tokio::test]
+        async fn waits_for_health() {
+            let engine = FakeDockerEngine::new().with_image("nginx:latest");
+            let dec = engine.client();
+            let id = started(&dec, HostConfig::default()).await;
+
+            engine.set_health(&id, "starting");
+
+            let waiting = {
+                let container = dec.container(&id).into_owned();
+
+                tokio::spawn(async move {
+                    container.wait_until_healthy(TIMEOUT).await
+                })
+            };
+
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            assert!(!waiting.is_finished());
+
+            engine.set_health(&id, "healthy");
+
+            let inspected = waiting.await.unwrap().unwrap();
+
+            assert_eq!("healthy", inspected.state.health.unwrap().status);
+        }
+
+        #[tokio::test]
+        async fn waits_for_log() {
+            let engine = FakeDockerEngine::new().with_image("nginx:latest");
+            let dec = engine.client();
+            let id = started(&dec, HostConfig::default()).await;
+
+            engine.append_log(&id, StreamKind::StdOut, "starting\n");
+
+            let pattern = Regex::new(r"(?m)^listening on \d+$").unwrap();
+
+            let (message, _) = not_ready(dec.container(&id).wait_for_log(pattern.clone(), Duration::from_millis(250)).await.unwrap_err());
+
+            assert_eq!(
+                "timed out after 250ms waiting until log matches /(?m)^listening on \\d+$/: the last of 1 log lines was \"starting\"",
+                message
+            );
+
+            engine.append_log(&id, StreamKind::StdErr, "listening on 80\n");
+
+            dec.container(&id).wait_for_log(pattern, TIMEOUT).await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn waits_for_http_and_port() {
+            let mut server = mockito::Server::new_async().await;
+
+            server.mock("GET", "/health")
+                .with_status(204)
+                .create_async()
+                .await;
+
+            let server_port = server.socket_address().port();
+
+            let engine = FakeDockerEngine::new().with_image("nginx:latest");
+            let dec = engine.client();
+            let id = started(&dec, HostConfig::default().bind_port("80/tcp", server_port.to_string())).await;
+
+            let ready = Readiness::all(vec![
+                Readiness::port("80"),
+                Readiness::http("80", "health", StatusCode::NO_CONTENT)
+            ]);
+
+            dec.container(&id).wait_until(&ready, TIMEOUT).await.unwrap();
+
+            let (message, _) = not_ready(
+                dec.container(&id)
+                    .wait_for_http("80", "/health", StatusCode::OK, Duration::from_millis(250))
+                    .await
+                    .unwrap_err()
+            );
+
+            assert_eq!(
+                format!("timed out after 250ms waiting until GET /health on port 80 returns 200: GET http://localhost:{}/health returned 204", server_port),
+                message
+            );
+
+            let (message, _) = not_ready(dec.container(&id).wait_for_port("443", TIMEOUT).await.unwrap_err());
+
+            assert_eq!("port 443 is not published", message);
+        }
+
+    }
+
+}
@@ -1,14 +1,29 @@
+use std::borrow::Cow;
+use std::time::Duration;
+
 use hyper::StatusCode;
+use log::warn;
 
 use crate::DockerEngineClient;
+use crate::client::{ContainerHandle, DecContainer};
+use crate::client::container::LogTail;
+use crate::client::shared::{remove_container_blocking, split_image};
 use crate::errors::DecUseError;
-use crate::requests::{CreateContainerRequest, ListContainersRequest};
-use crate::responses::{CreateContainerResponse, ListedContainer};
+use crate::requests::{CreateContainerRequest, ListContainersRequest, RemoveContainerArgs, RunOptions, WaitCondition};
+use crate::responses::{CreateContainerResponse, ListedContainer, RunOutput};
+
+/// How often DecContainers::run checks whether the container has exited, and reads its output.
+const RUN_POLL_INTERVAL: Duration = Duration::from_millis(250);
 
+#[derive(Clone)]
 pub struct DecContainers<'a> {
-    pub(super) client: &'a DockerEngineClient
+    pub(super) client: Cow<'a, DockerEngineClient>
 }
 
+/// A DecContainers that owns a clone of its client, instead of borrowing it, so that it
+/// can be moved into a spawned task or stored in a struct. See DecContainers::into_owned.
+pub type ContainersHandle = DecContainers<'static>;
+
 impl <'a> DecContainers<'a> {
 
     /// Create a container and return its ID.
@@ -38,7 +53,7 @@ impl <'a> DecContainers<'a> {
     /// }
     /// ```
     pub async fn create(&self, request: CreateContainerRequest) -> Result<CreateContainerResponse, DecUseError> {
-        let uri = self.client.url.containers().create(request.name.as_ref());
+        let uri = self.client.url.containers().create(request.name.as_ref(), request.platform.as_ref())?;
         let response = self.client.http.post_json(uri, &request)?.execute().await?;
 
         response
@@ -46,6 +61,44 @@ impl <'a> DecContainers<'a> {
             .parse()
     }
 
+    /// Create a container, and return an owned handle to it.
+    ///
+    /// Warnings returned by the Docker Engine are logged; use create to inspect them instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::errors::DecError;
+    /// use passivized_docker_engine_client::requests::CreateContainerRequest;
+    ///
+    /// async fn example() -> Result<(), DecError> {
+    ///     let dec = DockerEngineClient::new()?;
+    ///
+    ///     let request = CreateContainerRequest::default()
+    ///         .image("nginx");
+    ///
+    ///     let container = dec.containers().create_handle(request).await?;
+    ///
+    ///     let started = tokio::spawn(async move {
+    ///         container.start().await
+    ///     });
+    ///
+    ///     started.await.unwrap()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_handle(&self, request: CreateContainerRequest) -> Result<ContainerHandle, DecUseError> {
+        let response = self.create(request).await?;
+
+        for warning in &response.warnings {
+            warn!("Docker Engine warning for new container {}: {}", response.id, warning);
+        }
+
+        Ok(self.client.container(response.id).into_owned())
+    }
+
     /// Get a list of containers that meet the filter criteria.
     pub async fn list(&self, request: ListContainersRequest) -> Result<Vec<ListedContainer>, DecUseError> {
         let uri = self.client.url.containers().list(request)?;
@@ -56,4 +109,412 @@ impl <'a> DecContainers<'a> {
             .parse()
     }
 
-}
\ No newline at end of file
+    /// Convert into a ContainersHandle.
+    pub fn into_owned(self) -> ContainersHandle {
+        DecContainers {
+            client: Cow::Owned(self.client.into_owned())
+        }
+    }
+
+    /// Run a container to completion, similar to `docker run --rm`, and return its exit code
+    /// and output.
+    ///
+    /// Pulls the image if it is not present, creates and starts the container, and waits for
+    /// it to exit. The container, and its anonymous volumes, are always removed afterwards:
+    /// when run returns, whether or not it succeeded, and when its future is dropped before
+    /// completing.
+    ///
+    /// Waiting uses WaitCondition::NextExit, and also checks the container's state, so that
+    /// a container that exits before the wait reaches the Docker Engine is not missed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::errors::DecError;
+    /// use passivized_docker_engine_client::requests::{CreateContainerRequest, RunOptions};
+    ///
+    /// async fn example() -> Result<(), DecError> {
+    ///     let dec = DockerEngineClient::new()?;
+    ///
+    ///     let request = CreateContainerRequest::default()
+    ///         .image("busybox")
+    ///         .cmd(vec!["echo", "Hello, world."]);
+    ///
+    ///     let output = dec.containers().run(request, RunOptions::default()).await?;
+    ///
+    ///     println!("Exited with {}, and wrote {}", output.exit_code, output.stdout());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn run(&self, request: CreateContainerRequest, options: RunOptions) -> Result<RunOutput, DecUseError> {
+        if options.pull {
+            let image = request.image
+                .as_deref()
+                .unwrap_or_default();
+
+            let (repo, tag) = split_image(image);
+
+            self.client.images()
+                .with_cancellation(options.cancellation.clone())
+                .pull_if_not_present(repo, tag)
+                .await?;
+        }
+
+        let created = self.create(request).await?;
+
+        // From here on, dropping the guard removes the container.
+        let mut guard = RemoveOnDrop {
+            client: self.client.clone().into_owned(),
+            id: Some(created.id.into_string())
+        };
+
+        let container = self.client
+            .container(guard.id())
+            .with_cancellation(options.cancellation.clone());
+
+        let result = run_to_exit(&container, &options).await;
+
+        let args = RemoveContainerArgs::default()
+            .force(true)
+            .remove_volumes(true);
+
+        let removed = match container.remove_with(args).await {
+            Ok(()) | Err(DecUseError::NotFound { .. }) => Ok(()),
+            Err(other) => Err(other)
+        };
+
+        guard.disarm();
+
+        match (result, removed) {
+            (Ok(output), Ok(())) => Ok(output),
+            (Ok(_), Err(e)) => Err(e),
+            (Err(e), removed) => {
+                if let Err(failed) = removed {
+                    warn!("Failed to remove container {}: {}", container.id(), failed.error_message());
+                }
+
+                Err(e)
+            }
+        }
+    }
+
+}
+
+/// Start a created container, send its output while it runs, and wait until it exits.
+async fn run_to_exit(container: &DecContainer<'_>, options: &RunOptions) -> Result<RunOutput, DecUseError> {
+    container.start().await?;
+
+    let waited = container.wait(WaitCondition::NextExit);
+    tokio::pin!(waited);
+
+    let mut interval = tokio::time::interval(RUN_POLL_INTERVAL);
+    let mut tail = LogTail::default();
+
+    let exit_code = loop {
+        tokio::select! {
+            result = &mut waited =>
+                break result?.exit_code(),
+            _ = interval.tick() => {
+                send_output(container, options, &mut tail).await?;
+
+                let state = container.inspect().await?.state;
+
+                if !state.running && !state.restarting {
+                    break state.exit_code;
+                }
+            }
+        }
+    };
+
+    send_output(container, options, &mut tail).await?;
+
+    let output = if options.collect_output {
+        container.logs().await?
+    }
+    else {
+        Vec::new()
+    };
+
+    Ok(RunOutput {
+        exit_code,
+        output
+    })
+}
+
+/// Send the lines logged since those already sent, if the output is sent anywhere.
+async fn send_output(container: &DecContainer<'_>, options: &RunOptions, tail: &mut LogTail) -> Result<(), DecUseError> {
+    if let Some(sender) = &options.output_to {
+        for line in container.logs_after(tail).await? {
+            // The receiver may have stopped listening, which does not affect the run.
+            let _ = sender.send(line);
+        }
+    }
+
+    Ok(())
+}
+
+/// Removes a container when dropped, unless disarmed.
+struct RemoveOnDrop {
+    client: DockerEngineClient,
+    id: Option<String>
+}
+
+impl RemoveOnDrop {
+
+    fn disarm(&mut self) {
+        self.id = None;
+    }
+
+    fn id(&self) -> &str {
+        self.id
+            .as_deref()
+            .unwrap_or_default()
+    }
+
+}
+
+impl Drop for RemoveOnDrop {
+    fn drop(&mut self) {
+        if let Some(id) = &self.id {
+            remove_container_blocking(&self.client, id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod create_handle {
+        use const_str::concat;
+
+        use crate::DockerEngineClient;
+        use crate::client::ContainerHandle;
+        use crate::imp::api::DOCKER_ENGINE_VERSION_PATH;
+        use crate::imp::content_type;
+        use crate::requests::CreateContainerRequest;
+
+        #[tokio::test]
+        async fn handle_outlives_client_in_spawned_task() {
+            let mut server = mockito::Server::new_async().await;
+
+            server.mock("POST", concat!(DOCKER_ENGINE_VERSION_PATH, "/containers/create"))
+                .with_status(201)
+                .with_header("Content-Type", content_type::JSON)
+                .with_body(r#"{"Id": "abc123", "Warnings": []}"#)
+                .create_async()
+                .await;
+
+            let started = server.mock("POST", concat!(DOCKER_ENGINE_VERSION_PATH, "/containers/abc123/start"))
+                .with_status(204)
+                .create_async()
+                .await;
+
+            let handle: ContainerHandle = {
+                let dec = DockerEngineClient::with_server(server.url())
+                    .unwrap();
+
+                dec.containers()
+                    .create_handle(CreateContainerRequest::default().image("busybox"))
+                    .await
+                    .unwrap()
+            };
+
+            assert_eq!("abc123", handle.id());
+
+            tokio::spawn(async move { handle.start().await })
+                .await
+                .unwrap()
+                .unwrap();
+
+            started.assert_async().await;
+        }
+    }
+
+    mod run {
+        use std::time::Duration;
+
+        use crate::DockerEngineClient;
+        use crate::errors::DecUseError;
+        use crate::fake::FakeDockerEngine;
+        use crate::model::{Cancellation, CancellationToken, StreamKind};
+        use crate::requests::{CreateContainerRequest, ListContainersRequest, RunOptions};
+
+        fn request() -> CreateContainerRequest {
+            CreateContainerRequest::default()
+                .name("job")
+                .image("busybox:1.36")
+        }
+
+        async fn assert_removed(dec: &DockerEngineClient) {
+            let remaining = dec.containers()
+                .list(ListContainersRequest::default().all(true))
+                .await
+                .unwrap();
+
+            assert!(remaining.is_empty());
+        }
+
+        async fn until_running(dec: &DockerEngineClient) {
+            loop {
+                if let Ok(inspected) = dec.container("job").inspect().await {
+                    if inspected.state.running {
+                        return;
+                    }
+                }
+
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }
+
+        #[tokio::test]
+        async fn collects_output_and_removes() {
+            let engine = FakeDockerEngine::new()
+                .with_image("busybox:1.36");
+
+            let dec = engine.client();
+
+            let running = {
+                let dec = dec.clone();
+                tokio::spawn(async move {
+                    dec.containers()
+                        .run(request(), RunOptions::default().pull(false))
+                        .await
+                })
+            };
+
+            until_running(&dec).await;
+
+            engine.append_log("job", StreamKind::StdOut, "out\n");
+            engine.append_log("job", StreamKind::StdErr, "err\n");
+            engine.exit_container("job", 3);
+
+            let output = running.await
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(3, output.exit_code);
+            assert_eq!("out\n", output.stdout());
+            assert_eq!("err\n", output.stderr());
+
+            assert_removed(&dec).await;
+        }
+
+        #[tokio::test]
+        async fn pulls_missing_image() {
+            let engine = FakeDockerEngine::new();
+            let dec = engine.client();
+
+            let running = {
+                let dec = dec.clone();
+                tokio::spawn(async move {
+                    dec.containers()
+                        .run(request(), RunOptions::default())
+                        .await
+                })
+            };
+
+            until_running(&dec).await;
+
+            engine.exit_container("job", 0);
+
+            assert_eq!(0, running.await.unwrap().unwrap().exit_code);
+
+            let images = dec.images()
+                .list()
+                .await
+                .unwrap();
+
+            assert_eq!(vec!["busybox:1.36".to_string()], images[0].repo_tags);
+
+            assert_removed(&dec).await;
+        }
+
+        #[tokio::test]
+        async fn removes_when_cancelled() {
+            let engine = FakeDockerEngine::new()
+                .with_image("busybox:1.36");
+
+            let dec = engine.client();
+            let token = CancellationToken::new();
+
+            let running = {
+                let dec = dec.clone();
+                let options = RunOptions::default()
+                    .cancellation(Cancellation::default().token(token.clone()));
+
+                tokio::spawn(async move {
+                    dec.containers()
+                        .run(request(), options)
+                        .await
+                })
+            };
+
+            until_running(&dec).await;
+
+            token.cancel();
+
+            match running.await.unwrap().unwrap_err() {
+                DecUseError::Cancelled { .. } => {},
+                other => panic!("Unexpected error: {:?}", other)
+            }
+
+            assert_removed(&dec).await;
+        }
+
+        #[tokio::test]
+        async fn removes_when_dropped() {
+            let engine = FakeDockerEngine::new()
+                .with_image("busybox:1.36");
+
+            let dec = engine.client();
+
+            let containers = dec.containers();
+            let run = containers.run(request(), RunOptions::default());
+
+            assert!(tokio::time::timeout(Duration::from_millis(500), run).await.is_err());
+
+            assert_removed(&dec).await;
+        }
+
+        #[tokio::test]
+        async fn sends_output_while_running() {
+            let engine = FakeDockerEngine::new()
+                .with_image("busybox:1.36");
+
+            let dec = engine.client();
+            let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+
+            let running = {
+                let dec = dec.clone();
+                let options = RunOptions::default()
+                    .collect_output(false)
+                    .output_to(sender);
+
+                tokio::spawn(async move {
+                    dec.containers()
+                        .run(request(), options)
+                        .await
+                })
+            };
+
+            until_running(&dec).await;
+
+            engine.append_log("job", StreamKind::StdOut, "first\n");
+
+            assert_eq!("first\n", receiver.recv().await.unwrap().text);
+
+            engine.append_log("job", StreamKind::StdOut, "last\n");
+            engine.exit_container("job", 0);
+
+            let output = running.await
+                .unwrap()
+                .unwrap();
+
+            assert!(output.output.is_empty());
+            assert_eq!("last\n", receiver.recv().await.unwrap().text);
+            assert!(receiver.recv().await.is_none());
+        }
+    }
+}
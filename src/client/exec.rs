@@ -1,19 +1,41 @@
+use std::borrow::Cow;
+
 use hyper::StatusCode;
 
 use crate::client::shared::parse_container_log;
 use crate::DockerEngineClient;
 use crate::errors::DecUseError;
-use crate::model::StreamLine;
+use crate::model::{Cancellation, StreamLine};
 use crate::requests::ExecStartRequest;
 use crate::responses::ExecInspectResponse;
 
+#[derive(Clone)]
 pub struct DecExec<'a> {
-    pub(super) client: &'a DockerEngineClient,
+    pub(super) cancellation: Cancellation,
+    pub(super) client: Cow<'a, DockerEngineClient>,
     pub(super) exec_id: String
 }
 
+/// A DecExec that owns a clone of its client, instead of borrowing it, so that it
+/// can be moved into a spawned task or stored in a struct. See DecExec::into_owned.
+pub type ExecHandle = DecExec<'static>;
+
 impl <'a> DecExec<'a> {
 
+    /// ID of the container exec.
+    pub fn id(&self) -> &str {
+        &self.exec_id
+    }
+
+    /// Convert into a ExecHandle.
+    pub fn into_owned(self) -> ExecHandle {
+        DecExec {
+            cancellation: self.cancellation,
+            client: Cow::Owned(self.client.into_owned()),
+            exec_id: self.exec_id
+        }
+    }
+
     /// Get a description of an existing container command. The command does not
     /// need to be running; it only needs to be defined.
     pub async fn inspect(&self) -> Result<ExecInspectResponse, DecUseError> {
@@ -33,13 +55,19 @@ impl <'a> DecExec<'a> {
         assert!(!request.tty, "Attaching with a TTY is not currently supported by the Rust library.");
 
         let uri = self.client.url.exec().start(&self.exec_id);
-        let response = self.client.http.post_json(uri, &request)?.execute().await?;
+        let response = self.client.http.post_json(uri, &request)?.execute_streaming(&self.cancellation).await?;
 
         response
             .assert_item_status(StatusCode::OK)?
             .parse_with(parse_container_log)
     }
 
+    /// Stop running the exec at a deadline, or when a token is cancelled.
+    pub fn with_cancellation(mut self, v: Cancellation) -> Self {
+        self.cancellation = v;
+        self
+    }
+
 }
 
 
@@ -120,4 +148,115 @@ mod tests {
         }
 
     }
+
+    mod start_with_cancellation {
+        use std::time::Duration;
+
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+        use tokio::sync::oneshot;
+
+        use crate::DockerEngineClient;
+        use crate::errors::DecUseError;
+        use crate::model::{Cancellation, CancellationToken};
+        use crate::requests::ExecStartRequest;
+
+        /// Start a server that begins a streamed response and never finishes it. Reports
+        /// when the client closes the connection.
+        async fn never_finishing_server() -> (String, oneshot::Receiver<()>) {
+            let listener = TcpListener::bind("127.0.0.1:0")
+                .await
+                .unwrap();
+
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let (closed_sender, closed_receiver) = oneshot::channel();
+
+            tokio::spawn(async move {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = [0u8; 4096];
+
+                let _ = socket.read(&mut buffer).await.unwrap();
+
+                socket.write_all(b"HTTP/1.1 200 OK\r\nContent-Type: application/vnd.docker.raw-stream\r\nTransfer-Encoding: chunked\r\n\r\n")
+                    .await
+                    .unwrap();
+
+                while let Ok(n) = socket.read(&mut buffer).await {
+                    if n == 0 {
+                        break;
+                    }
+                }
+
+                let _ = closed_sender.send(());
+            });
+
+            (url, closed_receiver)
+        }
+
+        fn request() -> ExecStartRequest {
+            ExecStartRequest {
+                detach: false,
+                ..ExecStartRequest::default()
+            }
+        }
+
+        #[tokio::test]
+        async fn cancelled_by_token() {
+            let (url, closed) = never_finishing_server().await;
+            let dec = DockerEngineClient::with_server(url)
+                .unwrap();
+
+            let token = CancellationToken::new();
+            let canceller = token.clone();
+
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                canceller.cancel();
+            });
+
+            let error = dec.exec("some_exec_id")
+                .with_cancellation(Cancellation::default().token(token))
+                .start(request())
+                .await
+                .unwrap_err();
+
+            if let DecUseError::Cancelled { uri } = error {
+                assert!(uri.ends_with("/exec/some_exec_id/start"));
+            }
+            else {
+                panic!("Unexpected failure: {}", error);
+            }
+
+            tokio::time::timeout(Duration::from_secs(5), closed)
+                .await
+                .expect("Connection was not closed")
+                .unwrap();
+        }
+
+        #[tokio::test]
+        async fn stopped_at_deadline() {
+            let (url, closed) = never_finishing_server().await;
+            let dec = DockerEngineClient::with_server(url)
+                .unwrap();
+
+            let error = dec.exec("some_exec_id")
+                .with_cancellation(Cancellation::default().timeout(Duration::from_millis(100)))
+                .start(request())
+                .await
+                .unwrap_err();
+
+            if let DecUseError::Timeout { uri, .. } = error {
+                assert!(uri.ends_with("/exec/some_exec_id/start"));
+            }
+            else {
+                panic!("Unexpected failure: {}", error);
+            }
+
+            tokio::time::timeout(Duration::from_secs(5), closed)
+                .await
+                .expect("Connection was not closed")
+                .unwrap();
+        }
+
+    }
 }
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 
 use hyper::StatusCode;
@@ -5,14 +6,20 @@ use hyper::StatusCode;
 use crate::DockerEngineClient;
 use crate::errors::DecUseError;
 use crate::imp::content_type;
-use crate::model::Tar;
-use crate::requests::{BuildImageRequest, CreateImageRequest};
-use crate::responses::{BuildImageResponseStreamItem, ListedImage};
+use crate::model::{Cancellation, ImageRef, Platform, Tar};
+use crate::requests::{BuildImageRequest, CreateImageRequest, SearchImagesFilters};
+use crate::responses::{BuildImageResponseStreamItem, DistributionInspectResponse, InspectImageResponse, ListedImage, SearchedImage};
 
+#[derive(Clone)]
 pub struct DecImages<'a> {
-    pub(super) client: &'a DockerEngineClient
+    pub(super) cancellation: Cancellation,
+    pub(super) client: Cow<'a, DockerEngineClient>
 }
 
+/// A DecImages that owns a clone of its client, instead of borrowing it, so that it
+/// can be moved into a spawned task or stored in a struct. See DecImages::into_owned.
+pub type ImagesHandle = DecImages<'static>;
+
 impl <'a> DecImages<'a> {
 
     /// Build a new image.
@@ -33,7 +40,7 @@ impl <'a> DecImages<'a> {
                 content_type::TAR,
                 context.0
             )?
-            .execute()
+            .execute_streaming(&self.cancellation)
             .await?;
 
         let items = response
@@ -55,13 +62,71 @@ impl <'a> DecImages<'a> {
     /// this method.
     pub async fn create(&self, request: CreateImageRequest) -> Result<(), DecUseError> {
         let uri = self.client.url.images().create(request)?;
-        let response = self.client.http.post_with_auth(uri, &self.client.registry_auth)?.execute().await?;
+        let response = self.client.http.post_with_auth(uri, &self.client.registry_auth)?.execute_streaming(&self.cancellation).await?;
 
         // Pull responses return a JSON stream with status messages reporting progress, but we ignore the stream and wait for completion.
         response
             .assert_unit_status(StatusCode::OK)
     }
 
+    /// Get the manifest descriptor and supported platforms of an image from its
+    /// registry, without pulling it.
+    ///
+    /// The registry credentials set with `with_registry_auth`, if any, are sent
+    /// to the registry.
+    ///
+    /// See https://docs.docker.com/engine/api/v1.41/#tag/Distribution/operation/DistributionInspect
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::errors::DecError;
+    ///
+    /// async fn example() -> Result<(), DecError> {
+    ///     let dec = DockerEngineClient::new()?;
+    ///     let inspected = dec.images().distribution_inspect("nginx:latest").await?;
+    ///
+    ///     println!("Remote digest: {}", inspected.descriptor.digest);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn distribution_inspect<R: Into<String>>(&self, image_reference: R) -> Result<DistributionInspectResponse, DecUseError> {
+        let uri = self.client.url.distribution().inspect(image_reference);
+        let response = self.client.http.get_with_auth(uri, &self.client.registry_auth)?.execute().await?;
+
+        response
+            .assert_item_status(StatusCode::OK)?
+            .parse()
+    }
+
+    /// Get a description of an image stored by the Docker engine.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::errors::DecError;
+    ///
+    /// async fn example() -> Result<(), DecError> {
+    ///     let dec = DockerEngineClient::new()?;
+    ///     let image = dec.images().inspect("nginx:latest").await?;
+    ///
+    ///     println!("Image platform: {}", image.platform());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn inspect<ID: Into<ImageRef>>(&self, image_id_or_name: ID) -> Result<InspectImageResponse, DecUseError> {
+        let uri = self.client.url.images().inspect(image_id_or_name.into());
+        let response = self.client.http.get(uri)?.execute().await?;
+
+        response
+            .assert_item_status(StatusCode::OK)?
+            .parse()
+    }
+
     /// Get a list of the images stored by the Docker engine.
     ///
     /// # Example
@@ -137,20 +202,50 @@ impl <'a> DecImages<'a> {
         self.create(request).await
     }
 
+    /// Pull an image for a specific platform, such as an arm64 image onto an amd64
+    /// host for use with emulation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::errors::DecError;
+    /// use passivized_docker_engine_client::model::Platform;
+    ///
+    /// async fn example() -> Result<(), DecError> {
+    ///     let dec = DockerEngineClient::new()?;
+    ///
+    ///     dec.images().pull_with_platform("busybox", "latest", Platform::new("linux", "arm64")).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn pull_with_platform<R: Into<String>, T: Into<String>>(&self, repo: R, tag: T, platform: Platform) -> Result<(), DecUseError> {
+        let request = CreateImageRequest::default()
+            .image(repo)
+            .tag(tag)
+            .platform(platform);
+
+        self.create(request).await
+    }
+
     /// If an image does not exist in the Docker Engine, pull it; but if it
     /// already exists, do nothing.
     ///
+    /// Only the tag or digest is compared, not the platform. See pull_if_not_present_with_platform.
+    ///
     /// This is a convenience/wrapper method over multiple api calls.
     pub async fn pull_if_not_present<R: Into<String>, T: Into<String>>(&self, repo: R, tag: T) -> Result<DecImagesPullIfNotPresentResult, DecUseError> {
         let image_repo = repo.into();
         let image_tag = tag.into();
-        let sought = format!("{}:{}", image_repo, image_tag);
 
         let present = self.list().await?;
 
+        let sought = image_reference(&image_repo, &image_tag);
+
         let found = present
             .iter()
-            .any(|item| item.repo_tags.contains(&sought));
+            .any(|item| item.repo_tags.contains(&sought) || item.repo_digests.contains(&sought));
 
         if found {
            Ok(DecImagesPullIfNotPresentResult::AlreadyPresent)
@@ -162,35 +257,121 @@ impl <'a> DecImages<'a> {
         }
     }
 
+    /// If an image does not exist in the Docker Engine for a specific platform, pull
+    /// it; but if it already exists for that platform, do nothing.
+    ///
+    /// An image with the same tag but for a different platform is replaced.
+    ///
+    /// This is a convenience/wrapper method over multiple api calls.
+    pub async fn pull_if_not_present_with_platform<R: Into<String>, T: Into<String>>(&self, repo: R, tag: T, platform: Platform) -> Result<DecImagesPullIfNotPresentResult, DecUseError> {
+        let image_repo = repo.into();
+        let image_tag = tag.into();
+
+        let found = match self.inspect(image_reference(&image_repo, &image_tag)).await {
+            Ok(inspected) => inspected.platform().matches(&platform),
+            Err(DecUseError::NotFound { .. }) => false,
+            Err(other) => return Err(other)
+        };
+
+        if found {
+            Ok(DecImagesPullIfNotPresentResult::AlreadyPresent)
+        }
+        else {
+            self.pull_with_platform(image_repo, image_tag, platform).await?;
+
+            Ok(DecImagesPullIfNotPresentResult::Pulled)
+        }
+    }
+
     /// Copy an image from the Docker Engine to a Docker image registry.
     pub async fn push<R: Into<String>, T: Into<String>>(&self, repo: R, tag: T) -> Result<(), DecUseError> {
         let uri = self.client.url.images().push(repo, tag);
-        let response = self.client.http.post_with_auth(uri, &self.client.registry_auth)?.execute().await?;
+        let response = self.client.http.post_with_auth(uri, &self.client.registry_auth)?.execute_streaming(&self.cancellation).await?;
 
         response
             .assert_unit_status(StatusCode::OK)
     }
 
+    /// Search for images on Docker Hub (or the registry the Docker Engine is configured to search).
+    ///
+    /// See https://docs.docker.com/engine/api/v1.41/#tag/Image/operation/ImageSearch
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::errors::DecError;
+    /// use passivized_docker_engine_client::requests::SearchImagesFilters;
+    ///
+    /// async fn example() -> Result<(), DecError> {
+    ///     let dec = DockerEngineClient::new()?;
+    ///
+    ///     let filters = SearchImagesFilters::default()
+    ///         .is_official(true);
+    ///
+    ///     let found = dec.images().search("postgres", Some(10), filters).await?;
+    ///
+    ///     for image in found {
+    ///         println!("{} ({} stars): {}", image.name, image.star_count, image.description);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn search<T: Into<String>>(&self, term: T, limit: Option<usize>, filters: SearchImagesFilters) -> Result<Vec<SearchedImage>, DecUseError> {
+        let uri = self.client.url.images().search(term, limit, filters)?;
+        let response = self.client.http.get_with_auth(uri, &self.client.registry_auth)?.execute().await?;
+
+        response
+            .assert_list_status(StatusCode::OK)?
+            .parse()
+    }
+
     /// Tag an image that exists in the Docker Engine with an additional name or tag.
-    pub async fn tag<ID: Into<String>, R: Into<String>, T: Into<String>>(&self, image_id_or_name: ID, new_repo: R, new_tag: T) -> Result<(), DecUseError> {
-        let uri = self.client.url.images().tag(image_id_or_name, new_repo, new_tag);
+    pub async fn tag<ID: Into<ImageRef>, R: Into<String>, T: Into<String>>(&self, image_id_or_name: ID, new_repo: R, new_tag: T) -> Result<(), DecUseError> {
+        let uri = self.client.url.images().tag(image_id_or_name.into(), new_repo, new_tag);
         let response = self.client.http.post(uri)?.execute().await?;
 
         response
             .assert_unit_status(StatusCode::CREATED)
     }
 
+    /// Remove an image, with all of its tags. Unless forced, an image that is tagged in
+    /// more than one repository, or used by a container, is not removed.
+    pub async fn remove<ID: Into<ImageRef>>(&self, image_id_or_name: ID, force: bool) -> Result<(), DecUseError> {
+        let uri = self.client.url.images().remove(image_id_or_name.into(), force);
+        let response = self.client.http.delete(uri)?.execute().await?;
+
+        response
+            .assert_unit_status(StatusCode::OK)
+    }
+
     /// Remove a name or tag from an image.
     ///
     /// The image will still exist even after the last tag is removed, can be
     /// returned by the list() method, and can be referenced by its hash.
-    pub async fn untag<ID: Into<String>>(&self, image_id_or_name_and_tag: ID) -> Result<(), DecUseError> {
-        let uri = self.client.url.images().untag(image_id_or_name_and_tag);
+    pub async fn untag<ID: Into<ImageRef>>(&self, image_id_or_name_and_tag: ID) -> Result<(), DecUseError> {
+        let uri = self.client.url.images().untag(image_id_or_name_and_tag.into());
         let response = self.client.http.delete(uri)?.execute().await?;
 
         response
             .assert_unit_status(StatusCode::OK)
     }
+
+    /// Stop building, pulling or pushing an image at a deadline, or when a token is cancelled.
+    pub fn with_cancellation(mut self, v: Cancellation) -> Self {
+        self.cancellation = v;
+        self
+    }
+
+    /// Convert into a ImagesHandle.
+    pub fn into_owned(self) -> ImagesHandle {
+        DecImages {
+            cancellation: self.cancellation,
+            client: Cow::Owned(self.client.into_owned())
+        }
+    }
+
 }
 
 pub enum DecImagesPullIfNotPresentResult {
@@ -210,4 +391,30 @@ impl Display for DecImagesPullIfNotPresentResult {
 
         write!(f, "{}", message)
     }
-}
\ No newline at end of file
+}
+
+/// Reference to an image by repository and tag, or by repository and digest
+/// when the tag is a digest such as "sha256:4c0f...".
+fn image_reference(repo: &str, tag: &str) -> String {
+    if tag.contains(':') {
+        format!("{}@{}", repo, tag)
+    }
+    else {
+        format!("{}:{}", repo, tag)
+    }
+}
+
+#[cfg(test)]
+mod test_image_reference {
+    use super::image_reference;
+
+    #[test]
+    fn with_tag() {
+        assert_eq!("nginx:1.20", image_reference("nginx", "1.20"));
+    }
+
+    #[test]
+    fn with_digest() {
+        assert_eq!("nginx@sha256:a3ed95", image_reference("nginx", "sha256:a3ed95"));
+    }
+}
@@ -1,17 +1,46 @@
+use std::borrow::Cow;
+
 use hyper::StatusCode;
 
 use crate::DockerEngineClient;
 use crate::errors::DecUseError;
-use crate::requests::InspectNetworkArgs;
+use crate::requests::{ConnectNetworkRequest, InspectNetworkArgs};
 use crate::responses::InspectNetworkResponse;
 
+#[derive(Clone)]
 pub struct DecNetwork<'a> {
-    pub(super) client: &'a DockerEngineClient,
+    pub(super) client: Cow<'a, DockerEngineClient>,
     pub(super) network_id: String
 }
 
+/// A DecNetwork that owns a clone of its client, instead of borrowing it, so that it
+/// can be moved into a spawned task or stored in a struct. See DecNetwork::into_owned.
+pub type NetworkHandle = DecNetwork<'static>;
+
 impl <'a> DecNetwork<'a> {
 
+    /// ID of the network.
+    pub fn id(&self) -> &str {
+        &self.network_id
+    }
+
+    /// Convert into a NetworkHandle.
+    pub fn into_owned(self) -> NetworkHandle {
+        DecNetwork {
+            client: Cow::Owned(self.client.into_owned()),
+            network_id: self.network_id
+        }
+    }
+
+    /// Connect a container to the network.
+    pub async fn connect(&self, request: ConnectNetworkRequest) -> Result<(), DecUseError> {
+        let uri = self.client.url.networks().connect(&self.network_id);
+        let response = self.client.http.post_json(uri, &request)?.execute().await?;
+
+        response
+            .assert_unit_status(StatusCode::OK)
+    }
+
     /// Get a description of an existing network.
     pub async fn inspect(&self) -> Result<InspectNetworkResponse, DecUseError> {
         self.inspect_with(InspectNetworkArgs::default()).await
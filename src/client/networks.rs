@@ -1,14 +1,21 @@
+use std::borrow::Cow;
+
 use hyper::StatusCode;
 
 use crate::DockerEngineClient;
 use crate::errors::DecUseError;
 use crate::requests::CreateNetworkRequest;
-use crate::responses::CreateNetworkResponse;
+use crate::responses::{CreateNetworkResponse, InspectNetworkResponse};
 
+#[derive(Clone)]
 pub struct DecNetworks<'a> {
-    pub(super) client: &'a DockerEngineClient
+    pub(super) client: Cow<'a, DockerEngineClient>
 }
 
+/// A DecNetworks that owns a clone of its client, instead of borrowing it, so that it
+/// can be moved into a spawned task or stored in a struct. See DecNetworks::into_owned.
+pub type NetworksHandle = DecNetworks<'static>;
+
 impl <'a> DecNetworks<'a> {
 
     /// Establish a new Docker network, and return a description of it.
@@ -21,4 +28,21 @@ impl <'a> DecNetworks<'a> {
             .parse()
     }
 
+    /// Get a description of each network.
+    pub async fn list(&self) -> Result<Vec<InspectNetworkResponse>, DecUseError> {
+        let uri = self.client.url.networks().list();
+        let response = self.client.http.get(uri)?.execute().await?;
+
+        response
+            .assert_list_status(StatusCode::OK)?
+            .parse()
+    }
+
+    /// Convert into a NetworksHandle.
+    pub fn into_owned(self) -> NetworksHandle {
+        DecNetworks {
+            client: Cow::Owned(self.client.into_owned())
+        }
+    }
+
 }
@@ -0,0 +1,564 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::DockerEngineClient;
+use crate::errors::DecUseError;
+use crate::model::{ImageId, Tar, Volume};
+use crate::requests::{BuildImageRequest, CreateContainerRequest, CreateNetworkRequest, CreateVolumeRequest, Filters, ListContainersRequest, RemoveContainerArgs};
+use crate::responses::{BuildImageResponseStreamItem, CleanupReport, CreateContainerResponse, CreateNetworkResponse};
+
+/// Label with the unique id of the session that created a container, network, volume or image.
+pub const SESSION_LABEL: &str = "passivized.docker_engine_client.session";
+
+/// Label with the time a session was started, in seconds since the unix epoch.
+pub const SESSION_CREATED_LABEL: &str = "passivized.docker_engine_client.session.created";
+
+static SESSION_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Creates containers, networks, volumes and images labeled with a unique session id,
+/// so that everything created through the session can be removed with one call to cleanup.
+///
+/// Clones of a session share its record of built images.
+///
+/// # Example
+///
+/// ```rust
+/// use passivized_docker_engine_client::DockerEngineClient;
+/// use passivized_docker_engine_client::errors::DecError;
+/// use passivized_docker_engine_client::requests::{CreateContainerRequest, CreateVolumeRequest};
+///
+/// async fn example() -> Result<(), DecError> {
+///     let dec = DockerEngineClient::new()?;
+///     let session = dec.session();
+///
+///     session.create_volume(CreateVolumeRequest::default()).await?;
+///     session.create_container(CreateContainerRequest::default().image("nginx:latest")).await?;
+///
+///     let report = session.cleanup().await?;
+///
+///     println!("Removed {} containers", report.containers.len());
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct DecSession<'a> {
+    pub(super) client: Cow<'a, DockerEngineClient>,
+    pub(super) created: u64,
+    pub(super) session_id: String,
+    pub(super) images: Arc<Mutex<Vec<BuiltImage>>>
+}
+
+/// An image built through a session, with the tags the session requested.
+#[derive(Clone, Debug)]
+pub(super) struct BuiltImage {
+    id: ImageId,
+    tags: Vec<String>
+}
+
+/// A DecSession that owns a clone of its client, instead of borrowing it, so that it
+/// can be moved into a spawned task or stored in a struct. See DecSession::into_owned.
+pub type SessionHandle = DecSession<'static>;
+
+impl <'a> DecSession<'a> {
+
+    pub(super) fn new(client: Cow<'a, DockerEngineClient>) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let session_id = format!(
+            "{:x}-{:x}-{:x}",
+            now.as_nanos(),
+            std::process::id(),
+            SESSION_COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+
+        Self {
+            client,
+            created: now.as_secs(),
+            session_id,
+            images: Arc::new(Mutex::new(Vec::new()))
+        }
+    }
+
+    /// Unique id of the session, which is the value of its SESSION_LABEL.
+    pub fn id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// Build an image labeled with the session, and record its id for cleanup. See DecImages::build.
+    ///
+    /// The image is found by its first tag, or by the "Successfully built" message of the
+    /// Docker Engine's classic builder. An image that cannot be found is not recorded.
+    pub async fn build_image(&self, request: BuildImageRequest, context: Tar) -> Result<Vec<BuildImageResponseStreamItem>, DecUseError> {
+        let request = self.labels()
+            .into_iter()
+            .fold(request, |r, (k, v)| r.label(k, v));
+
+        let tags = request.tags.clone();
+
+        let items = self.client.images().build(request, context).await?;
+
+        if items.iter().any(|item| item.has_error()) {
+            return Ok(items);
+        }
+
+        let built = tags.first()
+            .cloned()
+            .or_else(|| built_image_id(&items));
+
+        if let Some(built) = built {
+            let id = self.client.images().inspect(built).await?.id;
+
+            self.images
+                .lock()
+                .unwrap()
+                .push(BuiltImage { id, tags });
+        }
+
+        Ok(items)
+    }
+
+    /// Create a container labeled with the session. See DecContainers::create.
+    pub async fn create_container(&self, request: CreateContainerRequest) -> Result<CreateContainerResponse, DecUseError> {
+        let request = self.labels()
+            .into_iter()
+            .fold(request, |r, (k, v)| r.label(k, v));
+
+        self.client.containers().create(request).await
+    }
+
+    /// Create a network labeled with the session. See DecNetworks::create.
+    pub async fn create_network(&self, request: CreateNetworkRequest) -> Result<CreateNetworkResponse, DecUseError> {
+        let request = self.labels()
+            .into_iter()
+            .fold(request, |r, (k, v)| r.label(k, v));
+
+        self.client.networks().create(request).await
+    }
+
+    /// Create a volume labeled with the session. See DecVolumes::create.
+    pub async fn create_volume(&self, request: CreateVolumeRequest) -> Result<Volume, DecUseError> {
+        let request = self.labels()
+            .into_iter()
+            .fold(request, |r, (k, v)| r.label(k, v));
+
+        self.client.volumes().create(request).await
+    }
+
+    /// Remove the containers, networks and volumes labeled with the session, then the images
+    /// built through the session. Containers are forced to stop, and their anonymous volumes
+    /// are removed.
+    ///
+    /// Images are selected by the ids recorded by build_image rather than by label, because
+    /// images built FROM a session's image inherit its labels. They are not forced: an image
+    /// that is still used by another container, or tagged by someone else, is kept, and stays
+    /// recorded for the next cleanup.
+    ///
+    /// Anything already removed by other means is skipped.
+    pub async fn cleanup(&self) -> Result<CleanupReport, DecUseError> {
+        let mut report = remove_labeled(&self.client, |labels| {
+            labels.get(SESSION_LABEL) == Some(&self.session_id)
+        })
+            .await?;
+
+        // Images stay recorded until they are removed, so that a failed or partial
+        // cleanup can be retried.
+        let images: Vec<BuiltImage> = self.images
+            .lock()
+            .unwrap()
+            .clone();
+
+        for image in images {
+            let removal = remove_image(&self.client, &image).await?;
+
+            if removal != ImageRemoval::Kept {
+                self.images
+                    .lock()
+                    .unwrap()
+                    .retain(|recorded| recorded.id != image.id);
+            }
+
+            if removal == ImageRemoval::Removed {
+                report.images.push(image.id);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Convert into a SessionHandle. The record of built images is shared with this session.
+    pub fn into_owned(self) -> SessionHandle {
+        DecSession {
+            client: Cow::Owned(self.client.into_owned()),
+            created: self.created,
+            session_id: self.session_id,
+            images: self.images
+        }
+    }
+
+    fn labels(&self) -> [(&'static str, String); 2] {
+        [
+            (SESSION_LABEL, self.session_id.clone()),
+            (SESSION_CREATED_LABEL, self.created.to_string())
+        ]
+    }
+
+}
+
+/// Remove the containers, networks, volumes and images labeled with a session that was
+/// started at least ttl ago, by any process.
+///
+/// Resources without a valid SESSION_CREATED_LABEL are not removed. Images are not forced:
+/// one that is still used by a container, has dependent child images, or is tagged in more
+/// than one repository, is kept. Images are removed newest first, so that children are
+/// removed before their parents.
+pub(super) async fn sweep_sessions(client: &DockerEngineClient, ttl: Duration) -> Result<CleanupReport, DecUseError> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let cutoff = now.saturating_sub(ttl.as_secs());
+
+    let stale = |labels: &HashMap<String, String>| {
+        labels.contains_key(SESSION_LABEL)
+            && labels.get(SESSION_CREATED_LABEL)
+                .and_then(|created| created.parse::<u64>().ok())
+                .map(|created| created <= cutoff)
+                .unwrap_or_default()
+    };
+
+    let mut report = remove_labeled(client, &stale).await?;
+
+    let mut images = client.images().list().await?;
+    images.sort_by_key(|image| std::cmp::Reverse(image.created));
+
+    for image in images {
+        if stale(&image.labels) {
+            match client.images().remove(&image.id, false).await {
+                Ok(()) => report.images.push(image.id),
+                Err(DecUseError::NotFound { .. }) | Err(DecUseError::Conflict { .. }) => {},
+                Err(other) => return Err(other)
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+async fn remove_labeled<F>(client: &DockerEngineClient, selected: F) -> Result<CleanupReport, DecUseError>
+where
+    F: Fn(&HashMap<String, String>) -> bool
+{
+    let mut report = CleanupReport::default();
+
+    let containers = client.containers()
+        .list(
+            ListContainersRequest::default()
+                .all(true)
+                .filters(Filters::default().label_present(SESSION_LABEL))
+        )
+        .await?;
+
+    for container in containers {
+        if selected(&container.labels) {
+            let args = RemoveContainerArgs::default()
+                .force(true)
+                .remove_volumes(true);
+
+            if removed(client.container(&container.id).remove_with(args).await)? {
+                report.containers.push(container.id);
+            }
+        }
+    }
+
+    for network in client.networks().list().await? {
+        if selected(&network.labels) && removed(client.network(&network.id).remove().await)? {
+            report.networks.push(network.id);
+        }
+    }
+
+    for volume in client.volumes().list().await?.volumes {
+        if selected(&volume.labels) && removed(client.volume(&volume.name).remove(false).await)? {
+            report.volumes.push(volume.name);
+        }
+    }
+
+    Ok(report)
+}
+
+/// Outcome of removing an image without forcing it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ImageRemoval {
+    Removed,
+
+    /// The image no longer existed.
+    Gone,
+
+    /// The image is still in use, or tagged by someone else.
+    Kept
+}
+
+/// Remove the tags a session gave an image, then the image itself, without forcing.
+async fn remove_image(client: &DockerEngineClient, image: &BuiltImage) -> Result<ImageRemoval, DecUseError> {
+    let inspected = match client.images().inspect(&image.id).await {
+        Ok(inspected) => inspected,
+        Err(DecUseError::NotFound { .. }) => return Ok(ImageRemoval::Gone),
+        Err(other) => return Err(other)
+    };
+
+    for tag in &image.tags {
+        if inspected.repo_tags.iter().any(|t| t == tag) {
+            match client.images().untag(tag).await {
+                Ok(()) | Err(DecUseError::NotFound { .. }) => {},
+                Err(DecUseError::Conflict { .. }) => return Ok(ImageRemoval::Kept),
+                Err(other) => return Err(other)
+            }
+        }
+    }
+
+    // Untagging the last tag may already have removed the image.
+    match client.images().remove(&image.id, false).await {
+        Ok(()) | Err(DecUseError::NotFound { .. }) => Ok(ImageRemoval::Removed),
+        Err(DecUseError::Conflict { .. }) => Ok(ImageRemoval::Kept),
+        Err(other) => Err(other)
+    }
+}
+
+/// Short image id from the "Successfully built" message of the classic builder.
+fn built_image_id(items: &[BuildImageResponseStreamItem]) -> Option<String> {
+    items
+        .iter()
+        .filter_map(|item| item.stream.as_deref())
+        .find_map(|text| text.trim().strip_prefix("Successfully built "))
+        .map(|id| id.trim().to_string())
+}
+
+/// Whether a resource was removed, or Ok(false) if it no longer existed.
+fn removed(result: Result<(), DecUseError>) -> Result<bool, DecUseError> {
+    match result {
+        Ok(()) => Ok(true),
+        Err(DecUseError::NotFound { .. }) => Ok(false),
+        Err(other) => Err(other)
+    }
+}
+
+#[cfg(test)]
+mod test_session {
+
+    mod cleanup {
+        use crate::client::SESSION_LABEL;
+        use crate::fake::FakeDockerEngine;
+        use crate::model::Tar;
+        use crate::requests::{BuildImageRequest, CreateContainerRequest, CreateNetworkRequest, CreateVolumeRequest, ListContainersRequest};
+
+        #[tokio::test]
+        async fn removes_only_own_resources() {
+            let engine = FakeDockerEngine::new()
+                .with_image("busybox:1.36");
+
+            let dec = engine.client();
+            let mine = dec.session();
+            let theirs = dec.session();
+
+            assert_ne!(mine.id(), theirs.id());
+
+            let container = mine.create_container(CreateContainerRequest::default().image("busybox:1.36"))
+                .await
+                .unwrap();
+
+            dec.container(&container.id).start().await.unwrap();
+
+            let network = mine.create_network(CreateNetworkRequest::default().name("mine"))
+                .await
+                .unwrap();
+
+            let volume = mine.create_volume(CreateVolumeRequest::default().name("mine"))
+                .await
+                .unwrap();
+
+            mine.build_image(BuildImageRequest::default().tag("mine:1"), Tar(Vec::new()))
+                .await
+                .unwrap();
+
+            let other = theirs.create_container(CreateContainerRequest::default().image("busybox:1.36"))
+                .await
+                .unwrap();
+
+            theirs.create_volume(CreateVolumeRequest::default().name("theirs"))
+                .await
+                .unwrap();
+
+            let report = mine.cleanup().await.unwrap();
+
+            assert_eq!(vec![container.id], report.containers);
+            assert_eq!(vec![network.id], report.networks);
+            assert_eq!(vec![volume.name], report.volumes);
+            assert_eq!(1, report.images.len());
+
+            let containers = dec.containers()
+                .list(ListContainersRequest::default().all(true))
+                .await
+                .unwrap();
+
+            assert_eq!(vec![other.id], containers.into_iter().map(|c| c.id).collect::<Vec<_>>());
+
+            let volumes = dec.volumes().list().await.unwrap().volumes;
+            assert_eq!(vec!["theirs".to_string()], volumes.into_iter().map(|v| v.name).collect::<Vec<_>>());
+
+            assert!(dec.images().inspect("mine:1").await.is_err());
+            assert!(dec.images().inspect("busybox:1.36").await.is_ok());
+
+            let again = mine.cleanup().await.unwrap();
+            assert!(again.is_empty());
+        }
+
+        #[tokio::test]
+        async fn removes_only_recorded_images() {
+            let engine = FakeDockerEngine::new();
+
+            let dec = engine.client();
+            let session = dec.session();
+
+            session.build_image(BuildImageRequest::default().tag("base:1"), Tar(Vec::new()))
+                .await
+                .unwrap();
+
+            let untagged = session.build_image(BuildImageRequest::default(), Tar(Vec::new()))
+                .await
+                .unwrap();
+
+            assert!(!untagged.is_empty());
+
+            // An image built FROM the session's image, which inherits its labels.
+            dec.images()
+                .build(BuildImageRequest::default().tag("derived:1").label(SESSION_LABEL, session.id()), Tar(Vec::new()))
+                .await
+                .unwrap();
+
+            let report = session.cleanup().await.unwrap();
+
+            assert_eq!(2, report.images.len());
+            assert!(dec.images().inspect("base:1").await.is_err());
+            assert!(dec.images().inspect("derived:1").await.is_ok());
+            assert_eq!(1, dec.images().list().await.unwrap().len());
+        }
+
+        #[tokio::test]
+        async fn keeps_images_in_use() {
+            let engine = FakeDockerEngine::new();
+
+            let dec = engine.client();
+            let session = dec.session();
+
+            session.build_image(BuildImageRequest::default().tag("base:1"), Tar(Vec::new()))
+                .await
+                .unwrap();
+
+            let user = dec.containers()
+                .create(CreateContainerRequest::default().image("base:1"))
+                .await
+                .unwrap();
+
+            let report = session.cleanup().await.unwrap();
+
+            assert!(report.images.is_empty());
+            assert!(dec.images().inspect("base:1").await.is_ok());
+
+            dec.container(&user.id).remove().await.unwrap();
+
+            let again = session.cleanup().await.unwrap();
+
+            assert_eq!(1, again.images.len());
+            assert!(dec.images().inspect("base:1").await.is_err());
+        }
+    }
+
+    mod sweep {
+        use std::time::Duration;
+
+        use crate::client::{SESSION_CREATED_LABEL, SESSION_LABEL};
+        use crate::fake::FakeDockerEngine;
+        use crate::model::Tar;
+        use crate::requests::{BuildImageRequest, CreateContainerRequest, CreateVolumeRequest};
+
+        #[tokio::test]
+        async fn removes_stale_sessions() {
+            let engine = FakeDockerEngine::new()
+                .with_image("busybox:1.36");
+
+            let dec = engine.client();
+
+            let stale = dec.containers()
+                .create(CreateContainerRequest::default()
+                    .image("busybox:1.36")
+                    .label(SESSION_LABEL, "old")
+                    .label(SESSION_CREATED_LABEL, "1000")
+                )
+                .await
+                .unwrap();
+
+            dec.volumes()
+                .create(CreateVolumeRequest::default()
+                    .name("unknown-age")
+                    .label(SESSION_LABEL, "old")
+                )
+                .await
+                .unwrap();
+
+            dec.images()
+                .build(BuildImageRequest::default()
+                    .tag("old:1")
+                    .label(SESSION_LABEL, "old")
+                    .label(SESSION_CREATED_LABEL, "1000"),
+                    Tar(Vec::new())
+                )
+                .await
+                .unwrap();
+
+            dec.images()
+                .build(BuildImageRequest::default()
+                    .tag("in-use:1")
+                    .label(SESSION_LABEL, "old")
+                    .label(SESSION_CREATED_LABEL, "1000"),
+                    Tar(Vec::new())
+                )
+                .await
+                .unwrap();
+
+            dec.containers()
+                .create(CreateContainerRequest::default().image("in-use:1"))
+                .await
+                .unwrap();
+
+            let fresh = dec.session()
+                .create_container(CreateContainerRequest::default().image("busybox:1.36"))
+                .await
+                .unwrap();
+
+            dec.session()
+                .build_image(BuildImageRequest::default().tag("fresh:1"), Tar(Vec::new()))
+                .await
+                .unwrap();
+
+            let report = dec.sweep_sessions(Duration::from_secs(3600))
+                .await
+                .unwrap();
+
+            assert_eq!(vec![stale.id], report.containers);
+            assert!(report.volumes.is_empty());
+            assert_eq!(1, report.images.len());
+            assert!(dec.images().inspect("old:1").await.is_err());
+            assert!(dec.images().inspect("in-use:1").await.is_ok());
+            assert!(dec.images().inspect("fresh:1").await.is_ok());
+
+            assert!(dec.container(&fresh.id).inspect().await.is_ok());
+            assert!(dec.volume("unknown-age").inspect().await.is_ok());
+        }
+    }
+}
@@ -1,12 +1,15 @@
 use std::io::Cursor;
+use log::warn;
 use time::format_description::well_known::Iso8601;
 
 use time::OffsetDateTime;
 
+use crate::DockerEngineClient;
 use crate::errors::DecUseError;
 use crate::imp::content_type;
 use crate::model::StreamLine;
 use crate::imp::http_proxy::DockerEngineHttpResponse;
+use crate::requests::RemoveContainerArgs;
 
 /// Parse output of https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerLogs
 pub(super) fn parse_container_log(response: DockerEngineHttpResponse) -> Result<Vec<StreamLine>, DecUseError> {
@@ -21,6 +24,56 @@ pub(super) fn parse_container_log(response: DockerEngineHttpResponse) -> Result<
     Ok(lines)
 }
 
+/// Force-remove a container, and its anonymous volumes, from a Drop implementation.
+///
+/// Drop cannot await, and may run on a thread of a runtime that would have to make progress
+/// for the removal to complete, so the container is removed on a new thread and runtime.
+/// Failures are logged.
+pub(crate) fn remove_container_blocking(client: &DockerEngineClient, id: &str) {
+    let client = client.detached();
+    let container_id = id.to_string();
+
+    let removed = std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| e.to_string())?;
+
+        let args = RemoveContainerArgs::default()
+            .force(true)
+            .remove_volumes(true);
+
+        match runtime.block_on(client.container(&container_id).remove_with(args)) {
+            Ok(()) | Err(DecUseError::NotFound { .. }) => Ok(()),
+            Err(other) => Err(other.error_message())
+        }
+    })
+        .join();
+
+    match removed {
+        Ok(Ok(())) => {},
+        Ok(Err(message)) => warn!("Failed to remove container {}: {}", id, message),
+        Err(_) => warn!("Failed to remove container {}: removal panicked", id)
+    }
+}
+
+/// Repository and tag of an image reference, such as "localhost:5000/app:1.0".
+///
+/// For a reference with a digest, such as "app@sha256:4c0f...", the digest is returned
+/// in place of the tag, and any tag before it is ignored.
+pub(crate) fn split_image(image: &str) -> (&str, &str) {
+    if let Some((name, digest)) = image.split_once('@') {
+        return (split_image(name).0, digest);
+    }
+
+    let name_start = image.rfind('/').map(|i| i + 1).unwrap_or(0);
+
+    match image[name_start..].rfind(':') {
+        Some(i) => (&image[..name_start + i], &image[name_start + i + 1..]),
+        None => (image, "latest")
+    }
+}
+
 /// Parse a time in the format 2022-11-28T00:34:45.107901180Z
 fn parse_log_container_timestamp(text: &str) -> Result<OffsetDateTime, String> {
     OffsetDateTime::parse(text, &Iso8601::DEFAULT)
@@ -65,6 +118,7 @@ mod test_parse_container_log {
     #[test]
     fn parses() {
         let response = DockerEngineHttpResponse {
+            request_method: Default::default(),
             request_uri: Default::default(),
             status: Default::default(),
             content_type: Some(STREAM.into()),
@@ -88,6 +142,7 @@ mod test_parse_container_log {
         const WRONG: &str = "x-wrong/x-wrong";
 
         let response = DockerEngineHttpResponse {
+            request_method: Default::default(),
             request_uri: Default::default(),
             status: Default::default(),
             content_type: Some(WRONG.into()),
@@ -221,4 +276,30 @@ mod test_split_container_log_timestamp_line {
             actual.text
         )
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test_split_image {
+    use super::split_image;
+
+    #[test]
+    fn with_registry_port() {
+        assert_eq!(("localhost:5000/app", "1.0"), split_image("localhost:5000/app:1.0"));
+        assert_eq!(("localhost:5000/app", "latest"), split_image("localhost:5000/app"));
+    }
+
+    #[test]
+    fn with_tag() {
+        assert_eq!(("nginx", "1.20"), split_image("nginx:1.20"));
+        assert_eq!(("nginx", "latest"), split_image("nginx"));
+    }
+
+    #[test]
+    fn with_digest() {
+        const DIGEST: &str = "sha256:a3ed95caeb02ffe68cdd9fd84406680ae93d633cb16422d00e8a7c22955b46d4";
+
+        assert_eq!(("nginx", DIGEST), split_image(&format!("nginx@{}", DIGEST)));
+        assert_eq!(("nginx", DIGEST), split_image(&format!("nginx:1.20@{}", DIGEST)));
+        assert_eq!(("localhost:5000/app", DIGEST), split_image(&format!("localhost:5000/app@{}", DIGEST)));
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use hyper::{Request, Response};
+use hyper::body::Bytes;
+
+/// Failure of a DockerEngineTransport to send a request or receive its response.
+pub type TransportError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Response, or failure, of DockerEngineTransport::send.
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<Response<Bytes>, TransportError>> + Send + 'a>>;
+
+/// Sends requests to a Docker Engine, and receives their complete responses.
+///
+/// By default, a DockerEngineClient uses a transport built on Hyper, which connects over
+/// http, https, ssh or a unix socket. Replace it with DockerEngineClientBuilder::transport,
+/// for example to test code that uses this library without a Docker Engine; see
+/// fake::FakeDockerEngine, from the test-util cargo feature.
+///
+/// Timeouts, cancellation and retries are applied by the client, around the transport.
+///
+/// # Example
+///
+/// ```rust
+/// use hyper::{Request, Response};
+/// use hyper::body::Bytes;
+///
+/// use passivized_docker_engine_client::DockerEngineClient;
+/// use passivized_docker_engine_client::client::{DockerEngineTransport, TransportFuture};
+/// use passivized_docker_engine_client::errors::DecError;
+///
+/// /// Answers every request with an empty list.
+/// #[derive(Debug)]
+/// struct EmptyEngine;
+///
+/// impl DockerEngineTransport for EmptyEngine {
+///     fn send(&self, _request: Request<Bytes>) -> TransportFuture<'_> {
+///         Box::pin(async {
+///             let response = Response::builder()
+///                 .header("Content-Type", "application/json")
+///                 .body(Bytes::from("[]"))?;
+///
+///             Ok(response)
+///         })
+///     }
+/// }
+///
+/// async fn example() -> Result<(), DecError> {
+///     let dec = DockerEngineClient::builder()
+///         .transport(EmptyEngine)
+///         .build()?;
+///
+///     assert!(dec.images().list().await?.is_empty());
+///
+///     Ok(())
+/// }
+/// ```
+pub trait DockerEngineTransport: Debug + Send + Sync {
+
+    /// Send a request, and receive the complete response body.
+    ///
+    /// Return Ok for any response from the Docker Engine, including error statuses.
+    fn send(&self, request: Request<Bytes>) -> TransportFuture<'_>;
+
+    /// A copy of this transport that does not share open connections with it, for use from
+    /// another Tokio runtime. Connections belong to the runtime that opened them, and stall
+    /// while that runtime is blocked.
+    ///
+    /// Defaults to None, meaning this transport can be used from any runtime.
+    fn detached(&self) -> Option<Arc<dyn DockerEngineTransport>> {
+        None
+    }
+
+}
@@ -1,16 +1,37 @@
+use std::borrow::Cow;
+
 use hyper::StatusCode;
 
 use crate::DockerEngineClient;
 use crate::errors::DecUseError;
 use crate::model::Volume;
+use crate::requests::UpdateVolumeRequest;
 
+#[derive(Clone)]
 pub struct DecVolume<'a> {
-    pub(super) client: &'a DockerEngineClient,
+    pub(super) client: Cow<'a, DockerEngineClient>,
     pub(super) volume_id: String
 }
 
+/// A DecVolume that owns a clone of its client, instead of borrowing it, so that it
+/// can be moved into a spawned task or stored in a struct. See DecVolume::into_owned.
+pub type VolumeHandle = DecVolume<'static>;
+
 impl <'a> DecVolume<'a> {
 
+    /// ID of the volume.
+    pub fn id(&self) -> &str {
+        &self.volume_id
+    }
+
+    /// Convert into a VolumeHandle.
+    pub fn into_owned(self) -> VolumeHandle {
+        DecVolume {
+            client: Cow::Owned(self.client.into_owned()),
+            volume_id: self.volume_id
+        }
+    }
+
     /// Get description of an existing volume.
     ///
     /// # Example
@@ -65,4 +86,102 @@ impl <'a> DecVolume<'a> {
             .assert_unit_status(StatusCode::NO_CONTENT)
     }
 
-}
\ No newline at end of file
+    /// Change the availability or secrets of a cluster volume.
+    ///
+    /// Sent with Docker Engine api version 1.42, unless a newer version was configured. Requires a swarm manager. Docker Engine
+    /// rejects updates to volumes that are not cluster volumes.
+    ///
+    /// See https://docs.docker.com/engine/api/v1.42/#tag/Volume/operation/VolumeUpdate
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::errors::DecError;
+    /// use passivized_docker_engine_client::requests::UpdateVolumeRequest;
+    ///
+    /// async fn example() -> Result<(), DecError> {
+    ///     let dec = DockerEngineClient::new()?;
+    ///
+    ///     let volume = dec.volume("example").inspect().await?;
+    ///     let cluster_volume = volume.cluster_volume.unwrap();
+    ///
+    ///     let mut spec = cluster_volume.spec;
+    ///
+    ///     if let Some(access_mode) = spec.access_mode.as_mut() {
+    ///         access_mode.availability = Some("drain".into());
+    ///     }
+    ///
+    ///     let request = UpdateVolumeRequest::new(cluster_volume.version.index, spec);
+    ///
+    ///     dec.volume("example").update(request).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn update(&self, request: UpdateVolumeRequest) -> Result<(), DecUseError> {
+        let uri = self.client.url.volumes().update(&self.volume_id, request.version);
+        let response = self.client.http.put_json(uri, &request)?.execute().await?;
+
+        response
+            .assert_unit_status(StatusCode::OK)
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod update {
+        use mockito::ServerGuard;
+
+        use crate::DockerEngineClient;
+        use crate::model::ClusterVolumeSpec;
+        use crate::requests::UpdateVolumeRequest;
+
+        fn mockito_client(server: &ServerGuard) -> DockerEngineClient {
+            DockerEngineClient::with_server(server.url())
+                .unwrap()
+        }
+
+        #[tokio::test]
+        async fn uses_version_1_42() {
+            let mut server = mockito::Server::new_async().await;
+            let dec = mockito_client(&server);
+
+            let mock = server.mock("PUT", "/v1.42/volumes/foo?version=12")
+                .match_body(mockito::Matcher::JsonString(r#"{"Spec":{}}"#.into()))
+                .with_status(200)
+                .create_async()
+                .await;
+
+            dec.volume("foo").update(UpdateVolumeRequest::new(12, ClusterVolumeSpec::default()))
+                .await
+                .unwrap();
+
+            mock.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn keeps_newer_configured_version() {
+            let mut server = mockito::Server::new_async().await;
+
+            let dec = DockerEngineClient::builder()
+                .server(server.url())
+                .api_version("v1.43")
+                .build()
+                .unwrap();
+
+            let mock = server.mock("PUT", "/v1.43/volumes/foo?version=12")
+                .with_status(200)
+                .create_async()
+                .await;
+
+            dec.volume("foo").update(UpdateVolumeRequest::new(12, ClusterVolumeSpec::default()))
+                .await
+                .unwrap();
+
+            mock.assert_async().await;
+        }
+    }
+}
@@ -1,18 +1,29 @@
+use std::borrow::Cow;
+
 use hyper::StatusCode;
 
 use crate::DockerEngineClient;
 use crate::errors::DecUseError;
+use crate::model::Volume;
 use crate::requests::CreateVolumeRequest;
 use crate::responses::{ListVolumesResponse, PruneVolumesResponse};
 
+#[derive(Clone)]
 pub struct DecVolumes<'a> {
-    pub(super) client: &'a DockerEngineClient
+    pub(super) client: Cow<'a, DockerEngineClient>
 }
 
+/// A DecVolumes that owns a clone of its client, instead of borrowing it, so that it
+/// can be moved into a spawned task or stored in a struct. See DecVolumes::into_owned.
+pub type VolumesHandle = DecVolumes<'static>;
+
 impl <'a> DecVolumes<'a> {
 
     /// Create a new volume.
     ///
+    /// If no name is specified, Docker Engine generates one, which can be found in
+    /// the returned volume.
+    ///
     /// # Arguments
     /// * `request` - description of volume to create
     ///
@@ -29,17 +40,20 @@ impl <'a> DecVolumes<'a> {
     ///     let request = CreateVolumeRequest::default()
     ///         .name("example");
     ///
-    ///     dec.volumes().create(request).await?;
+    ///     let volume = dec.volumes().create(request).await?;
+    ///
+    ///     println!("Created volume {} at {}", volume.name, volume.mountpoint);
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub async fn create(&self, request: CreateVolumeRequest) -> Result<(), DecUseError> {
-        let uri = self.client.url.volumes().create();
+    pub async fn create(&self, request: CreateVolumeRequest) -> Result<Volume, DecUseError> {
+        let uri = self.client.url.volumes().create(request.cluster_volume_spec.is_some());
         let response = self.client.http.post_json(uri, &request)?.execute().await?;
 
         response
-            .assert_unit_status(StatusCode::CREATED)
+            .assert_item_status(StatusCode::CREATED)?
+            .parse()
     }
 
     /// Get a list of volumes.
@@ -94,4 +108,77 @@ impl <'a> DecVolumes<'a> {
             .parse()
     }
 
-}
\ No newline at end of file
+    /// Convert into a VolumesHandle.
+    pub fn into_owned(self) -> VolumesHandle {
+        DecVolumes {
+            client: Cow::Owned(self.client.into_owned())
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod create {
+        use const_str::concat;
+        use mockito::ServerGuard;
+
+        use crate::DockerEngineClient;
+        use crate::imp::api::DOCKER_ENGINE_VERSION_PATH;
+        use crate::imp::content_type;
+        use crate::model::ClusterVolumeSpec;
+        use crate::requests::CreateVolumeRequest;
+
+        const VOLUME: &str = r#"{"Name":"foo","Driver":"local","Mountpoint":"","CreatedAt":"2023-01-01T00:00:00Z","Labels":null,"Scope":"local","Options":null,"UsageData":null}"#;
+
+        fn mockito_client(server: &ServerGuard) -> DockerEngineClient {
+            DockerEngineClient::with_server(server.url())
+                .unwrap()
+        }
+
+        #[tokio::test]
+        async fn local_volume_uses_default_version() {
+            let mut server = mockito::Server::new_async().await;
+            let dec = mockito_client(&server);
+
+            let mock = server.mock("POST", concat!(DOCKER_ENGINE_VERSION_PATH, "/volumes/create"))
+                .with_status(201)
+                .with_header("Content-Type", content_type::JSON)
+                .with_body(VOLUME)
+                .create_async()
+                .await;
+
+            let volume = dec.volumes().create(CreateVolumeRequest::default().name("foo"))
+                .await
+                .unwrap();
+
+            mock.assert_async().await;
+            assert_eq!("foo", volume.name);
+        }
+
+        #[tokio::test]
+        async fn cluster_volume_uses_version_1_42() {
+            let mut server = mockito::Server::new_async().await;
+            let dec = mockito_client(&server);
+
+            let mock = server.mock("POST", "/v1.42/volumes/create")
+                .match_body(mockito::Matcher::PartialJsonString(r#"{"Name":"foo","ClusterVolumeSpec":{}}"#.into()))
+                .with_status(201)
+                .with_header("Content-Type", content_type::JSON)
+                .with_body(VOLUME)
+                .create_async()
+                .await;
+
+            let request = CreateVolumeRequest::default()
+                .name("foo")
+                .cluster_volume_spec(ClusterVolumeSpec::default());
+
+            dec.volumes().create(request)
+                .await
+                .unwrap();
+
+            mock.assert_async().await;
+        }
+    }
+}
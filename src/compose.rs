@@ -0,0 +1,29 @@
+//! Compose files: services, networks and volumes, mapped onto the requests of this library,
+//! and brought up and down as a project.
+//!
+//! Requires the compose cargo feature.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use passivized_docker_engine_client::DockerEngineClient;
+//! use passivized_docker_engine_client::compose::{ComposeFile, ComposeProject};
+//! use passivized_docker_engine_client::errors::ComposeError;
+//!
+//! async fn example(dec: &DockerEngineClient) -> Result<(), ComposeError> {
+//!     let file = ComposeFile::load("docker-compose.yml")?;
+//!     let project = ComposeProject::new("integration", file);
+//!
+//!     project.up(dec).await?;
+//!
+//!     // Run tests against the services.
+//!
+//!     project.down(dec).await
+//! }
+//! ```
+
+mod file;
+mod project;
+
+pub use file::*;
+pub use project::*;
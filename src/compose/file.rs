@@ -0,0 +1,1001 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Deserializer};
+use serde::de::Error;
+use serde_yaml::Value;
+
+use crate::errors::ComposeError;
+
+/// A Compose file. See https://docs.docker.com/compose/compose-file/
+///
+/// Elements that this library does not act on, such as build, deploy and secrets, are ignored.
+/// Variables such as ${TAG} are not interpolated.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ComposeFile {
+
+    /// Project name, if set in the file.
+    #[serde(default)]
+    pub name: Option<String>,
+
+    #[serde(default, deserialize_with = "dz_nullable_map")]
+    pub networks: BTreeMap<String, ComposeNetwork>,
+
+    #[serde(default)]
+    pub services: BTreeMap<String, ComposeService>,
+
+    #[serde(default, deserialize_with = "dz_nullable_map")]
+    pub volumes: BTreeMap<String, ComposeVolume>
+
+}
+
+impl ComposeFile {
+
+    /// Parse the text of a Compose file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::compose::ComposeFile;
+    ///
+    /// let file = ComposeFile::parse(r#"
+    /// services:
+    ///   web:
+    ///     image: nginx:1.20
+    ///     ports:
+    ///       - "8080:80"
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(Some("nginx:1.20".into()), file.services["web"].image);
+    /// ```
+    pub fn parse(yaml: &str) -> Result<Self, ComposeError> {
+        serde_yaml::from_str(yaml)
+            .map_err(|e| ComposeError::Parse(e.to_string()))
+    }
+
+    /// Read and parse a Compose file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ComposeError> {
+        Self::parse(&read_file(path.as_ref())?)
+    }
+
+}
+
+/// A network in the top-level networks element.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ComposeNetwork {
+
+    #[serde(default)]
+    pub attachable: bool,
+
+    #[serde(default)]
+    pub driver: Option<String>,
+
+    #[serde(default, deserialize_with = "dz_labels")]
+    pub driver_opts: BTreeMap<String, String>,
+
+    #[serde(default)]
+    pub enable_ipv6: bool,
+
+    /// The network already exists, and is neither created nor removed.
+    #[serde(default)]
+    pub external: bool,
+
+    #[serde(default)]
+    pub internal: bool,
+
+    #[serde(default, deserialize_with = "dz_labels")]
+    pub labels: BTreeMap<String, String>,
+
+    /// Name of the network in the Docker Engine, instead of one prefixed with the project name.
+    #[serde(default)]
+    pub name: Option<String>
+
+}
+
+/// A volume in the top-level volumes element.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ComposeVolume {
+
+    #[serde(default)]
+    pub driver: Option<String>,
+
+    #[serde(default, deserialize_with = "dz_labels")]
+    pub driver_opts: BTreeMap<String, String>,
+
+    /// The volume already exists, and is neither created nor removed.
+    #[serde(default)]
+    pub external: bool,
+
+    #[serde(default, deserialize_with = "dz_labels")]
+    pub labels: BTreeMap<String, String>,
+
+    /// Name of the volume in the Docker Engine, instead of one prefixed with the project name.
+    #[serde(default)]
+    pub name: Option<String>
+
+}
+
+/// A service in the top-level services element.
+///
+/// Short and long forms, such as a command as a string or a list, or ports as strings or
+/// mappings, are read into one form.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ComposeService {
+
+    #[serde(default)]
+    pub cap_add: Vec<String>,
+
+    /// Overrides the image's command. A string is split into words, as a shell would.
+    #[serde(default, deserialize_with = "dz_command")]
+    pub command: Vec<String>,
+
+    #[serde(default)]
+    pub container_name: Option<String>,
+
+    /// Services that must start before this one, and the condition each must meet.
+    #[serde(default, deserialize_with = "dz_depends_on")]
+    pub depends_on: BTreeMap<String, ComposeDependency>,
+
+    /// Overrides the image's entrypoint. A string is split into words, as a shell would.
+    #[serde(default, deserialize_with = "dz_command")]
+    pub entrypoint: Vec<String>,
+
+    /// Files of environment variables, relative to the project directory.
+    #[serde(default, deserialize_with = "dz_env_files")]
+    pub env_file: Vec<ComposeEnvFile>,
+
+    /// Environment variables. A variable without a value takes its value from the
+    /// environment of this process, and is left out if it is not set there.
+    #[serde(default, deserialize_with = "dz_environment")]
+    pub environment: BTreeMap<String, Option<String>>,
+
+    #[serde(default)]
+    pub healthcheck: Option<ComposeHealthCheck>,
+
+    #[serde(default)]
+    pub hostname: Option<String>,
+
+    #[serde(default)]
+    pub image: Option<String>,
+
+    #[serde(default, deserialize_with = "dz_labels")]
+    pub labels: BTreeMap<String, String>,
+
+    /// Networks to connect to, by their keys in the top-level networks element. When empty,
+    /// the service is connected to the project's default network.
+    #[serde(default, deserialize_with = "dz_service_networks")]
+    pub networks: BTreeMap<String, ComposeServiceNetwork>,
+
+    #[serde(default, deserialize_with = "dz_ports")]
+    pub ports: Vec<ComposePort>,
+
+    #[serde(default)]
+    pub privileged: bool,
+
+    #[serde(default)]
+    pub stop_signal: Option<String>,
+
+    #[serde(default)]
+    pub user: Option<String>,
+
+    #[serde(default, deserialize_with = "dz_service_volumes")]
+    pub volumes: Vec<ComposeServiceVolume>,
+
+    #[serde(default)]
+    pub working_dir: Option<String>
+
+}
+
+/// An entry of a service's depends_on element.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ComposeDependency {
+
+    #[serde(default)]
+    pub condition: ComposeDependencyCondition
+
+}
+
+/// When a dependency is ready for the services that depend on it.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ComposeDependencyCondition {
+    /// Once the dependency has started.
+    #[default]
+    ServiceStarted,
+
+    /// Once the dependency's health check has passed.
+    ServiceHealthy,
+
+    /// Once the dependency has exited with exit code 0.
+    ServiceCompletedSuccessfully
+}
+
+/// An entry of a service's env_file element.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComposeEnvFile {
+    pub path: String,
+
+    /// When false, a missing file is ignored.
+    pub required: bool
+}
+
+/// A service's healthcheck element.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ComposeHealthCheck {
+
+    /// Disables the image's health check.
+    #[serde(default)]
+    pub disable: bool,
+
+    #[serde(default, deserialize_with = "dz_duration")]
+    pub interval: Option<Duration>,
+
+    #[serde(default)]
+    pub retries: Option<u64>,
+
+    #[serde(default, deserialize_with = "dz_duration")]
+    pub start_period: Option<Duration>,
+
+    /// Command to run, with "CMD", "CMD-SHELL" or "NONE" first. A string is run with the
+    /// container's default shell, as with "CMD-SHELL".
+    #[serde(default, deserialize_with = "dz_health_test")]
+    pub test: Vec<String>,
+
+    #[serde(default, deserialize_with = "dz_duration")]
+    pub timeout: Option<Duration>
+
+}
+
+/// A published port, such as "127.0.0.1:8080:80/tcp".
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComposePort {
+    pub host_ip: Option<String>,
+
+    /// Host port. When None, the Docker Engine chooses one.
+    pub published: Option<String>,
+
+    /// Container port.
+    pub target: String,
+
+    pub protocol: String
+}
+
+/// An entry of a service's networks element.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ComposeServiceNetwork {
+
+    /// Names for the service on the network, in addition to the service name.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+
+    #[serde(default)]
+    pub ipv4_address: Option<String>
+
+}
+
+/// What a service volume mounts.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ComposeMountKind {
+    /// A directory or file on the host.
+    Bind,
+
+    /// A volume in the top-level volumes element, or an anonymous volume when there is no source.
+    Volume,
+
+    /// A temporary file system in memory.
+    Tmpfs
+}
+
+/// An entry of a service's volumes element, such as "data:/var/lib/data:ro".
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComposeServiceVolume {
+    pub kind: ComposeMountKind,
+
+    /// Host path of a bind mount, or key of a volume in the top-level volumes element.
+    pub source: Option<String>,
+
+    /// Path in the container.
+    pub target: String,
+
+    pub read_only: bool
+}
+
+pub(super) fn read_file(path: &Path) -> Result<String, ComposeError> {
+    std::fs::read_to_string(path)
+        .map_err(|e| ComposeError::Io {
+            path: path.display().to_string(),
+            message: e.to_string()
+        })
+}
+
+/// Text of a scalar, such as a label value, which YAML may have read as a number or boolean.
+fn scalar(value: &Value) -> Result<Option<String>, String> {
+    match value {
+        Value::Null => Ok(None),
+        Value::Bool(b) => Ok(Some(b.to_string())),
+        Value::Number(n) => Ok(Some(n.to_string())),
+        Value::String(s) => Ok(Some(s.clone())),
+        other => Err(format!("expected a string, number or boolean, not {:?}", other))
+    }
+}
+
+fn string(value: &Value) -> Result<String, String> {
+    scalar(value)?
+        .ok_or_else(|| "expected a value".to_string())
+}
+
+/// Entries of a mapping, with the keys as strings.
+fn entries(value: &Value) -> Result<Vec<(String, &Value)>, String> {
+    match value {
+        Value::Mapping(mapping) => mapping
+            .iter()
+            .map(|(k, v)| Ok((string(k)?, v)))
+            .collect(),
+        other => Err(format!("expected a mapping, not {:?}", other))
+    }
+}
+
+/// Items of a sequence.
+fn items(value: &Value) -> Result<&Vec<Value>, String> {
+    match value {
+        Value::Sequence(sequence) => Ok(sequence),
+        other => Err(format!("expected a list, not {:?}", other))
+    }
+}
+
+/// Deserialize a Value, and convert it, reporting a failure as a serde error.
+fn convert<'de, D, T, F>(deserializer: D, f: F) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        F: FnOnce(&Value) -> Result<T, String>
+{
+    let value = Value::deserialize(deserializer)?;
+
+    f(&value).map_err(D::Error::custom)
+}
+
+fn dz_nullable_map<'de, D, T>(deserializer: D) -> Result<BTreeMap<String, T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Default + serde::de::DeserializeOwned
+{
+    let entries: BTreeMap<String, Option<T>> = BTreeMap::deserialize(deserializer)?;
+
+    Ok(entries
+        .into_iter()
+        .map(|(k, v)| (k, v.unwrap_or_default()))
+        .collect())
+}
+
+/// A mapping, or a list of KEY=VALUE items.
+fn key_values(value: &Value) -> Result<BTreeMap<String, Option<String>>, String> {
+    match value {
+        Value::Null => Ok(BTreeMap::new()),
+        Value::Sequence(sequence) => sequence
+            .iter()
+            .map(|item| {
+                let item = string(item)?;
+
+                Ok(match item.split_once('=') {
+                    Some((k, v)) => (k.to_string(), Some(v.to_string())),
+                    None => (item, None)
+                })
+            })
+            .collect(),
+        other => entries(other)?
+            .into_iter()
+            .map(|(k, v)| Ok((k, scalar(v)?)))
+            .collect()
+    }
+}
+
+fn dz_labels<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, String>, D::Error> {
+    convert(deserializer, |value| {
+        Ok(key_values(value)?
+            .into_iter()
+            .map(|(k, v)| (k, v.unwrap_or_default()))
+            .collect())
+    })
+}
+
+fn dz_environment<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, Option<String>>, D::Error> {
+    convert(deserializer, key_values)
+}
+
+fn dz_command<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    convert(deserializer, |value| match value {
+        Value::Null => Ok(Vec::new()),
+        Value::Sequence(sequence) => sequence
+            .iter()
+            .map(string)
+            .collect(),
+        other => split_words(&string(other)?)
+    })
+}
+
+fn dz_health_test<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    convert(deserializer, |value| match value {
+        Value::Null => Ok(Vec::new()),
+        Value::Sequence(sequence) => sequence
+            .iter()
+            .map(string)
+            .collect(),
+        other => Ok(vec!["CMD-SHELL".to_string(), string(other)?])
+    })
+}
+
+fn dz_duration<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+    convert(deserializer, |value| {
+        scalar(value)?
+            .map(|text| parse_duration(&text))
+            .transpose()
+    })
+}
+
+fn dz_depends_on<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, ComposeDependency>, D::Error> {
+    convert(deserializer, |value| match value {
+        Value::Null => Ok(BTreeMap::new()),
+        Value::Sequence(sequence) => sequence
+            .iter()
+            .map(|item| Ok((string(item)?, ComposeDependency::default())))
+            .collect(),
+        other => entries(other)?
+            .into_iter()
+            .map(|(k, v)| {
+                let dependency = match v {
+                    Value::Null => ComposeDependency::default(),
+                    v => serde_yaml::from_value(v.clone()).map_err(|e| e.to_string())?
+                };
+
+                Ok((k, dependency))
+            })
+            .collect()
+    })
+}
+
+fn dz_env_files<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<ComposeEnvFile>, D::Error> {
+    let env_file = |value: &Value| -> Result<ComposeEnvFile, String> {
+        match value {
+            Value::Mapping(_) => {
+                let mut path = None;
+                let mut required = true;
+
+                for (k, v) in entries(value)? {
+                    match k.as_str() {
+                        "path" => path = Some(string(v)?),
+                        "required" => required = v.as_bool().ok_or("expected required to be a boolean")?,
+                        _ => {}
+                    }
+                }
+
+                Ok(ComposeEnvFile {
+                    path: path.ok_or("env_file requires a path")?,
+                    required
+                })
+            },
+            other => Ok(ComposeEnvFile {
+                path: string(other)?,
+                required: true
+            })
+        }
+    };
+
+    convert(deserializer, |value| match value {
+        Value::Null => Ok(Vec::new()),
+        Value::Sequence(sequence) => sequence
+            .iter()
+            .map(env_file)
+            .collect(),
+        other => Ok(vec![env_file(other)?])
+    })
+}
+
+fn dz_service_networks<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, ComposeServiceNetwork>, D::Error> {
+    convert(deserializer, |value| match value {
+        Value::Null => Ok(BTreeMap::new()),
+        Value::Sequence(sequence) => sequence
+            .iter()
+            .map(|item| Ok((string(item)?, ComposeServiceNetwork::default())))
+            .collect(),
+        other => entries(other)?
+            .into_iter()
+            .map(|(k, v)| {
+                let network = match v {
+                    Value::Null => ComposeServiceNetwork::default(),
+                    v => serde_yaml::from_value(v.clone()).map_err(|e| e.to_string())?
+                };
+
+                Ok((k, network))
+            })
+            .collect()
+    })
+}
+
+fn dz_ports<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<ComposePort>, D::Error> {
+    let port = |value: &Value| -> Result<ComposePort, String> {
+        match value {
+            Value::Mapping(_) => {
+                let mut port = ComposePort {
+                    host_ip: None,
+                    published: None,
+                    target: String::new(),
+                    protocol: "tcp".into()
+                };
+
+                for (k, v) in entries(value)? {
+                    match k.as_str() {
+                        "host_ip" => port.host_ip = scalar(v)?,
+                        "published" => port.published = scalar(v)?,
+                        "target" => port.target = string(v)?,
+                        "protocol" => port.protocol = string(v)?,
+                        _ => {}
+                    }
+                }
+
+                if port.target.is_empty() {
+                    Err("a port requires a target".into())
+                }
+                else {
+                    Ok(port)
+                }
+            },
+            other => parse_port(&string(other)?)
+        }
+    };
+
+    convert(deserializer, |value| match value {
+        Value::Null => Ok(Vec::new()),
+        other => items(other)?
+            .iter()
+            .map(port)
+            .collect()
+    })
+}
+
+fn dz_service_volumes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<ComposeServiceVolume>, D::Error> {
+    let volume = |value: &Value| -> Result<ComposeServiceVolume, String> {
+        match value {
+            Value::Mapping(_) => {
+                let mut kind = ComposeMountKind::Volume;
+                let mut source = None;
+                let mut target = None;
+                let mut read_only = false;
+
+                for (k, v) in entries(value)? {
+                    match k.as_str() {
+                        "type" => kind = match string(v)?.as_str() {
+                            "bind" => ComposeMountKind::Bind,
+                            "volume" => ComposeMountKind::Volume,
+                            "tmpfs" => ComposeMountKind::Tmpfs,
+                            other => return Err(format!("unsupported volume type {}", other))
+                        },
+                        "source" => source = scalar(v)?,
+                        "target" => target = Some(string(v)?),
+                        "read_only" => read_only = v.as_bool().ok_or("expected read_only to be a boolean")?,
+                        _ => {}
+                    }
+                }
+
+                Ok(ComposeServiceVolume {
+                    kind,
+                    source,
+                    target: target.ok_or("a volume requires a target")?,
+                    read_only
+                })
+            },
+            other => Ok(parse_service_volume(&string(other)?))
+        }
+    };
+
+    convert(deserializer, |value| match value {
+        Value::Null => Ok(Vec::new()),
+        other => items(other)?
+            .iter()
+            .map(volume)
+            .collect()
+    })
+}
+
+/// Parse a Go duration, as used by Compose files, such as "1m30s" or "500ms".
+pub(super) fn parse_duration(text: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration {:?}", text);
+
+    let mut rest = text.trim();
+    let mut total = Duration::ZERO;
+
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+
+    // A bare number, which is not valid in Go, is read as seconds.
+    if let Ok(seconds) = rest.parse::<f64>() {
+        return Duration::try_from_secs_f64(seconds).map_err(|_| invalid());
+    }
+
+    while !rest.is_empty() {
+        let number_end = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .ok_or_else(invalid)?;
+
+        let unit_end = rest[number_end..]
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .map(|i| number_end + i)
+            .unwrap_or(rest.len());
+
+        let number: f64 = rest[..number_end].parse().map_err(|_| invalid())?;
+
+        let unit_seconds = match &rest[number_end..unit_end] {
+            "ns" => 1e-9,
+            "us" | "µs" => 1e-6,
+            "ms" => 1e-3,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            _ => return Err(invalid())
+        };
+
+        // Rejects negative, infinite and NaN values, and totals that overflow.
+        total = Duration::try_from_secs_f64(number * unit_seconds)
+            .ok()
+            .and_then(|d| total.checked_add(d))
+            .ok_or_else(invalid)?;
+
+        rest = &rest[unit_end..];
+    }
+
+    Ok(total)
+}
+
+/// Parse the short syntax of a port, "[[HOST_IP:]HOST_PORT:]CONTAINER_PORT[/PROTOCOL]".
+pub(super) fn parse_port(text: &str) -> Result<ComposePort, String> {
+    let (ports, protocol) = text
+        .split_once('/')
+        .unwrap_or((text, "tcp"));
+
+    if ports.contains('-') {
+        return Err(format!("port ranges are not supported: {}", text));
+    }
+
+    let mut parts: Vec<&str> = ports.rsplitn(3, ':').collect();
+    parts.reverse();
+
+    let (host_ip, published, target) = match parts.as_slice() {
+        [target] => (None, None, *target),
+        [published, target] => (None, Some(*published), *target),
+        [host_ip, published, target] => (Some(*host_ip), Some(*published), *target),
+        _ => return Err(format!("invalid port {}", text))
+    };
+
+    if target.is_empty() || target.parse::<u16>().is_err() {
+        return Err(format!("invalid port {}", text));
+    }
+
+    Ok(ComposePort {
+        host_ip: host_ip
+            .map(|ip| ip.trim_start_matches('[').trim_end_matches(']').to_string())
+            .filter(|ip| !ip.is_empty()),
+        published: published
+            .filter(|p| !p.is_empty())
+            .map(String::from),
+        target: target.into(),
+        protocol: protocol.into()
+    })
+}
+
+/// Parse the short syntax of a service volume, "[SOURCE:]TARGET[:MODE]".
+pub(super) fn parse_service_volume(text: &str) -> ComposeServiceVolume {
+    let parts: Vec<&str> = text.split(':').collect();
+
+    let (source, target, mode) = match parts.as_slice() {
+        [source, target] => (Some(*source), *target, ""),
+        [source, target, mode, ..] => (Some(*source), *target, *mode),
+        _ => (None, text, "")
+    };
+
+    let is_path = |s: &str| s.starts_with('.') || s.starts_with('/') || s.starts_with('~');
+
+    ComposeServiceVolume {
+        kind: match source {
+            Some(s) if is_path(s) => ComposeMountKind::Bind,
+            _ => ComposeMountKind::Volume
+        },
+        source: source.map(String::from),
+        target: target.into(),
+        read_only: mode.split(',').any(|m| m == "ro")
+    }
+}
+
+/// Split a command into words, as a shell would, with single and double quotes and backslashes.
+pub(super) fn split_words(text: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if let Some(w) = word.take() {
+                    words.push(w);
+                }
+            },
+            '\'' => {
+                let w = word.get_or_insert_with(String::new);
+
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => w.push(c),
+                        None => return Err(format!("unterminated quote in {:?}", text))
+                    }
+                }
+            },
+            '"' => {
+                let w = word.get_or_insert_with(String::new);
+
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => w.push(c),
+                            Some(c) => {
+                                w.push('\\');
+                                w.push(c);
+                            },
+                            None => return Err(format!("unterminated quote in {:?}", text))
+                        },
+                        Some(c) => w.push(c),
+                        None => return Err(format!("unterminated quote in {:?}", text))
+                    }
+                }
+            },
+            '\\' => {
+                if let Some(c) = chars.next() {
+                    word.get_or_insert_with(String::new).push(c);
+                }
+            },
+            c => word.get_or_insert_with(String::new).push(c)
+        }
+    }
+
+    words.extend(word);
+
+    Ok(words)
+}
+
+#[cfg(test)]
+mod test_compose_file {
+
+    mod parse {
+        use std::time::Duration;
+
+        use crate::compose::{ComposeDependencyCondition, ComposeEnvFile, ComposeFile, ComposeMountKind, ComposePort};
+        use crate::errors::ComposeError;
+
+        const FILE: &str = r#"
+name: shop
+services:
+  db:
+    image: postgres:15
+    environment:
+      POSTGRES_PASSWORD: secret
+      POSTGRES_PORT: 5432
+    healthcheck:
+      test: pg_isready -U postgres
+      interval: 1m30s
+      retries: 5
+    volumes:
+      - data:/var/lib/postgresql/data
+  web:
+    image: nginx:1.20
+    command: nginx -g 'daemon off;'
+    depends_on:
+      db:
+        condition: service_healthy
+    env_file:
+      - web.env
+      - path: local.env
+        required: false
+    environment:
+      - MODE=production
+      - HOME
+    labels:
+      tier: front
+    networks:
+      front:
+        aliases: [www]
+      back:
+    ports:
+      - 8080:80
+      - "127.0.0.1:8443:443/tcp"
+      - target: 53
+        protocol: udp
+    volumes:
+      - ./html:/usr/share/nginx/html:ro
+networks:
+  front:
+  back:
+    internal: true
+volumes:
+  data:
+"#;
+
+        #[test]
+        fn compose_file() {
+            let file = ComposeFile::parse(FILE).unwrap();
+
+            assert_eq!(Some("shop".into()), file.name);
+            assert_eq!(vec!["back", "front"], file.networks.keys().collect::<Vec<_>>());
+            assert!(file.networks["back"].internal);
+            assert!(file.volumes.contains_key("data"));
+
+            let db = &file.services["db"];
+
+            assert_eq!(Some(Some("5432".to_string())), db.environment.get("POSTGRES_PORT").cloned());
+
+            let health = db.healthcheck.as_ref().unwrap();
+
+            assert_eq!(vec!["CMD-SHELL", "pg_isready -U postgres"], health.test);
+            assert_eq!(Some(Duration::from_secs(90)), health.interval);
+            assert_eq!(Some(5), health.retries);
+
+            assert_eq!(ComposeMountKind::Volume, db.volumes[0].kind);
+            assert_eq!(Some("data".into()), db.volumes[0].source);
+
+            let web = &file.services["web"];
+
+            assert_eq!(vec!["nginx", "-g", "daemon off;"], web.command);
+            assert_eq!(ComposeDependencyCondition::ServiceHealthy, web.depends_on["db"].condition);
+            assert_eq!(
+                vec![
+                    ComposeEnvFile { path: "web.env".into(), required: true },
+                    ComposeEnvFile { path: "local.env".into(), required: false }
+                ],
+                web.env_file
+            );
+            assert_eq!(Some(Some("production".to_string())), web.environment.get("MODE").cloned());
+            assert_eq!(Some(None), web.environment.get("HOME").cloned());
+            assert_eq!("front", web.labels["tier"]);
+            assert_eq!(vec!["www"], web.networks["front"].aliases);
+            assert!(web.networks["back"].aliases.is_empty());
+
+            assert_eq!(
+                vec![
+                    ComposePort { host_ip: None, published: Some("8080".into()), target: "80".into(), protocol: "tcp".into() },
+                    ComposePort { host_ip: Some("127.0.0.1".into()), published: Some("8443".into()), target: "443".into(), protocol: "tcp".into() },
+                    ComposePort { host_ip: None, published: None, target: "53".into(), protocol: "udp".into() }
+                ],
+                web.ports
+            );
+
+            assert_eq!(ComposeMountKind::Bind, web.volumes[0].kind);
+            assert!(web.volumes[0].read_only);
+        }
+
+        #[test]
+        fn depends_on_list() {
+            let file = ComposeFile::parse("services:\n  web:\n    depends_on: [db, cache]\n").unwrap();
+
+            let web = &file.services["web"];
+
+            assert_eq!(vec!["cache", "db"], web.depends_on.keys().collect::<Vec<_>>());
+            assert_eq!(ComposeDependencyCondition::ServiceStarted, web.depends_on["db"].condition);
+        }
+
+        #[test]
+        fn invalid() {
+            match ComposeFile::parse("services:\n  web:\n    ports: [\"80-81\"]\n").unwrap_err() {
+                ComposeError::Parse(message) =>
+                    assert!(message.contains("port ranges are not supported: 80-81"), "{}", message),
+                other =>
+                    panic!("Unexpected error: {:?}", other)
+            }
+        }
+
+    }
+
+    mod parse_duration {
+        use std::time::Duration;
+
+        use super::super::parse_duration;
+
+        #[test]
+        fn compound() {
+            assert_eq!(Duration::from_secs(5430), parse_duration("1h30m30s").unwrap());
+        }
+
+        #[test]
+        fn fractional() {
+            assert_eq!(Duration::from_millis(1500), parse_duration("1.5s").unwrap());
+        }
+
+        #[test]
+        fn invalid() {
+            assert_eq!("invalid duration \"10 parsecs\"", parse_duration("10 parsecs").unwrap_err());
+        }
+
+        #[test]
+        fn milliseconds() {
+            assert_eq!(Duration::from_millis(250), parse_duration("250ms").unwrap());
+        }
+
+        #[test]
+        fn negative() {
+            parse_duration("-5").unwrap_err();
+            parse_duration("-5s").unwrap_err();
+        }
+
+        #[test]
+        fn not_finite() {
+            parse_duration("inf").unwrap_err();
+            parse_duration("NaN").unwrap_err();
+        }
+
+        #[test]
+        fn too_large() {
+            parse_duration("99999999999999999999999h").unwrap_err();
+            parse_duration("1e300").unwrap_err();
+        }
+
+        #[test]
+        fn in_compose_file_is_a_parse_error() {
+            use crate::compose::ComposeFile;
+            use crate::errors::ComposeError;
+
+            let text = "
+services:
+  web:
+    image: nginx
+    healthcheck:
+      test: [\"CMD\", \"true\"]
+      interval: -5
+";
+
+            match ComposeFile::parse(text) {
+                Err(ComposeError::Parse(message)) => assert!(message.contains("invalid duration"), "{}", message),
+                other => panic!("Unexpected result: {:?}", other.map(|_| ()))
+            }
+        }
+
+    }
+
+    mod parse_service_volume {
+        use crate::compose::{ComposeMountKind, ComposeServiceVolume};
+
+        use super::super::parse_service_volume;
+
+        #[test]
+        fn anonymous() {
+            let expected = ComposeServiceVolume {
+                kind: ComposeMountKind::Volume,
+                source: None,
+                target: "/cache".into(),
+                read_only: false
+            };
+
+            assert_eq!(expected, parse_service_volume("/cache"));
+        }
+
+        #[test]
+        fn bind_in_home() {
+            assert_eq!(ComposeMountKind::Bind, parse_service_volume("~/config:/config:rw").kind);
+        }
+
+    }
+
+    mod split_words {
+        use super::super::split_words;
+
+        #[test]
+        fn escaped() {
+            assert_eq!(vec!["echo", "a b", "\"c\""], split_words(r#"echo a\ b "\"c\"""#).unwrap());
+        }
+
+        #[test]
+        fn unterminated() {
+            assert_eq!("unterminated quote in \"echo 'hi\"", split_words("echo 'hi").unwrap_err());
+        }
+
+    }
+
+}
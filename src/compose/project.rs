@@ -0,0 +1,1013 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::DockerEngineClient;
+use crate::client::shared::split_image;
+use crate::compose::{ComposeDependencyCondition, ComposeFile, ComposeMountKind, ComposeService, ComposeServiceNetwork};
+use crate::compose::file::read_file;
+use crate::errors::{ComposeError, DecUseError};
+use crate::model::{Cancellation, ContainerId, HealthCheck, MountMode, Readiness};
+use crate::requests::{ConnectNetworkRequest, CreateContainerRequest, CreateNetworkRequest, CreateVolumeRequest, EndpointConfig, Filters, HostConfig, ListContainersRequest, NetworkingConfig, RemoveContainerArgs, WaitCondition};
+
+/// Label with the name of the project, on its containers, networks and volumes.
+pub const LABEL_PROJECT: &str = "com.docker.compose.project";
+
+/// Label with the service name, on a project's containers.
+pub const LABEL_SERVICE: &str = "com.docker.compose.service";
+
+/// Label with the number of a service's container, on a project's containers.
+pub const LABEL_CONTAINER_NUMBER: &str = "com.docker.compose.container-number";
+
+/// Label that marks a container as one-off, as with `docker compose run`, or not.
+pub const LABEL_ONEOFF: &str = "com.docker.compose.oneoff";
+
+/// Label with the key of a network in the Compose file, on a project's networks.
+pub const LABEL_NETWORK: &str = "com.docker.compose.network";
+
+/// Label with the key of a volume in the Compose file, on a project's volumes.
+pub const LABEL_VOLUME: &str = "com.docker.compose.volume";
+
+/// How long ComposeProject::up waits for a dependency to meet its condition, unless set.
+pub const DEFAULT_DEPENDENCY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Key of the network that services without a networks element are connected to.
+const DEFAULT_NETWORK: &str = "default";
+
+/// A Compose file, with the project name that its containers, networks and volumes are
+/// named and labeled with, and the directory that relative paths in it are resolved against.
+///
+/// Names and labels follow Docker Compose, so `docker compose -p NAME ps` lists the containers.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use passivized_docker_engine_client::DockerEngineClient;
+/// use passivized_docker_engine_client::compose::ComposeProject;
+/// use passivized_docker_engine_client::errors::ComposeError;
+///
+/// async fn example(dec: &DockerEngineClient) -> Result<(), ComposeError> {
+///     let project = ComposeProject::load("tests/docker-compose.yml")?;
+///
+///     let containers = project.up(dec).await?;
+///
+///     for container in &containers {
+///         println!("Service {} is running in container {}", container.service, container.id);
+///     }
+///
+///     project.down(dec).await
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ComposeProject {
+    directory: PathBuf,
+    file: ComposeFile,
+    name: String,
+    pull: bool,
+    timeout: Duration
+}
+
+/// A container started by ComposeProject::up.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComposeContainer {
+    pub service: String,
+    pub id: ContainerId
+}
+
+impl ComposeProject {
+
+    /// A project with a name, and relative paths resolved against the current directory.
+    /// The name is converted to lowercase, without characters Docker Compose does not allow.
+    pub fn new<N: Into<String>>(name: N, file: ComposeFile) -> Self {
+        Self {
+            directory: PathBuf::from("."),
+            file,
+            name: normalize_project_name(&name.into()),
+            pull: true,
+            timeout: DEFAULT_DEPENDENCY_TIMEOUT
+        }
+    }
+
+    /// Read a Compose file. The project is named by the file's name element, or else after the
+    /// directory containing the file, and relative paths are resolved against that directory.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ComposeError> {
+        let path = path.as_ref();
+        let file = ComposeFile::parse(&read_file(path)?)?;
+
+        let directory = path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+            .to_path_buf();
+
+        let name = match &file.name {
+            Some(name) => name.clone(),
+            None => std::fs::canonicalize(&directory)
+                .ok()
+                .and_then(|d| d.file_name().map(|n| n.to_string_lossy().to_string()))
+                .unwrap_or_default()
+        };
+
+        Ok(Self::new(name, file).directory(directory))
+    }
+
+    /// Directory that relative paths, in env_file and bind mounts, are resolved against.
+    pub fn directory<P: Into<PathBuf>>(mut self, v: P) -> Self {
+        self.directory = v.into();
+        self
+    }
+
+    /// Whether up pulls images that are not present. Defaults to true.
+    pub fn pull(mut self, v: bool) -> Self {
+        self.pull = v;
+        self
+    }
+
+    /// How long up waits for each dependency to become healthy or complete. Defaults to
+    /// DEFAULT_DEPENDENCY_TIMEOUT.
+    pub fn timeout(mut self, v: Duration) -> Self {
+        self.timeout = v;
+        self
+    }
+
+    pub fn file(&self) -> &ComposeFile {
+        &self.file
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Name of a service's container, such as "shop-web-1", unless the service sets container_name.
+    pub fn container_name(&self, service: &str) -> String {
+        self.file.services
+            .get(service)
+            .and_then(|s| s.container_name.clone())
+            .unwrap_or_else(|| format!("{}-{}-1", self.name, service))
+    }
+
+    /// Name of a network in the Docker Engine, such as "shop_default", by its key in the file.
+    pub fn network_name(&self, key: &str) -> String {
+        match self.file.networks.get(key) {
+            Some(network) if network.name.is_some() =>
+                network.name.clone().unwrap_or_default(),
+            Some(network) if network.external =>
+                key.to_string(),
+            _ =>
+                format!("{}_{}", self.name, key)
+        }
+    }
+
+    /// Name of a volume in the Docker Engine, such as "shop_data", by its key in the file.
+    pub fn volume_name(&self, key: &str) -> String {
+        match self.file.volumes.get(key) {
+            Some(volume) if volume.name.is_some() =>
+                volume.name.clone().unwrap_or_default(),
+            Some(volume) if volume.external =>
+                key.to_string(),
+            _ =>
+                format!("{}_{}", self.name, key)
+        }
+    }
+
+    /// Requests for the networks that up creates: those in the file that are not external, and
+    /// the default network if a service uses it.
+    pub fn network_requests(&self) -> Vec<CreateNetworkRequest> {
+        self.network_keys()
+            .into_iter()
+            .map(|key| {
+                let network = self.file.networks
+                    .get(key)
+                    .cloned()
+                    .unwrap_or_default();
+
+                let mut request = CreateNetworkRequest::default()
+                    .name(self.network_name(key))
+                    .check_duplicate(true)
+                    .attachable(network.attachable)
+                    .enable_ipv6(network.enable_ipv6)
+                    .internal(network.internal)
+                    .label(LABEL_PROJECT, self.name.clone())
+                    .label(LABEL_NETWORK, key);
+
+                if let Some(driver) = network.driver {
+                    request = request.driver(driver);
+                }
+
+                for (k, v) in network.driver_opts {
+                    request = request.option(k, v);
+                }
+
+                for (k, v) in network.labels {
+                    request = request.label(k, v);
+                }
+
+                request
+            })
+            .collect()
+    }
+
+    /// Keys of the networks that up creates.
+    fn network_keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = self.file.networks
+            .iter()
+            .filter(|(_, n)| !n.external)
+            .map(|(k, _)| k.as_str())
+            .collect();
+
+        let default_used = self.file.services
+            .values()
+            .any(|s| s.networks.is_empty());
+
+        if default_used && !self.file.networks.contains_key(DEFAULT_NETWORK) {
+            keys.push(DEFAULT_NETWORK);
+        }
+
+        keys
+    }
+
+    /// Requests for the volumes in the file that are not external.
+    pub fn volume_requests(&self) -> Vec<CreateVolumeRequest> {
+        self.file.volumes
+            .iter()
+            .filter(|(_, v)| !v.external)
+            .map(|(key, volume)| {
+                let mut request = CreateVolumeRequest::default()
+                    .name(self.volume_name(key))
+                    .label(LABEL_PROJECT, self.name.clone())
+                    .label(LABEL_VOLUME, key.clone());
+
+                if let Some(driver) = &volume.driver {
+                    request = request.driver(driver.clone());
+                }
+
+                for (k, v) in &volume.driver_opts {
+                    request = request.driver_opt(k.clone(), v.clone());
+                }
+
+                for (k, v) in &volume.labels {
+                    request = request.label(k.clone(), v.clone());
+                }
+
+                request
+            })
+            .collect()
+    }
+
+    /// Request for a service's container, connected to the first of its networks. Environment
+    /// files are read by this call.
+    pub fn container_request(&self, service: &str) -> Result<CreateContainerRequest, ComposeError> {
+        let invalid = |message: String| ComposeError::InvalidService {
+            service: service.to_string(),
+            message
+        };
+
+        let definition = self.file.services
+            .get(service)
+            .ok_or_else(|| invalid("the service is not defined".into()))?;
+
+        let image = definition.image
+            .clone()
+            .ok_or_else(|| invalid("the service has no image; building images is not supported".into()))?;
+
+        let mut request = CreateContainerRequest::default()
+            .name(self.container_name(service))
+            .image(image)
+            .label(LABEL_PROJECT, self.name.clone())
+            .label(LABEL_SERVICE, service)
+            .label(LABEL_CONTAINER_NUMBER, "1")
+            .label(LABEL_ONEOFF, "False");
+
+        for (k, v) in &definition.labels {
+            request = request.label(k.clone(), v.clone());
+        }
+
+        if !definition.command.is_empty() {
+            request = request.cmd(definition.command.clone());
+        }
+
+        if !definition.entrypoint.is_empty() {
+            request = request.entry_point(definition.entrypoint.clone());
+        }
+
+        for (k, v) in self.environment(definition)? {
+            request = request.env(format!("{}={}", k, v));
+        }
+
+        if let Some(v) = &definition.hostname {
+            request = request.hostname(v.clone());
+        }
+
+        if let Some(v) = &definition.user {
+            request = request.user(v.clone());
+        }
+
+        if let Some(v) = &definition.working_dir {
+            request = request.working_dir(v.clone());
+        }
+
+        if let Some(v) = &definition.stop_signal {
+            request = request.stop_signal(v.clone());
+        }
+
+        if let Some(check) = &definition.healthcheck {
+            let mut health_check = HealthCheck::default();
+
+            if check.disable {
+                health_check = health_check.test(vec!["NONE"]);
+            }
+            else if !check.test.is_empty() {
+                health_check = health_check.test(check.test.clone());
+            }
+
+            if let Some(v) = check.interval {
+                health_check = health_check.interval(v);
+            }
+
+            if let Some(v) = check.timeout {
+                health_check = health_check.timeout(v);
+            }
+
+            if let Some(v) = check.retries {
+                health_check = health_check.retries(v);
+            }
+
+            if let Some(v) = check.start_period {
+                health_check = health_check.start_period(v);
+            }
+
+            request = request.health_check(health_check);
+        }
+
+        let mut host_config = HostConfig::default();
+
+        if definition.privileged {
+            host_config = host_config.privileged();
+        }
+
+        for cap in &definition.cap_add {
+            host_config = host_config.cap_add(cap.clone());
+        }
+
+        for port in &definition.ports {
+            let container_port = format!("{}/{}", port.target, port.protocol);
+            let host_port = port.published.clone().unwrap_or_default();
+
+            request = request.expose_port(container_port.clone());
+
+            host_config = match &port.host_ip {
+                Some(ip) => host_config.bind_ip(container_port, ip, host_port),
+                None => host_config.bind_port(container_port, host_port)
+            };
+        }
+
+        for volume in &definition.volumes {
+            let mode = if volume.read_only { MountMode::ReadOnly } else { MountMode::Writable };
+
+            match (volume.kind, &volume.source) {
+                (ComposeMountKind::Tmpfs, _) =>
+                    return Err(invalid(format!("tmpfs mounts are not supported, at {}", volume.target))),
+                (_, None) =>
+                    request = request.volume(volume.target.clone()),
+                (ComposeMountKind::Bind, Some(source)) =>
+                    host_config = host_config.mount(self.host_path(source), volume.target.clone(), mode),
+                (ComposeMountKind::Volume, Some(key)) => {
+                    if !self.file.volumes.contains_key(key) {
+                        return Err(invalid(format!("volume {} is not defined in the top-level volumes element", key)));
+                    }
+
+                    host_config = host_config.mount(self.volume_name(key), volume.target.clone(), mode);
+                }
+            }
+        }
+
+        let networks = self.service_networks(service, definition)?;
+
+        if let Some((key, network)) = networks.first() {
+            let network_name = self.network_name(key);
+
+            host_config = host_config.network_mode(network_name.clone());
+
+            request = request.networking_config(
+                NetworkingConfig::default()
+                    .endpoint(network_name, endpoint(service, *network))
+            );
+        }
+
+        Ok(request.host_config(host_config))
+    }
+
+    /// Services in the order they must start, so that each starts after its dependencies.
+    pub fn startup_order(&self) -> Result<Vec<String>, ComposeError> {
+        let mut order = Vec::new();
+
+        for service in self.file.services.keys() {
+            self.visit(service, &mut Vec::new(), &mut order)?;
+        }
+
+        Ok(order)
+    }
+
+    /// Create the networks and volumes, and then create and start the services in dependency
+    /// order. Before a service starts, each of its dependencies must meet its condition.
+    ///
+    /// Networks that already exist are used as they are. If a step fails, what was created
+    /// is left in place; use down to remove it.
+    pub async fn up(&self, dec: &DockerEngineClient) -> Result<Vec<ComposeContainer>, ComposeError> {
+        let order = self.startup_order()?;
+
+        let existing: Vec<String> = dec.networks()
+            .list()
+            .await?
+            .into_iter()
+            .map(|n| n.name)
+            .collect();
+
+        for (key, request) in self.network_keys().into_iter().zip(self.network_requests()) {
+            if !existing.contains(&self.network_name(key)) {
+                dec.networks().create(request).await?;
+            }
+        }
+
+        for request in self.volume_requests() {
+            dec.volumes().create(request).await?;
+        }
+
+        let mut started: HashMap<String, ContainerId> = HashMap::new();
+        let mut containers = Vec::new();
+
+        for service in order {
+            let definition = &self.file.services[&service];
+
+            for (dependency, condition) in &definition.depends_on {
+                self.wait_for(dec, &service, dependency, &started[dependency], condition.condition).await?;
+            }
+
+            let request = self.container_request(&service)?;
+
+            if self.pull {
+                let (repo, tag) = split_image(request.image.as_deref().unwrap_or_default());
+
+                dec.images().pull_if_not_present(repo, tag).await?;
+            }
+
+            let id = dec.containers().create(request).await?.id;
+
+            for (key, network) in self.service_networks(&service, definition)?.into_iter().skip(1) {
+                let connect = ConnectNetworkRequest::default()
+                    .container(id.clone())
+                    .endpoint_config(endpoint(&service, network));
+
+                dec.network(self.network_name(key)).connect(connect).await?;
+            }
+
+            dec.container(&id).start().await?;
+
+            started.insert(service.clone(), id.clone());
+            containers.push(ComposeContainer { service, id });
+        }
+
+        Ok(containers)
+    }
+
+    /// Remove the project's containers, along with their anonymous volumes, and then its
+    /// networks and volumes, found by the project label. Named volumes are removed too, so
+    /// their data is lost.
+    pub async fn down(&self, dec: &DockerEngineClient) -> Result<(), ComposeError> {
+        let containers = dec.containers()
+            .list(
+                ListContainersRequest::default()
+                    .all(true)
+                    .filters(Filters::default().label_value(LABEL_PROJECT, self.name.clone()))
+            )
+            .await?;
+
+        for container in containers {
+            let args = RemoveContainerArgs::default()
+                .force(true)
+                .remove_volumes(true);
+
+            ignore_not_found(dec.container(&container.id).remove_with(args).await)?;
+        }
+
+        for network in dec.networks().list().await? {
+            if self.is_labeled(&network.labels) {
+                ignore_not_found(dec.network(&network.id).remove().await)?;
+            }
+        }
+
+        for volume in dec.volumes().list().await?.volumes {
+            if self.is_labeled(&volume.labels) {
+                ignore_not_found(dec.volume(&volume.name).remove(false).await)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Variables from the service's env_file entries, overridden by its environment element.
+    fn environment(&self, definition: &ComposeService) -> Result<BTreeMap<String, String>, ComposeError> {
+        let mut result = BTreeMap::new();
+
+        for env_file in &definition.env_file {
+            let path = self.directory.join(&env_file.path);
+
+            if !env_file.required && !path.exists() {
+                continue;
+            }
+
+            result.extend(parse_env_file(&read_file(&path)?));
+        }
+
+        for (k, v) in &definition.environment {
+            match v {
+                Some(v) => {
+                    result.insert(k.clone(), v.clone());
+                },
+                None => {
+                    if let Ok(v) = std::env::var(k) {
+                        result.insert(k.clone(), v);
+                    }
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Path on the host of a bind mount source.
+    fn host_path(&self, source: &str) -> String {
+        if let Some(rest) = source.strip_prefix("~/") {
+            if let Ok(home) = std::env::var("HOME") {
+                return Path::new(&home).join(rest).display().to_string();
+            }
+        }
+
+        if Path::new(source).is_absolute() {
+            source.to_string()
+        }
+        else {
+            let directory = std::fs::canonicalize(&self.directory)
+                .unwrap_or_else(|_| self.directory.clone());
+
+            directory
+                .join(source.trim_start_matches("./"))
+                .display()
+                .to_string()
+        }
+    }
+
+    fn is_labeled(&self, labels: &HashMap<String, String>) -> bool {
+        labels.get(LABEL_PROJECT) == Some(&self.name)
+    }
+
+    /// Networks a service is connected to, by key, with the default network if it has none.
+    fn service_networks<'s>(&self, service: &str, definition: &'s ComposeService) -> Result<Vec<(&'s str, Option<&'s ComposeServiceNetwork>)>, ComposeError> {
+        if definition.networks.is_empty() {
+            return Ok(vec![(DEFAULT_NETWORK, None)]);
+        }
+
+        definition.networks
+            .iter()
+            .map(|(key, network)| {
+                if key != DEFAULT_NETWORK && !self.file.networks.contains_key(key) {
+                    Err(ComposeError::InvalidService {
+                        service: service.to_string(),
+                        message: format!("network {} is not defined in the top-level networks element", key)
+                    })
+                }
+                else {
+                    Ok((key.as_str(), Some(network)))
+                }
+            })
+            .collect()
+    }
+
+    /// Depth-first visit of a service's dependencies, adding each service to order after them.
+    fn visit(&self, service: &str, path: &mut Vec<String>, order: &mut Vec<String>) -> Result<(), ComposeError> {
+        if order.iter().any(|s| s == service) {
+            return Ok(());
+        }
+
+        if let Some(start) = path.iter().position(|s| s == service) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(service.to_string());
+
+            return Err(ComposeError::DependencyCycle(cycle));
+        }
+
+        path.push(service.to_string());
+
+        for dependency in self.file.services[service].depends_on.keys() {
+            if !self.file.services.contains_key(dependency) {
+                return Err(ComposeError::InvalidService {
+                    service: service.to_string(),
+                    message: format!("it depends on {}, which is not defined", dependency)
+                });
+            }
+
+            self.visit(dependency, path, order)?;
+        }
+
+        path.pop();
+        order.push(service.to_string());
+
+        Ok(())
+    }
+
+    /// Wait until a started dependency meets its condition.
+    async fn wait_for(&self, dec: &DockerEngineClient, service: &str, dependency: &str, id: &str, condition: ComposeDependencyCondition) -> Result<(), ComposeError> {
+        let failed = |message: String| ComposeError::DependencyFailed {
+            service: service.to_string(),
+            dependency: dependency.to_string(),
+            message
+        };
+
+        match condition {
+            ComposeDependencyCondition::ServiceStarted => {},
+            ComposeDependencyCondition::ServiceHealthy => {
+                dec.container(id)
+                    .wait_until(&Readiness::Healthy, self.timeout)
+                    .await
+                    .map_err(|e| match e {
+                        DecUseError::NotReady { message, .. } => failed(format!("is not healthy: {}", message)),
+                        other => other.into()
+                    })?;
+            },
+            ComposeDependencyCondition::ServiceCompletedSuccessfully => {
+                let waited = dec.container(id)
+                    .with_cancellation(Cancellation::default().timeout(self.timeout))
+                    .wait(WaitCondition::NotRunning)
+                    .await
+                    .map_err(|e| match e {
+                        DecUseError::Timeout { .. } => failed(format!("did not complete within {:?}", self.timeout)),
+                        other => other.into()
+                    })?;
+
+                if waited.exit_code() != 0 {
+                    return Err(failed(format!("exited with exit code {}", waited.exit_code())));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+}
+
+/// Endpoint on a network for a service's container, with the service name as an alias.
+fn endpoint(service: &str, network: Option<&ComposeServiceNetwork>) -> EndpointConfig {
+    let mut config = EndpointConfig::default()
+        .alias(service);
+
+    if let Some(network) = network {
+        for alias in &network.aliases {
+            config = config.alias(alias.clone());
+        }
+
+        if let Some(ip) = &network.ipv4_address {
+            config.ipam_config = config.ipam_config.ipv4_address(ip);
+        }
+    }
+
+    config
+}
+
+fn ignore_not_found(result: Result<(), DecUseError>) -> Result<(), DecUseError> {
+    match result {
+        Err(DecUseError::NotFound { .. }) => Ok(()),
+        other => other
+    }
+}
+
+/// Lowercase letters, digits, dashes and underscores, as Docker Compose requires.
+fn normalize_project_name(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect()
+}
+
+/// Variables in an env_file: KEY=VALUE lines, with optional quotes around the value.
+/// Blank lines and comments are skipped.
+fn parse_env_file(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(k, v)| {
+            let v = v.trim();
+
+            let unquoted = [('"', '"'), ('\'', '\'')]
+                .iter()
+                .find_map(|(open, close)| v.strip_prefix(*open)?.strip_suffix(*close))
+                .unwrap_or(v);
+
+            (k.trim().to_string(), unquoted.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_compose_project {
+
+    mod container_request {
+        use crate::compose::{ComposeFile, ComposeProject};
+        use crate::errors::ComposeError;
+
+        #[test]
+        fn maps_service() {
+            let file = ComposeFile::parse(r#"
+services:
+  web:
+    image: nginx:1.20
+    command: ["nginx", "-g", "daemon off;"]
+    environment:
+      MODE: test
+    healthcheck:
+      test: ["CMD", "curl", "-f", "http://localhost"]
+      retries: 3
+    networks:
+      back:
+      front:
+        aliases: [www]
+    ports:
+      - "127.0.0.1:8080:80"
+    volumes:
+      - data:/data:ro
+      - /cache
+networks:
+  back:
+  front:
+volumes:
+  data:
+"#).unwrap();
+
+            let request = ComposeProject::new("Shop", file)
+                .container_request("web")
+                .unwrap();
+
+            assert_eq!(Some("shop-web-1".into()), request.name);
+            assert_eq!(vec!["nginx", "-g", "daemon off;"], request.cmd);
+            assert_eq!(vec!["MODE=test"], request.env);
+            assert_eq!("shop", request.labels["com.docker.compose.project"]);
+            assert_eq!("web", request.labels["com.docker.compose.service"]);
+            assert!(request.exposed_ports.contains_key("80/tcp"));
+            assert!(request.volumes.contains_key("/cache"));
+            assert!(request.health_check.is_some());
+
+            let host_config = request.host_config.unwrap();
+
+            assert_eq!(vec!["shop_data:/data:ro"], host_config.binds);
+            assert_eq!(Some("shop_back".into()), host_config.network_mode);
+            assert_eq!(Some("127.0.0.1".into()), host_config.port_bindings["80/tcp"][0].host_ip);
+            assert_eq!("8080", host_config.port_bindings["80/tcp"][0].host_port);
+
+            let endpoints = request.networking_config.unwrap().endpoints_config;
+
+            assert_eq!(vec!["web"], endpoints["shop_back"].aliases);
+        }
+
+        #[test]
+        fn reads_env_files() {
+            let directory = tempfile::tempdir().unwrap();
+
+            std::fs::write(directory.path().join("web.env"), "# Settings\nMODE=\"file\"\nLEVEL=debug\n").unwrap();
+
+            let file = ComposeFile::parse(r#"
+services:
+  web:
+    image: nginx
+    env_file:
+      - web.env
+      - path: missing.env
+        required: false
+    environment:
+      MODE: override
+"#).unwrap();
+
+            let request = ComposeProject::new("shop", file)
+                .directory(directory.path())
+                .container_request("web")
+                .unwrap();
+
+            assert_eq!(vec!["LEVEL=debug", "MODE=override"], request.env);
+        }
+
+        #[test]
+        fn undefined_volume() {
+            let file = ComposeFile::parse("services:\n  web:\n    image: nginx\n    volumes: [\"data:/data\"]\n").unwrap();
+
+            match ComposeProject::new("shop", file).container_request("web").unwrap_err() {
+                ComposeError::InvalidService { service, message } => {
+                    assert_eq!("web", service);
+                    assert_eq!("volume data is not defined in the top-level volumes element", message);
+                },
+                other =>
+                    panic!("Unexpected error: {:?}", other)
+            }
+        }
+
+        #[test]
+        fn without_image() {
+            let file = ComposeFile::parse("services:\n  web:\n    build: .\n").unwrap();
+
+            let error = ComposeProject::new("shop", file)
+                .container_request("web")
+                .unwrap_err();
+
+            assert_eq!("Invalid service web: the service has no image; building images is not supported", error.to_string());
+        }
+
+    }
+
+    mod startup_order {
+        use crate::compose::{ComposeFile, ComposeProject};
+
+        #[test]
+        fn cycle() {
+            let file = ComposeFile::parse(r#"
+services:
+  a:
+    depends_on: [b]
+  b:
+    depends_on: [c]
+  c:
+    depends_on: [a]
+"#).unwrap();
+
+            let error = ComposeProject::new("p", file)
+                .startup_order()
+                .unwrap_err();
+
+            assert_eq!("Services depend on each other in a cycle: a -> b -> c -> a", error.to_string());
+        }
+
+        #[test]
+        fn dependencies_first() {
+            let file = ComposeFile::parse(r#"
+services:
+  api:
+    depends_on: [db, cache]
+  cache:
+  db:
+  web:
+    depends_on: [api]
+"#).unwrap();
+
+            let order = ComposeProject::new("p", file)
+                .startup_order()
+                .unwrap();
+
+            assert_eq!(vec!["cache", "db", "api", "web"], order);
+        }
+
+    }
+
+    mod up_and_down {
+        use std::time::Duration;
+
+        use crate::DockerEngineClient;
+        use crate::compose::{ComposeFile, ComposeProject};
+        use crate::errors::ComposeError;
+        use crate::fake::FakeDockerEngine;
+        use crate::requests::ListContainersRequest;
+
+        const FILE: &str = r#"
+services:
+  db:
+    image: postgres:15
+    healthcheck:
+      test: pg_isready
+    volumes:
+      - data:/var/lib/postgresql/data
+  migrate:
+    image: app:1.0
+    command: migrate
+    depends_on:
+      db:
+        condition: service_healthy
+  web:
+    image: app:1.0
+    depends_on:
+      migrate:
+        condition: service_completed_successfully
+    networks:
+      - default
+      - public
+networks:
+  public:
+volumes:
+  data:
+"#;
+
+        async fn until_running(dec: &DockerEngineClient, name: &str) {
+            loop {
+                if let Ok(inspected) = dec.container(name).inspect().await {
+                    if inspected.state.running {
+                        return;
+                    }
+                }
+
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }
+
+        #[tokio::test]
+        async fn starts_in_order_and_removes_by_label() {
+            let engine = FakeDockerEngine::new();
+            let dec = engine.client();
+
+            let project = ComposeProject::new("shop", ComposeFile::parse(FILE).unwrap());
+
+            let up = {
+                let dec = dec.clone();
+                let project = project.clone();
+
+                tokio::spawn(async move { project.up(&dec).await })
+            };
+
+            until_running(&dec, "shop-db-1").await;
+            engine.set_health("shop-db-1", "healthy");
+
+            until_running(&dec, "shop-migrate-1").await;
+            engine.exit_container("shop-migrate-1", 0);
+
+            let containers = up.await
+                .unwrap()
+                .unwrap();
+
+            let services: Vec<&str> = containers
+                .iter()
+                .map(|c| c.service.as_str())
+                .collect();
+
+            assert_eq!(vec!["db", "migrate", "web"], services);
+
+            let web = dec.container("shop-web-1").inspect().await.unwrap();
+
+            let mut networks: Vec<&String> = web.network_settings.networks.keys().collect();
+            networks.sort();
+
+            assert_eq!(vec!["shop_default", "shop_public"], networks);
+            assert_eq!("shop_data", dec.volume("shop_data").inspect().await.unwrap().name);
+
+            project.down(&dec).await.unwrap();
+
+            let remaining = dec.containers()
+                .list(ListContainersRequest::default().all(true))
+                .await
+                .unwrap();
+
+            assert!(remaining.is_empty());
+
+            let network_names: Vec<String> = dec.networks()
+                .list()
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|n| n.name)
+                .collect();
+
+            assert!(!network_names.iter().any(|n| n.starts_with("shop_")), "{:?}", network_names);
+            assert!(dec.volumes().list().await.unwrap().volumes.is_empty());
+        }
+
+        #[tokio::test]
+        async fn stops_when_dependency_fails() {
+            let engine = FakeDockerEngine::new();
+            let dec = engine.client();
+
+            let project = ComposeProject::new("shop", ComposeFile::parse(FILE).unwrap());
+
+            let up = {
+                let dec = dec.clone();
+                let project = project.clone();
+
+                tokio::spawn(async move { project.up(&dec).await })
+            };
+
+            until_running(&dec, "shop-db-1").await;
+            engine.set_health("shop-db-1", "healthy");
+
+            until_running(&dec, "shop-migrate-1").await;
+            engine.exit_container("shop-migrate-1", 2);
+
+            match up.await.unwrap().unwrap_err() {
+                ComposeError::DependencyFailed { service, dependency, message } => {
+                    assert_eq!("web", service);
+                    assert_eq!("migrate", dependency);
+                    assert_eq!("exited with exit code 2", message);
+                },
+                other =>
+                    panic!("Unexpected error: {:?}", other)
+            }
+
+            assert!(dec.container("shop-web-1").inspect().await.is_err());
+
+            project.down(&dec).await.unwrap();
+        }
+
+    }
+
+}
@@ -1,9 +1,13 @@
 
+#[cfg(feature = "compose")]
+mod compose;
 mod dec;
 mod dec_create;
 mod dec_library;
 mod dec_use;
 
+#[cfg(feature = "compose")]
+pub use compose::*;
 pub use dec::*;
 pub use dec_create::*;
 pub use dec_library::*;
@@ -0,0 +1,46 @@
+use crate::errors::DecUseError;
+
+/// An error while reading a Compose file, or bringing its services up or down.
+#[derive(Debug, thiserror::Error)]
+pub enum ComposeError {
+
+    /// A Compose file, or an env_file it refers to, could not be read.
+    #[error("Failed to read {path}: {message}")]
+    Io {
+        path: String,
+        message: String
+    },
+
+    /// The file is not valid YAML, or does not follow the Compose specification.
+    #[error("Invalid Compose file: {0}")]
+    Parse(String),
+
+    /// A service cannot be mapped onto a container, for example because it has no image.
+    #[error("Invalid service {service}: {message}")]
+    InvalidService {
+        service: String,
+        message: String
+    },
+
+    /// Services depend on each other in a cycle, so none of them can start first.
+    #[error("Services depend on each other in a cycle: {}", .0.join(" -> "))]
+    DependencyCycle(Vec<String>),
+
+    /// A service did not start, because the condition of one of its dependencies was not met.
+    #[error("Service {service} cannot start because {dependency} {message}")]
+    DependencyFailed {
+        service: String,
+        dependency: String,
+        message: String
+    },
+
+    #[error("{0}")]
+    DuringUse(DecUseError)
+
+}
+
+impl From<DecUseError> for ComposeError {
+    fn from(other: DecUseError) -> Self {
+        ComposeError::DuringUse(other)
+    }
+}
@@ -1,12 +1,64 @@
+#[cfg(feature = "native-tls")]
 use hyper_tls::native_tls;
 
 /// An error during the creation of Docker Engine client.
 #[derive(Debug, thiserror::Error)]
 pub enum DecCreateError {
 
+    #[cfg(feature = "native-tls")]
     #[error("Failed to create TlsConnector: {0}")]
     TlsConnector(native_tls::Error),
 
+    #[cfg(feature = "rustls")]
+    #[error("Failed to create rustls client configuration: {0}")]
+    Rustls(rustls::Error),
+
+    #[error("Connecting with TLS requires the native-tls or rustls cargo feature")]
+    TlsFeatureDisabled,
+
+    #[error("Docker context {0} not found")]
+    ContextNotFound(String),
+
+    #[error("Failed to read Docker context store file {path}: {message}")]
+    ContextStore {
+        path: String,
+        message: String
+    },
+
+    #[error("Docker context {0} does not have a Docker Engine endpoint")]
+    ContextWithoutEndpoint(String),
+
+    #[error("Invalid HTTP header {name}: {message}")]
+    InvalidHeader {
+        name: String,
+        message: String
+    },
+
+    #[error("Invalid proxy url {url}: {message}")]
+    InvalidProxyUrl {
+        url: String,
+        message: String
+    },
+
+    #[error("Invalid ssh url {0}; expected ssh://[user@]host[:port]")]
+    InvalidSshUrl(String),
+
+    #[error("Failed to read TLS file {path}: {message}")]
+    TlsFile {
+        path: String,
+        message: String
+    },
+
+    #[error("Invalid TLS certificate: {0}")]
+    TlsCertificate(String),
+
+    /// Only one of a client certificate and its private key was provided.
+    #[error("Incomplete TLS client certificate: {0}")]
+    TlsClientIdentity(String),
+
+    #[error("Invalid TLS private key: {0}")]
+    TlsKey(String),
+
     #[error("Unsupported scheme in Docker Engine url")]
     UnsupportedUrlScheme,
 
@@ -20,8 +72,16 @@ pub enum DecCreateError {
 
 }
 
+#[cfg(feature = "native-tls")]
 impl From<native_tls::Error> for DecCreateError {
     fn from(other: native_tls::Error) -> Self {
         DecCreateError::TlsConnector(other)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(feature = "rustls")]
+impl From<rustls::Error> for DecCreateError {
+    fn from(other: rustls::Error) -> Self {
+        DecCreateError::Rustls(other)
+    }
+}
@@ -1,11 +1,14 @@
 use std::fmt::{Display, Formatter};
 use std::string::FromUtf8Error;
+use std::time::Duration;
 
-use hyper::StatusCode;
+use hyper::{Method, StatusCode};
+use crate::client::TransportError;
 use crate::imp::api::DockerEngineApiBuilderError;
 
 use crate::errors::DecLibraryError;
 use crate::model::StreamLineReadError;
+use crate::responses::State;
 use crate::imp::http_proxy::DockerEngineResponseNotUtf8;
 
 /// An error during the use of a Docker Engine client.
@@ -30,6 +33,40 @@ pub enum DecUseError {
         uri: String
     },
 
+    /// Received a 400 Bad Request response, because a parameter of the request was invalid.
+    BadParameter {
+        method: Method,
+        uri: String,
+
+        /// Error message returned by the Docker Engine
+        message: String
+    },
+
+    /// The call was stopped by the cancellation token of a model::Cancellation.
+    Cancelled {
+        uri: String
+    },
+
+    /// Received a 409 Conflict response, such as when a name is already in use,
+    /// or when removing a running container.
+    Conflict {
+        method: Method,
+        uri: String,
+
+        /// Error message returned by the Docker Engine
+        message: String
+    },
+
+    /// Received a 403 Forbidden response, such as when removing a network that
+    /// is in use, or when a registry denied access to an image.
+    Forbidden {
+        method: Method,
+        uri: String,
+
+        /// Error message returned by the Docker Engine
+        message: String
+    },
+
     /// A communication failure occurred while sending an HTTP request or receiving its response.
     HttpClientError(hyper::Error),
 
@@ -48,18 +85,48 @@ pub enum DecUseError {
         message: String
     },
 
+    /// Received a 304 Not Modified response, because the request would not change
+    /// anything, such as starting a container that is already started.
+    NotModified {
+        method: Method,
+        uri: String
+    },
+
+    /// A container did not become ready, because it stopped, or did not meet a model::Readiness
+    /// condition in time.
+    NotReady {
+        /// Container name or id
+        id: String,
+
+        /// What was last observed, such as the last log line or HTTP status
+        message: String,
+
+        /// State of the container when it was last inspected
+        last_state: Box<State>
+    },
+
     /// A problem while reading or parsing a container log or console output stream.
     StreamLineRead(StreamLineReadError),
 
-    /// Docker Engine rejected the request. Any number of failure status
-    /// codes can produce this outcome, including but not limited to:
-    ///
-    /// * 400 Bad Request
-    /// * 409 Conflict
-    /// * 500 Server Error
-    ///
-    /// However, this will not be used for 404 Not Found responses.
+    /// Docker Engine rejected the request with a failure status that does not
+    /// have a more specific variant, such as 406 Not Acceptable.
     Rejected {
+        method: Method,
+        uri: String,
+
+        /// HTTP status returned by the Docker Engine
+        status: StatusCode,
+
+        /// Error message returned by the Docker Engine
+        message: String
+    },
+
+    /// Received a 5xx response, other than 503 Service Unavailable, such as a
+    /// 500 Server Error when a registry could not be reached.
+    ServerError {
+        method: Method,
+        uri: String,
+
         /// HTTP status returned by the Docker Engine
         status: StatusCode,
 
@@ -67,6 +134,37 @@ pub enum DecUseError {
         message: String
     },
 
+    /// The Docker Engine did not accept a connection, or did not complete its response,
+    /// within a timeout configured with DockerEngineClientBuilder, or before the deadline
+    /// of a model::Cancellation.
+    Timeout {
+        uri: String,
+        timeout: Duration
+    },
+
+    /// A custom client::DockerEngineTransport failed to send a request or receive its response.
+    Transport(TransportError),
+
+    /// Received a 401 Unauthorized response, such as when credentials for a registry
+    /// are missing or wrong.
+    Unauthorized {
+        method: Method,
+        uri: String,
+
+        /// Error message returned by the Docker Engine
+        message: String
+    },
+
+    /// Received a 503 Service Unavailable response, such as from a Docker Engine
+    /// that is starting or shutting down, or a node that is not part of a swarm.
+    Unavailable {
+        method: Method,
+        uri: String,
+
+        /// Error message returned by the Docker Engine
+        message: String
+    },
+
     /// Received a response from the HTTP server with an unexpected or missing Content-Type.
     UnexpectedResponseContentType {
         expected: String,
@@ -123,15 +221,36 @@ impl DecUseError {
             Self::ApiNotImplemented { uri } =>
                 format!("Api not implemented at {}", uri),
 
+            Self::BadParameter { method, uri, message } =>
+                format!("Bad parameter in {} {}: {}", method, uri, message),
+
+            Self::Cancelled { uri } =>
+                format!("Request to {} was cancelled", uri),
+
+            Self::Conflict { method, uri, message } =>
+                format!("Conflict for {} {}: {}", method, uri, message),
+
+            Self::Forbidden { method, uri, message } =>
+                format!("Forbidden {} {}: {}", method, uri, message),
+
             Self::Internal(internal) =>
                 internal.message(),
 
             Self::NotFound { message } =>
                 message.clone(),
 
-            Self::Rejected { status, message } =>
+            Self::NotModified { method, uri } =>
+                format!("Nothing modified by {} {}", method, uri),
+
+            Self::NotReady { id, message, .. } =>
+                format!("Container {} is not ready: {}", id, message),
+
+            Self::Rejected { status, message, .. } =>
                 format!("Request rejected with HTTP status: {}: {}", status, message),
 
+            Self::ServerError { method, uri, status, message } =>
+                format!("Server error {} for {} {}: {}", status, method, uri, message),
+
             Self::StreamLineRead(error) =>
                 error.error_message(),
 
@@ -155,6 +274,18 @@ impl DecUseError {
             Self::HttpClientError2(error) =>
                 format!("Response error: {}", error),
 
+            Self::Timeout { uri, timeout } =>
+                format!("Request to {} timed out after {:?}", uri, timeout),
+
+            Self::Transport(error) =>
+                format!("Transport error: {}", error),
+
+            Self::Unauthorized { method, uri, message } =>
+                format!("Unauthorized {} {}: {}", method, uri, message),
+
+            Self::Unavailable { method, uri, message } =>
+                format!("Docker Engine unavailable for {} {}: {}", method, uri, message),
+
             Self::UnexpectedResponseContentType { expected, actual } =>
                 format!(
                     "Expected response Content-Type of {} but {}",
@@ -171,6 +302,35 @@ impl DecUseError {
         }
     }
 
+    /// HTTP status of a failure response from the Docker Engine, if the error was caused by one.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::BadParameter { .. } => Some(StatusCode::BAD_REQUEST),
+            Self::Conflict { .. } => Some(StatusCode::CONFLICT),
+            Self::Forbidden { .. } => Some(StatusCode::FORBIDDEN),
+            Self::NotFound { .. } | Self::ApiNotFound { .. } => Some(StatusCode::NOT_FOUND),
+            Self::ApiNotImplemented { .. } => Some(StatusCode::NOT_IMPLEMENTED),
+            Self::NotModified { .. } => Some(StatusCode::NOT_MODIFIED),
+            Self::Rejected { status, .. } | Self::ServerError { status, .. } => Some(*status),
+            Self::Unauthorized { .. } => Some(StatusCode::UNAUTHORIZED),
+            Self::Unavailable { .. } => Some(StatusCode::SERVICE_UNAVAILABLE),
+            _ => None
+        }
+    }
+
+    /// Classify a failure response that has a message, by its status.
+    pub(crate) fn from_status(method: Method, uri: String, status: StatusCode, message: String) -> Self {
+        match status {
+            StatusCode::BAD_REQUEST => Self::BadParameter { method, uri, message },
+            StatusCode::UNAUTHORIZED => Self::Unauthorized { method, uri, message },
+            StatusCode::FORBIDDEN => Self::Forbidden { method, uri, message },
+            StatusCode::CONFLICT => Self::Conflict { method, uri, message },
+            StatusCode::SERVICE_UNAVAILABLE => Self::Unavailable { method, uri, message },
+            _ if status.is_server_error() => Self::ServerError { method, uri, status, message },
+            _ => Self::Rejected { method, uri, status, message }
+        }
+    }
+
     // A more explicit conversion than a From/Into trait pair. Prevents misconversion of error status responses.
     pub fn from_not_utf8(other: DockerEngineResponseNotUtf8) -> Self {
         Self::UnparseableUtf8Response {
@@ -213,8 +373,23 @@ impl From<url::ParseError> for DecUseError {
 
 #[cfg(test)]
 mod test_error_message_and_display {
+    use hyper::Method;
+
     use crate::errors::DecUseError;
 
+    #[test]
+    pub fn conflict() {
+        let error = DecUseError::Conflict {
+            method: Method::POST,
+            uri: "/containers/create?name=web".into(),
+            message: "name is already in use".into()
+        };
+
+        let actual = format!("{}", error);
+
+        assert_eq!("Conflict for POST /containers/create?name=web: name is already in use".to_string(), actual);
+    }
+
     #[test]
     pub fn response_content_type_missing() {
         let error = DecUseError::UnexpectedResponseContentType {
@@ -239,3 +414,48 @@ mod test_error_message_and_display {
         assert_eq!("Expected response Content-Type of bar but received qux".to_string(), actual);
     }
 }
+
+#[cfg(test)]
+mod test_from_status {
+    use hyper::{Method, StatusCode};
+
+    use crate::errors::DecUseError;
+
+    fn classify(status: u16) -> DecUseError {
+        DecUseError::from_status(Method::DELETE, "/x".into(), StatusCode::from_u16(status).unwrap(), "boom".into())
+    }
+
+    #[test]
+    pub fn distinct_variants() {
+        assert!(matches!(classify(400), DecUseError::BadParameter { .. }));
+        assert!(matches!(classify(401), DecUseError::Unauthorized { .. }));
+        assert!(matches!(classify(403), DecUseError::Forbidden { .. }));
+        assert!(matches!(classify(409), DecUseError::Conflict { .. }));
+        assert!(matches!(classify(500), DecUseError::ServerError { .. }));
+        assert!(matches!(classify(503), DecUseError::Unavailable { .. }));
+        assert!(matches!(classify(406), DecUseError::Rejected { .. }));
+    }
+
+    #[test]
+    pub fn keeps_status_method_and_uri() {
+        for status in [400, 401, 403, 406, 409, 500, 502, 503] {
+            let error = classify(status);
+
+            assert_eq!(Some(status), error.status().map(|s| s.as_u16()));
+
+            match error {
+                DecUseError::BadParameter { method, uri, .. }
+                | DecUseError::Conflict { method, uri, .. }
+                | DecUseError::Forbidden { method, uri, .. }
+                | DecUseError::Rejected { method, uri, .. }
+                | DecUseError::ServerError { method, uri, .. }
+                | DecUseError::Unauthorized { method, uri, .. }
+                | DecUseError::Unavailable { method, uri, .. } => {
+                    assert_eq!(Method::DELETE, method);
+                    assert_eq!("/x", uri);
+                },
+                other => panic!("Unexpected error: {:?}", other)
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Stand-ins for the Docker Engine, for testing code that uses this library without a
+//! Docker Engine: an in-memory fake, and playback of recorded requests and responses.
+//!
+//! Requires the test-util cargo feature.
+
+mod cassette;
+mod engine;
+mod state;
+
+pub use cassette::*;
+pub use engine::FakeDockerEngine;
@@ -0,0 +1,426 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use hyper::{Request, Response, StatusCode};
+use hyper::body::Bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::client::{DockerEngineTransport, TransportError, TransportFuture};
+
+/// Requests and responses exchanged with a Docker Engine, saved as JSON.
+///
+/// Bodies are recorded complete, including streamed bodies such as logs, pull progress
+/// and exec output. Request headers are not recorded, because they may hold registry
+/// credentials.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Cassette {
+    pub interactions: Vec<CassetteInteraction>
+}
+
+impl Cassette {
+
+    /// Read a cassette from a file written by RecordingTransport or Cassette::save.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, std::io::Error> {
+        let text = std::fs::read_to_string(path)?;
+
+        serde_json::from_str(&text)
+            .map_err(std::io::Error::from)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), std::io::Error> {
+        let text = serde_json::to_string_pretty(self)?;
+
+        std::fs::write(path, text)
+    }
+
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CassetteInteraction {
+    pub request: CassetteRequest,
+    pub response: CassetteResponse
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CassetteRequest {
+    pub method: String,
+
+    /// Path and query, without the scheme and host, so that a cassette recorded over a
+    /// unix socket can be replayed by a client of any server.
+    pub path: String,
+
+    #[serde(default, skip_serializing_if = "CassetteBody::is_empty")]
+    pub body: CassetteBody
+}
+
+impl CassetteRequest {
+
+    fn new(request: &Request<Bytes>) -> Self {
+        Self {
+            method: request.method().to_string(),
+            path: request.uri()
+                .path_and_query()
+                .map(|pq| pq.as_str())
+                .unwrap_or("/")
+                .to_string(),
+            body: CassetteBody::new(request.body())
+        }
+    }
+
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CassetteResponse {
+    pub status: u16,
+
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+
+    #[serde(default, skip_serializing_if = "CassetteBody::is_empty")]
+    pub body: CassetteBody
+}
+
+impl CassetteResponse {
+
+    fn new(response: &Response<Bytes>) -> Self {
+        let headers = response.headers()
+            .iter()
+            .filter_map(|(name, value)| value
+                .to_str()
+                .ok()
+                .map(|v| (name.to_string(), v.to_string()))
+            )
+            .collect();
+
+        Self {
+            status: response.status().as_u16(),
+            headers,
+            body: CassetteBody::new(response.body())
+        }
+    }
+
+    fn to_response(&self) -> Result<Response<Bytes>, TransportError> {
+        let mut builder = Response::builder()
+            .status(StatusCode::from_u16(self.status)?);
+
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+
+        Ok(builder.body(self.body.to_bytes()?)?)
+    }
+
+}
+
+/// Body of a request or response. Text is saved as is, and other bodies, such as tar
+/// archives and multiplexed log streams, as hexadecimal.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum CassetteBody {
+    Text(String),
+    Hex(String)
+}
+
+impl Default for CassetteBody {
+    fn default() -> Self {
+        Self::Text(String::new())
+    }
+}
+
+impl CassetteBody {
+
+    fn new(bytes: &[u8]) -> Self {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => Self::Text(text.to_string()),
+            Err(_) => Self::Hex(
+                bytes
+                    .iter()
+                    .fold(String::with_capacity(bytes.len() * 2), |mut hex, b| {
+                        let _ = write!(hex, "{:02x}", b);
+                        hex
+                    })
+            )
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Text(text) => text.is_empty(),
+            Self::Hex(hex) => hex.is_empty()
+        }
+    }
+
+    fn to_bytes(&self) -> Result<Bytes, TransportError> {
+        match self {
+            Self::Text(text) =>
+                Ok(Bytes::from(text.clone())),
+            Self::Hex(hex) => {
+                let bytes = (0..hex.len())
+                    .step_by(2)
+                    .map(|i| hex
+                        .get(i..i + 2)
+                        .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                        .ok_or_else(|| format!("Invalid hex body in cassette at offset {}", i))
+                    )
+                    .collect::<Result<Vec<u8>, String>>()?;
+
+                Ok(Bytes::from(bytes))
+            }
+        }
+    }
+
+}
+
+/// Sends requests with another transport, and saves each request and its response to a
+/// cassette file, for later use by ReplayTransport.
+///
+/// The file is rewritten after every response, so that it is complete even if a test fails.
+/// See DockerEngineClientBuilder::record.
+#[derive(Debug)]
+pub struct RecordingTransport {
+    cassette: Arc<Mutex<Cassette>>,
+    inner: Arc<dyn DockerEngineTransport>,
+    path: PathBuf
+}
+
+impl RecordingTransport {
+
+    pub fn new<P: Into<PathBuf>>(inner: Arc<dyn DockerEngineTransport>, path: P) -> Self {
+        Self {
+            cassette: Arc::new(Mutex::new(Cassette::default())),
+            inner,
+            path: path.into()
+        }
+    }
+
+    fn save(&self, interaction: CassetteInteraction) -> Result<(), std::io::Error> {
+        let mut cassette = self.cassette
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        cassette.interactions.push(interaction);
+        cassette.save(&self.path)
+    }
+
+}
+
+impl DockerEngineTransport for RecordingTransport {
+
+    fn send(&self, request: Request<Bytes>) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let recorded = CassetteRequest::new(&request);
+            let response = self.inner.send(request).await?;
+
+            self.save(CassetteInteraction {
+                request: recorded,
+                response: CassetteResponse::new(&response)
+            })?;
+
+            Ok(response)
+        })
+    }
+
+    fn detached(&self) -> Option<Arc<dyn DockerEngineTransport>> {
+        let inner = self.inner.detached()?;
+
+        Some(Arc::new(Self {
+            cassette: self.cassette.clone(),
+            inner,
+            path: self.path.clone()
+        }))
+    }
+
+}
+
+/// Answers requests with the responses saved in a cassette, without a Docker Engine.
+///
+/// A request is answered by the first unplayed interaction with the same method, path,
+/// query and body. Each interaction is played once, so repeated requests, such as
+/// inspecting a container before and after starting it, get their responses in the
+/// recorded order. A request without a match fails with a transport error.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use passivized_docker_engine_client::DockerEngineClient;
+/// use passivized_docker_engine_client::errors::DecError;
+/// use passivized_docker_engine_client::fake::ReplayTransport;
+///
+/// async fn example() -> Result<(), DecError> {
+///     // Recorded with DockerEngineClient::builder().record("tests/cassettes/list.json")
+///     let replay = ReplayTransport::load("tests/cassettes/list.json")
+///         .expect("Missing cassette");
+///
+///     let dec = DockerEngineClient::builder()
+///         .transport(replay)
+///         .build()?;
+///
+///     let images = dec.images().list().await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ReplayTransport {
+    interactions: Mutex<Vec<(bool, CassetteInteraction)>>
+}
+
+impl ReplayTransport {
+
+    pub fn new(cassette: Cassette) -> Self {
+        Self {
+            interactions: Mutex::new(
+                cassette.interactions
+                    .into_iter()
+                    .map(|i| (false, i))
+                    .collect()
+            )
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, std::io::Error> {
+        Ok(Self::new(Cassette::load(path)?))
+    }
+
+    /// Number of recorded interactions that have not been requested yet.
+    pub fn unplayed(&self) -> usize {
+        self.lock()
+            .iter()
+            .filter(|(played, _)| !played)
+            .count()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<(bool, CassetteInteraction)>> {
+        self.interactions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn play(&self, request: &CassetteRequest) -> Result<Response<Bytes>, TransportError> {
+        let mut interactions = self.lock();
+
+        let (played, interaction) = interactions
+            .iter_mut()
+            .find(|(played, i)| !played && &i.request == request)
+            .ok_or_else(|| format!("No recorded response for {} {}", request.method, request.path))?;
+
+        *played = true;
+
+        interaction.response.to_response()
+    }
+
+}
+
+impl DockerEngineTransport for ReplayTransport {
+
+    fn send(&self, request: Request<Bytes>) -> TransportFuture<'_> {
+        let result = self.play(&CassetteRequest::new(&request));
+
+        Box::pin(async move {
+            result
+        })
+    }
+
+}
+
+#[cfg(test)]
+mod test_cassette {
+
+    mod body {
+        use super::super::CassetteBody;
+
+        #[test]
+        fn round_trips_binary() {
+            let bytes = [1u8, 0, 0, 0, 0, 0, 0, 2, b'h', b'i', 0xff];
+
+            let body = CassetteBody::new(&bytes);
+
+            assert_eq!(CassetteBody::Hex("01000000000000026869ff".into()), body);
+            assert_eq!(&bytes[..], &body.to_bytes().unwrap()[..]);
+        }
+
+        #[test]
+        fn keeps_text() {
+            assert_eq!(CassetteBody::Text("[]".into()), CassetteBody::new(b"[]"));
+        }
+
+    }
+
+    mod record_and_replay {
+        use crate::DockerEngineClient;
+        use crate::errors::DecUseError;
+        use crate::fake::{Cassette, FakeDockerEngine, ReplayTransport};
+        use crate::model::StreamKind;
+        use crate::requests::CreateContainerRequest;
+
+        #[tokio::test]
+        async fn replays_recording() {
+            let dir = tempfile::tempdir()
+                .unwrap();
+
+            let path = dir.path().join("cassette.json");
+
+            let engine = FakeDockerEngine::new()
+                .with_image("nginx:latest");
+
+            let recording = DockerEngineClient::builder()
+                .transport(engine.clone())
+                .record(&path)
+                .build()
+                .unwrap();
+
+            let created = recording.containers()
+                .create(CreateContainerRequest::default().name("web").image("nginx:latest"))
+                .await
+                .unwrap();
+
+            engine.append_log("web", StreamKind::StdErr, "warning");
+
+            recording.container("web").start().await.unwrap();
+            let logs = recording.container("web").logs().await.unwrap();
+            recording.container("missing").inspect().await.unwrap_err();
+
+            let cassette = Cassette::load(&path)
+                .unwrap();
+
+            assert_eq!(4, cassette.interactions.len());
+
+            let replay = ReplayTransport::new(cassette);
+
+            let dec = DockerEngineClient::builder()
+                .server("http://replay.example")
+                .transport(replay)
+                .build()
+                .unwrap();
+
+            let replayed = dec.containers()
+                .create(CreateContainerRequest::default().name("web").image("nginx:latest"))
+                .await
+                .unwrap();
+
+            assert_eq!(created.id, replayed.id);
+
+            dec.container("web").start().await.unwrap();
+
+            assert_eq!(logs, dec.container("web").logs().await.unwrap());
+
+            match dec.container("missing").inspect().await.unwrap_err() {
+                DecUseError::NotFound { message } => assert_eq!("No such container: missing", message),
+                other => panic!("Unexpected error: {:?}", other)
+            }
+
+            // Already played
+            match dec.container("web").start().await.unwrap_err() {
+                DecUseError::Transport(e) => assert!(e.to_string().starts_with("No recorded response for POST /v1."), "{}", e),
+                other => panic!("Unexpected error: {:?}", other)
+            }
+        }
+
+    }
+
+}
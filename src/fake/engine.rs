@@ -0,0 +1,1212 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use hyper::{Method, Request, Response, StatusCode};
+use hyper::body::Bytes;
+use hyper::header::CONTENT_TYPE;
+use serde_json::{json, Value};
+use tokio::sync::Notify;
+
+use crate::DockerEngineClient;
+use crate::client::{DockerEngineTransport, TransportFuture};
+use crate::fake::state::{ContainerStatus, Failure, FakeContainer, FakeImage, FakeNetwork, FakeState, FakeVolume, labels, normalize_reference, VERSION_TEMPLATE};
+use crate::imp::content_type;
+use crate::model::StreamKind;
+
+type Reply = Result<Response<Bytes>, Failure>;
+
+/// An in-memory Docker Engine, for testing code that uses this library without a Docker Engine.
+///
+/// Containers, images, networks and volumes are kept in memory, and requests are answered
+/// with the status codes and error messages of a real Docker Engine. No processes run:
+/// a started container keeps running until it is stopped or killed, or until a test calls
+/// exit_container. Pulling an image always succeeds, without contacting a registry, and
+/// building one adds an image with the requested tag and labels, without running the Dockerfile.
+///
+/// Clones share the same state.
+///
+/// Requests for operations that are not simulated, such as exec and push, are
+/// answered with 501 Not Implemented.
+///
+/// Requires the test-util cargo feature.
+///
+/// # Example
+///
+/// ```rust
+/// use passivized_docker_engine_client::errors::DecError;
+/// use passivized_docker_engine_client::fake::FakeDockerEngine;
+/// use passivized_docker_engine_client::requests::CreateContainerRequest;
+///
+/// async fn example() -> Result<(), DecError> {
+///     let engine = FakeDockerEngine::new()
+///         .with_image("nginx:latest");
+///
+///     let dec = engine.client();
+///
+///     let container = dec.containers()
+///         .create(CreateContainerRequest::default().image("nginx:latest"))
+///         .await?;
+///
+///     dec.container(&container.id).start().await?;
+///
+///     let inspected = dec.container(&container.id).inspect().await?;
+///     assert!(inspected.state.running);
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FakeDockerEngine {
+    changed: Arc<Notify>,
+    state: Arc<Mutex<FakeState>>
+}
+
+impl FakeDockerEngine {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A client that sends its requests to this engine.
+    pub fn client(&self) -> DockerEngineClient {
+        DockerEngineClient::builder()
+            .transport(self.clone())
+            .build()
+            .expect("Failed to build client for fake Docker Engine")
+    }
+
+    /// Add an image, as though it had been pulled or built.
+    pub fn with_image<R: Into<String>>(self, reference: R) -> Self {
+        self.lock().add_image(&reference.into());
+        self
+    }
+
+    /// Append a line to the log of a container, as though its process had written it.
+    ///
+    /// Panics if the container does not exist.
+    pub fn append_log<C: AsRef<str>, T: Into<String>>(&self, container: C, kind: StreamKind, text: T) {
+        let mut state = self.lock();
+
+        state.container(container.as_ref())
+            .expect("Container not found")
+            .logs
+            .push((kind, SystemTime::now(), text.into()));
+    }
+
+    /// Stop a running container, as though its process had exited.
+    ///
+    /// Panics if the container does not exist.
+    pub fn exit_container<C: AsRef<str>>(&self, container: C, exit_code: i64) {
+        self.lock()
+            .container(container.as_ref())
+            .expect("Container not found")
+            .exit(exit_code);
+
+        self.changed.notify_waiters();
+    }
+
+    /// Set the status of a container's health check, such as "starting", "healthy" or "unhealthy",
+    /// as though the container had a health check.
+    ///
+    /// Panics if the container does not exist.
+    pub fn set_health<C: AsRef<str>, S: Into<String>>(&self, container: C, status: S) {
+        self.lock()
+            .container(container.as_ref())
+            .expect("Container not found")
+            .health = Some(status.into());
+    }
+
+    fn lock(&self) -> MutexGuard<'_, FakeState> {
+        // A panic in a test thread must not hide the state from other tests.
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    async fn handle(&self, request: Request<Bytes>) -> Response<Bytes> {
+        let path = request.uri().path().to_string();
+
+        // Unversioned paths are accepted too, like the Docker Engine does.
+        let path = match path.strip_prefix("/v1.") {
+            Some(rest) => rest
+                .find('/')
+                .map(|i| rest[i..].to_string())
+                .unwrap_or_default(),
+            None => path
+        };
+
+        let query: HashMap<String, String> = url::form_urlencoded::parse(request.uri().query().unwrap_or_default().as_bytes())
+            .into_owned()
+            .collect();
+
+        // An image build sends a tar archive, rather than JSON.
+        if request.method() == Method::POST && path == "/build" {
+            return self.build_image(&query).unwrap_or_else(failure);
+        }
+
+        let body: Value = if request.body().is_empty() {
+            Value::Null
+        }
+        else {
+            match serde_json::from_slice(request.body()) {
+                Ok(v) => v,
+                Err(e) => return failure(Failure::bad_request(format!("invalid JSON: {}", e)))
+            }
+        };
+
+        let method = request.method().clone();
+
+        let reply = if let Some(name) = path.strip_prefix("/images/").filter(|n| !["json", "create", "build", "search"].contains(n)) {
+            self.image_item(&method, name, &query)
+        }
+        else {
+            let segments: Vec<&str> = path
+                .trim_start_matches('/')
+                .split('/')
+                .collect();
+
+            self.route(&method, &segments, &query, &body).await
+        };
+
+        reply.unwrap_or_else(failure)
+    }
+
+    async fn route(&self, method: &Method, segments: &[&str], query: &HashMap<String, String>, body: &Value) -> Reply {
+        match (method.as_str(), segments) {
+            ("GET", ["_ping"]) =>
+                Ok(text(StatusCode::OK, "OK")),
+            ("GET", ["version"]) =>
+                Ok(json_text(StatusCode::OK, VERSION_TEMPLATE)),
+
+            ("GET", ["containers", "json"]) =>
+                self.list_containers(query),
+            ("POST", ["containers", "create"]) =>
+                self.create_container(query, body),
+            ("GET", ["containers", id, "json"]) =>
+                self.with_container(id, |c| Ok(json_value(StatusCode::OK, c.inspect()))),
+            ("GET", ["containers", id, "logs"]) =>
+                self.container_logs(id, query),
+            ("POST", ["containers", id, "kill"]) =>
+                self.change_container(id, kill_container),
+            ("POST", ["containers", id, "pause"]) =>
+                self.change_container(id, pause_container),
+            ("POST", ["containers", id, "rename"]) =>
+                self.rename_container(id, query),
+            ("POST", ["containers", id, "start"]) =>
+                self.change_container(id, start_container),
+            ("POST", ["containers", id, "stop"]) =>
+                self.change_container(id, stop_container),
+            ("POST", ["containers", id, "unpause"]) =>
+                self.change_container(id, unpause_container),
+            ("POST", ["containers", id, "wait"]) =>
+                self.wait_container(id, query).await,
+            ("DELETE", ["containers", id]) =>
+                self.remove_container(id, query),
+
+            ("GET", ["images", "json"]) =>
+                Ok(json_value(StatusCode::OK, self.lock().images.iter().map(|i| i.listed()).collect())),
+            ("POST", ["images", "create"]) =>
+                self.pull_image(query),
+
+            ("GET", ["networks"]) =>
+                Ok(json_value(StatusCode::OK, self.lock().networks.iter().map(|n| n.inspect()).collect())),
+            ("POST", ["networks", "create"]) =>
+                self.create_network(body),
+            ("POST", ["networks", id, "connect"]) =>
+                self.connect_network(id, body),
+            ("GET", ["networks", id]) => {
+                let state = self.lock();
+                let index = state.network_index(id)?;
+
+                Ok(json_value(StatusCode::OK, state.networks[index].inspect()))
+            },
+            ("DELETE", ["networks", id]) =>
+                self.remove_network(id),
+
+            ("GET", ["volumes"]) =>
+                Ok(json_value(StatusCode::OK, json!({
+                    "Volumes": self.lock().volumes.iter().map(|v| v.inspect()).collect::<Vec<_>>(),
+                    "Warnings": []
+                }))),
+            ("POST", ["volumes", "create"]) =>
+                self.create_volume(body),
+            ("POST", ["volumes", "prune"]) =>
+                self.prune_volumes(),
+            ("GET", ["volumes", name]) => {
+                let state = self.lock();
+                let index = state.volume_index(name)?;
+
+                Ok(json_value(StatusCode::OK, state.volumes[index].inspect()))
+            },
+            ("PUT", ["volumes", _]) =>
+                Err(Failure::new(StatusCode::SERVICE_UNAVAILABLE, "This node is not a swarm manager. Use \"docker swarm init\" or \"docker swarm join\" to connect this node to swarm and try again.")),
+            ("DELETE", ["volumes", name]) =>
+                self.remove_volume(name),
+
+            (_, ["commit"]) | (_, ["containers", _, _]) | (_, ["distribution", ..]) | (_, ["exec", ..]) | (_, ["images", "search"]) =>
+                Err(Failure::new(StatusCode::NOT_IMPLEMENTED, format!("{} /{} is not supported by FakeDockerEngine", method, segments.join("/")))),
+
+            _ =>
+                Err(Failure::not_found("page not found"))
+        }
+    }
+
+    fn with_container<F>(&self, name_or_id: &str, f: F) -> Reply
+    where
+        F: FnOnce(&FakeContainer) -> Reply
+    {
+        let state = self.lock();
+        let index = state.container_index(name_or_id)?;
+
+        f(&state.containers[index])
+    }
+
+    fn change_container<F>(&self, name_or_id: &str, change: F) -> Reply
+    where
+        F: FnOnce(&mut FakeContainer) -> Result<StatusCode, Failure>
+    {
+        let status = change(self.lock().container(name_or_id)?)?;
+
+        self.changed.notify_waiters();
+
+        Ok(empty(status))
+    }
+
+    fn list_containers(&self, query: &HashMap<String, String>) -> Reply {
+        let all = query.get("all").map(|v| v == "true" || v == "1").unwrap_or_default();
+
+        let filters: HashMap<String, Vec<String>> = match query.get("filters") {
+            None => HashMap::new(),
+            Some(text) => serde_json::from_str(text)
+                .map_err(|e| Failure::bad_request(format!("invalid filter: {}", e)))?
+        };
+
+        let state = self.lock();
+
+        let listed: Vec<Value> = state.containers
+            .iter()
+            .rev()
+            .filter(|c| all || c.is_running())
+            .filter(|c| filters
+                .get("label")
+                .map(|wanted| wanted.iter().all(|l| match l.split_once('=') {
+                    None => c.labels.contains_key(l),
+                    Some((k, v)) => c.labels.get(k).map(|actual| actual == v).unwrap_or_default()
+                }))
+                .unwrap_or(true)
+            )
+            .filter(|c| filters
+                .get("name")
+                .map(|wanted| wanted.iter().any(|n| c.name.contains(n.trim_start_matches('/'))))
+                .unwrap_or(true)
+            )
+            .filter(|c| filters
+                .get("status")
+                .map(|wanted| wanted.iter().any(|s| s == c.status.name()))
+                .unwrap_or(true)
+            )
+            .map(|c| c.listed())
+            .collect();
+
+        Ok(json_value(StatusCode::OK, Value::Array(listed)))
+    }
+
+    fn create_container(&self, query: &HashMap<String, String>, body: &Value) -> Reply {
+        let image = body["Image"]
+            .as_str()
+            .filter(|i| !i.is_empty())
+            .ok_or_else(|| Failure::bad_request("Config cannot be empty in order to create a container"))?;
+
+        let mut state = self.lock();
+
+        let image_id = state.image_index(image)
+            .map(|index| state.images[index].id.clone())
+            .map_err(|_| Failure::not_found(format!("No such image: {}", normalize_reference(image))))?;
+
+        let name = match query.get("name") {
+            Some(name) => {
+                let name = name.trim_start_matches('/').to_string();
+
+                if let Some(other) = state.containers.iter().find(|c| c.name == name) {
+                    return Err(Failure::conflict(format!(
+                        "Conflict. The container name \"/{}\" is already in use by container \"{}\". You have to remove (or rename) that container to be able to reuse that name.",
+                        name,
+                        other.id
+                    )));
+                }
+
+                name
+            },
+            None => state.new_container_name()
+        };
+
+        let mut networks = Vec::new();
+
+        match body["HostConfig"]["NetworkMode"].as_str() {
+            None | Some("") | Some("default") | Some("bridge") | Some("host") | Some("none") => {},
+            Some(mode) if mode.starts_with("container:") => {},
+            Some(mode) => {
+                let index = state.network_index(mode)?;
+                networks.push(state.networks[index].name.clone());
+            }
+        }
+
+        let id = state.new_id();
+        let ip_address = state.new_ip_address();
+
+        let mut ports = Vec::new();
+
+        if let Some(bindings) = body["HostConfig"]["PortBindings"].as_object() {
+            for (container_port, bindings) in bindings {
+                for binding in bindings.as_array().into_iter().flatten() {
+                    let host_port = match binding["HostPort"].as_str() {
+                        Some(port) if !port.is_empty() && port != "0" => port.to_string(),
+                        _ => state.new_host_port()
+                    };
+
+                    ports.push((container_port.clone(), host_port));
+                }
+            }
+        }
+
+        // Like the Docker Engine, a container with a health check is starting until it passes.
+        let health = body["Healthcheck"]["Test"]
+            .as_array()
+            .filter(|test| !test.is_empty() && test[0] != "NONE")
+            .map(|_| "starting".to_string());
+
+        let cmd = body["Cmd"]
+            .as_array()
+            .map(|args| args.iter().filter_map(|a| a.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+
+        state.containers.push(FakeContainer {
+            id: id.clone(),
+            name,
+            image: image.to_string(),
+            image_id,
+            cmd,
+            labels: labels(&body["Labels"]),
+            created: SystemTime::now(),
+            status: ContainerStatus::Created,
+            exit_code: 0,
+            exits: 0,
+            started_at: None,
+            finished_at: None,
+            health,
+            ip_address,
+            networks,
+            ports,
+            logs: Vec::new()
+        });
+
+        Ok(json_value(StatusCode::CREATED, json!({ "Id": id, "Warnings": [] })))
+    }
+
+    fn container_logs(&self, name_or_id: &str, query: &HashMap<String, String>) -> Reply {
+        let flag = |name: &str| query.get(name).map(|v| v == "true" || v == "1").unwrap_or_default();
+
+        if !flag("stdout") && !flag("stderr") {
+            return Err(Failure::bad_request("You must choose at least one stream"));
+        }
+
+        let since = query.get("since")
+            .map(|v| parse_since(v))
+            .transpose()?;
+
+        self.with_container(name_or_id, |c| {
+            let stream = c.log_stream(flag("stdout"), flag("stderr"), flag("timestamps"), since);
+
+            Ok(response(StatusCode::OK, content_type::STREAM, stream))
+        })
+    }
+
+    fn rename_container(&self, name_or_id: &str, query: &HashMap<String, String>) -> Reply {
+        let new_name = query.get("name")
+            .map(|n| n.trim_start_matches('/').to_string())
+            .filter(|n| !n.is_empty())
+            .ok_or_else(|| Failure::bad_request("Neither old nor new names may be empty"))?;
+
+        let mut state = self.lock();
+        let index = state.container_index(name_or_id)?;
+
+        if let Some(other) = state.containers.iter().find(|c| c.name == new_name && c.id != state.containers[index].id) {
+            return Err(Failure::conflict(format!(
+                "Error when allocating new name: Conflict. The container name \"/{}\" is already in use by container \"{}\". You have to remove (or rename) that container to be able to reuse that name.",
+                new_name,
+                other.id
+            )));
+        }
+
+        state.containers[index].name = new_name;
+
+        Ok(empty(StatusCode::NO_CONTENT))
+    }
+
+    fn remove_container(&self, name_or_id: &str, query: &HashMap<String, String>) -> Reply {
+        let force = query.get("force").map(|v| v == "true" || v == "1").unwrap_or_default();
+
+        let mut state = self.lock();
+        let index = state.container_index(name_or_id)?;
+        let container = &state.containers[index];
+
+        if container.is_running() && !force {
+            return Err(Failure::conflict(format!(
+                "You cannot remove a {} container {}. {} the container before attempting removal or force remove",
+                container.status.name(),
+                container.id,
+                if container.status == ContainerStatus::Paused { "Unpause and then stop" } else { "Stop" }
+            )));
+        }
+
+        state.containers.remove(index);
+        drop(state);
+
+        self.changed.notify_waiters();
+
+        Ok(empty(StatusCode::NO_CONTENT))
+    }
+
+    /// Wait for the container to reach the condition, without holding the state lock.
+    async fn wait_container(&self, name_or_id: &str, query: &HashMap<String, String>) -> Reply {
+        let condition = query.get("condition")
+            .map(String::as_str)
+            .unwrap_or("not-running");
+
+        let (id, exits_before) = {
+            let mut state = self.lock();
+            let container = state.container(name_or_id)?;
+
+            (container.id.clone(), container.exits)
+        };
+
+        let mut last_exit_code = 0;
+
+        loop {
+            let changed = self.changed.notified();
+            tokio::pin!(changed);
+            changed.as_mut().enable();
+
+            {
+                let state = self.lock();
+                let container = state.containers.iter().find(|c| c.id == id);
+
+                let done = match (condition, container) {
+                    ("removed", None) => true,
+                    (_, None) => return Err(Failure::not_found(format!("No such container: {}", name_or_id))),
+                    ("next-exit", Some(c)) => c.exits > exits_before,
+                    ("removed", Some(_)) => false,
+                    (_, Some(c)) => !c.is_running()
+                };
+
+                if let Some(c) = container {
+                    last_exit_code = c.exit_code;
+                }
+
+                if done {
+                    return Ok(json_value(StatusCode::OK, json!({ "StatusCode": last_exit_code, "Error": null })));
+                }
+            }
+
+            changed.await;
+        }
+    }
+
+    /// Add an image with the tag and labels of a build, without running its Dockerfile.
+    fn build_image(&self, query: &HashMap<String, String>) -> Reply {
+        let labels: HashMap<String, String> = match query.get("labels") {
+            Some(text) => serde_json::from_str(text)
+                .map_err(|e| Failure::bad_request(format!("error reading labels: {}", e)))?,
+            None => HashMap::new()
+        };
+
+        let tag = query.get("t")
+            .map(|t| normalize_reference(t));
+
+        let mut state = self.lock();
+        let id = format!("sha256:{}", state.new_id());
+
+        if let Some(tag) = &tag {
+            for image in state.images.iter_mut() {
+                image.repo_tags.retain(|t| t != tag);
+            }
+        }
+
+        state.images.push(FakeImage {
+            id: id.clone(),
+            repo_tags: tag.iter().cloned().collect(),
+            labels,
+            created: SystemTime::now()
+        });
+
+        let mut lines = vec![
+            json!({ "stream": format!("Successfully built {}\n", &id.trim_start_matches("sha256:")[..12]) })
+        ];
+
+        if let Some(tag) = tag {
+            lines.push(json!({ "stream": format!("Successfully tagged {}\n", tag) }));
+        }
+
+        let text: String = lines
+            .iter()
+            .map(|l| format!("{}\r\n", l))
+            .collect();
+
+        Ok(response(StatusCode::OK, content_type::JSON, text.into_bytes()))
+    }
+
+    fn image_item(&self, method: &Method, path: &str, query: &HashMap<String, String>) -> Reply {
+        if let (&Method::GET, Some(name)) = (method, path.strip_suffix("/json")) {
+            let state = self.lock();
+            let index = state.image_index(name)?;
+
+            return Ok(json_value(StatusCode::OK, state.images[index].inspect()));
+        }
+
+        if let (&Method::POST, Some(name)) = (method, path.strip_suffix("/tag")) {
+            return self.tag_image(name, query);
+        }
+
+        if method == Method::DELETE {
+            return self.remove_image(path, query);
+        }
+
+        Err(Failure::new(StatusCode::NOT_IMPLEMENTED, format!("{} /images/{} is not supported by FakeDockerEngine", method, path)))
+    }
+
+    fn pull_image(&self, query: &HashMap<String, String>) -> Reply {
+        let from_image = query.get("fromImage")
+            .ok_or_else(|| Failure::bad_request("Importing an image from a file or url is not supported by FakeDockerEngine"))?;
+
+        let reference = match query.get("tag") {
+            Some(tag) if !tag.is_empty() => format!("{}:{}", from_image, tag),
+            _ => normalize_reference(from_image)
+        };
+
+        let mut state = self.lock();
+
+        let status = if state.image_index(&reference).is_ok() {
+            format!("Status: Image is up to date for {}", reference)
+        }
+        else {
+            state.add_image(&reference);
+            format!("Status: Downloaded newer image for {}", reference)
+        };
+
+        let lines = [
+            json!({ "status": format!("Pulling from {}", from_image), "id": reference.rsplit(':').next() }),
+            json!({ "status": status })
+        ];
+
+        let text: String = lines
+            .iter()
+            .map(|l| format!("{}\r\n", l))
+            .collect();
+
+        Ok(response(StatusCode::OK, content_type::JSON, text.into_bytes()))
+    }
+
+    fn tag_image(&self, name: &str, query: &HashMap<String, String>) -> Reply {
+        let repo = query.get("repo")
+            .filter(|r| !r.is_empty())
+            .ok_or_else(|| Failure::bad_request("repository name must have at least one component"))?;
+
+        let new_reference = match query.get("tag") {
+            Some(tag) if !tag.is_empty() => format!("{}:{}", repo, tag),
+            _ => normalize_reference(repo)
+        };
+
+        let mut state = self.lock();
+        let index = state.image_index(name)?;
+
+        for image in state.images.iter_mut() {
+            image.repo_tags.retain(|t| t != &new_reference);
+        }
+
+        state.images[index].repo_tags.push(new_reference);
+
+        Ok(empty(StatusCode::CREATED))
+    }
+
+    fn remove_image(&self, name: &str, query: &HashMap<String, String>) -> Reply {
+        let force = query.get("force").map(|v| v == "true" || v == "1").unwrap_or_default();
+
+        let mut state = self.lock();
+        let index = state.image_index(name)?;
+        let reference = normalize_reference(name);
+        let image_id = state.images[index].id.clone();
+
+        let untagging = state.images[index].repo_tags.contains(&reference);
+
+        if !force && (!untagging || state.images[index].repo_tags.len() == 1) {
+            if let Some(user) = state.containers.iter().find(|c| c.image_id == image_id) {
+                return Err(Failure::conflict(format!(
+                    "conflict: unable to delete {} (must be forced) - image is being used by stopped container {}",
+                    &image_id.trim_start_matches("sha256:")[..12],
+                    &user.id[..12]
+                )));
+            }
+        }
+
+        let mut removed = Vec::new();
+
+        if untagging {
+            state.images[index].repo_tags.retain(|t| t != &reference);
+            removed.push(json!({ "Untagged": reference }));
+        }
+
+        if state.images[index].repo_tags.is_empty() || !untagging {
+            state.images.remove(index);
+            removed.push(json!({ "Deleted": image_id }));
+        }
+
+        Ok(json_value(StatusCode::OK, Value::Array(removed)))
+    }
+
+    fn create_network(&self, body: &Value) -> Reply {
+        let name = body["Name"]
+            .as_str()
+            .filter(|n| !n.is_empty())
+            .ok_or_else(|| Failure::bad_request("network name is required"))?;
+
+        let mut state = self.lock();
+
+        if state.networks.iter().any(|n| n.name == name) {
+            return Err(Failure::conflict(format!("network with name {} already exists", name)));
+        }
+
+        let id = state.new_id();
+
+        state.networks.push(FakeNetwork {
+            id: id.clone(),
+            name: name.to_string(),
+            driver: body["Driver"].as_str().unwrap_or("bridge").to_string(),
+            labels: labels(&body["Labels"]),
+            created: SystemTime::now(),
+            predefined: false
+        });
+
+        Ok(json_value(StatusCode::CREATED, json!({ "Id": id, "Warning": "" })))
+    }
+
+    fn connect_network(&self, name_or_id: &str, body: &Value) -> Reply {
+        let mut state = self.lock();
+        let network = state.network_index(name_or_id)?;
+        let name = state.networks[network].name.clone();
+
+        let container_name_or_id = body["Container"]
+            .as_str()
+            .unwrap_or_default();
+
+        let container = state.container_index(container_name_or_id)?;
+        let container = &mut state.containers[container];
+
+        if container.networks.contains(&name) {
+            return Err(Failure::new(
+                StatusCode::FORBIDDEN,
+                format!("endpoint with name {} already exists in network {}", container.name, name)
+            ));
+        }
+
+        container.networks.push(name);
+
+        Ok(empty(StatusCode::OK))
+    }
+
+    fn remove_network(&self, name_or_id: &str) -> Reply {
+        let mut state = self.lock();
+        let index = state.network_index(name_or_id)?;
+
+        let network = &state.networks[index];
+
+        if state.containers.iter().any(|c| c.is_running() && c.networks.contains(&network.name)) {
+            return Err(Failure::new(
+                StatusCode::FORBIDDEN,
+                format!("error while removing network: network {} id {} has active endpoints", network.name, network.id)
+            ));
+        }
+
+        if state.networks[index].predefined {
+            return Err(Failure::new(
+                StatusCode::FORBIDDEN,
+                format!("{} is a pre-defined network and cannot be removed", state.networks[index].name)
+            ));
+        }
+
+        state.networks.remove(index);
+
+        Ok(empty(StatusCode::NO_CONTENT))
+    }
+
+    fn create_volume(&self, body: &Value) -> Reply {
+        let mut state = self.lock();
+
+        let name = match body["Name"].as_str().filter(|n| !n.is_empty()) {
+            Some(name) => name.to_string(),
+            None => state.new_id()
+        };
+
+        // Like the Docker Engine, creating an existing volume returns it.
+        let index = match state.volume_index(&name) {
+            Ok(index) => index,
+            Err(_) => {
+                state.volumes.push(FakeVolume {
+                    name,
+                    driver: body["Driver"].as_str().unwrap_or("local").to_string(),
+                    labels: labels(&body["Labels"]),
+                    options: labels(&body["DriverOpts"]),
+                    created: SystemTime::now()
+                });
+
+                state.volumes.len() - 1
+            }
+        };
+
+        Ok(json_value(StatusCode::CREATED, state.volumes[index].inspect()))
+    }
+
+    fn prune_volumes(&self) -> Reply {
+        let mut state = self.lock();
+
+        let deleted: Vec<String> = state.volumes
+            .drain(..)
+            .map(|v| v.name)
+            .collect();
+
+        Ok(json_value(StatusCode::OK, json!({ "VolumesDeleted": deleted, "SpaceReclaimed": 0 })))
+    }
+
+    fn remove_volume(&self, name: &str) -> Reply {
+        let mut state = self.lock();
+        let index = state.volume_index(name)?;
+
+        state.volumes.remove(index);
+
+        Ok(empty(StatusCode::NO_CONTENT))
+    }
+
+}
+
+impl DockerEngineTransport for FakeDockerEngine {
+
+    fn send(&self, request: Request<Bytes>) -> TransportFuture<'_> {
+        Box::pin(async move {
+            Ok(self.handle(request).await)
+        })
+    }
+
+}
+
+fn kill_container(container: &mut FakeContainer) -> Result<StatusCode, Failure> {
+    if !container.is_running() {
+        return Err(Failure::conflict(format!("Container {} is not running", container.id)));
+    }
+
+    container.exit(137);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn pause_container(container: &mut FakeContainer) -> Result<StatusCode, Failure> {
+    match container.status {
+        ContainerStatus::Running => {
+            container.status = ContainerStatus::Paused;
+            Ok(StatusCode::NO_CONTENT)
+        },
+        ContainerStatus::Paused =>
+            Err(Failure::conflict(format!("Container {} is already paused", container.id))),
+        _ =>
+            Err(Failure::conflict(format!("Container {} is not running", container.id)))
+    }
+}
+
+fn start_container(container: &mut FakeContainer) -> Result<StatusCode, Failure> {
+    if container.is_running() {
+        return Ok(StatusCode::NOT_MODIFIED);
+    }
+
+    container.status = ContainerStatus::Running;
+    container.exit_code = 0;
+    container.started_at = Some(SystemTime::now());
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn stop_container(container: &mut FakeContainer) -> Result<StatusCode, Failure> {
+    if !container.is_running() {
+        return Ok(StatusCode::NOT_MODIFIED);
+    }
+
+    container.exit(0);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn unpause_container(container: &mut FakeContainer) -> Result<StatusCode, Failure> {
+    if container.status != ContainerStatus::Paused {
+        return Err(Failure::conflict(format!("Container {} is not paused", container.id)));
+    }
+
+    container.status = ContainerStatus::Running;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Parse the since parameter of a logs request, a UNIX timestamp with optional fractional seconds.
+fn parse_since(value: &str) -> Result<SystemTime, Failure> {
+    let (seconds, fraction) = value.split_once('.').unwrap_or((value, "0"));
+
+    let seconds: u64 = seconds.parse()
+        .map_err(|_| Failure::bad_request(format!("invalid since: {}", value)))?;
+
+    let nanos: u32 = format!("{:0<9}", fraction)
+        .get(..9)
+        .and_then(|digits| digits.parse().ok())
+        .ok_or_else(|| Failure::bad_request(format!("invalid since: {}", value)))?;
+
+    Ok(SystemTime::UNIX_EPOCH + Duration::new(seconds, nanos))
+}
+
+fn response(status: StatusCode, content_type: &str, body: Vec<u8>) -> Response<Bytes> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, content_type)
+        .body(Bytes::from(body))
+        .expect("Invalid fake response")
+}
+
+fn empty(status: StatusCode) -> Response<Bytes> {
+    Response::builder()
+        .status(status)
+        .body(Bytes::new())
+        .expect("Invalid fake response")
+}
+
+fn failure(failure: Failure) -> Response<Bytes> {
+    json_value(failure.status, json!({ "message": failure.message }))
+}
+
+fn json_text(status: StatusCode, text: &str) -> Response<Bytes> {
+    response(status, content_type::JSON, text.as_bytes().to_vec())
+}
+
+fn json_value(status: StatusCode, value: Value) -> Response<Bytes> {
+    response(status, content_type::JSON, value.to_string().into_bytes())
+}
+
+fn text(status: StatusCode, text: &str) -> Response<Bytes> {
+    response(status, "text/plain; charset=utf-8", text.as_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod test_fake_docker_engine {
+
+    mod containers {
+        use crate::errors::DecUseError;
+        use crate::fake::FakeDockerEngine;
+        use crate::model::StreamKind;
+        use crate::requests::{CreateContainerRequest, Filters, ListContainersRequest, WaitCondition};
+
+        #[tokio::test]
+        async fn runs_through_lifecycle() {
+            let engine = FakeDockerEngine::new()
+                .with_image("nginx:latest");
+
+            let dec = engine.client();
+
+            let created = dec.containers()
+                .create(CreateContainerRequest::default()
+                    .name("web")
+                    .image("nginx")
+                    .label("app", "fake")
+                )
+                .await
+                .unwrap();
+
+            let container = dec.container(&created.id);
+
+            assert!(!container.inspect().await.unwrap().state.running);
+
+            container.start().await.unwrap();
+
+            let inspected = dec.container("web").inspect().await.unwrap();
+            assert_eq!(created.id, inspected.id);
+            assert!(inspected.state.running);
+
+            let listed = dec.containers()
+                .list(ListContainersRequest::default().filters(Filters::default().label_value("app", "fake")))
+                .await
+                .unwrap();
+
+            assert_eq!(1, listed.len());
+
+            engine.append_log("web", StreamKind::StdOut, "hello");
+
+            let logs = container.logs().await.unwrap();
+            assert_eq!(1, logs.len());
+            assert_eq!("hello", logs[0].text);
+
+            container.stop().await.unwrap();
+
+            let waited = container.wait(WaitCondition::NotRunning).await.unwrap();
+            assert_eq!(0, waited.exit_code());
+
+            container.remove().await.unwrap();
+
+            match dec.container("web").inspect().await.unwrap_err() {
+                DecUseError::NotFound { message } => assert_eq!("No such container: web", message),
+                other => panic!("Unexpected error: {:?}", other)
+            }
+        }
+
+        #[tokio::test]
+        async fn rejects_conflicts() {
+            let engine = FakeDockerEngine::new()
+                .with_image("nginx:latest");
+
+            let dec = engine.client();
+
+            let request = CreateContainerRequest::default()
+                .name("web")
+                .image("nginx:latest");
+
+            dec.containers().create(request.clone()).await.unwrap();
+
+            match dec.containers().create(request).await.unwrap_err() {
+                DecUseError::Conflict { .. } => {},
+                other => panic!("Unexpected error: {:?}", other)
+            }
+
+            dec.container("web").start().await.unwrap();
+
+            match dec.container("web").remove().await.unwrap_err() {
+                DecUseError::Conflict { .. } => {},
+                other => panic!("Unexpected error: {:?}", other)
+            }
+        }
+
+        #[tokio::test]
+        async fn reports_not_modified() {
+            let engine = FakeDockerEngine::new()
+                .with_image("nginx:latest");
+
+            let dec = engine.client();
+
+            dec.containers()
+                .create(CreateContainerRequest::default().name("web").image("nginx:latest"))
+                .await
+                .unwrap();
+
+            let container = dec.container("web");
+
+            container.start_checked().await.unwrap();
+
+            match container.start_checked().await.unwrap_err() {
+                DecUseError::NotModified { method, uri } => {
+                    assert_eq!(http::Method::POST, method);
+                    assert!(uri.ends_with("/containers/web/start"), "Unexpected uri: {}", uri);
+                },
+                other => panic!("Unexpected error: {:?}", other)
+            }
+
+            container.start().await.unwrap();
+
+            container.stop_checked().await.unwrap();
+
+            match container.stop_checked().await.unwrap_err() {
+                DecUseError::NotModified { .. } => {},
+                other => panic!("Unexpected error: {:?}", other)
+            }
+
+            container.stop().await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn rejects_missing_image() {
+            let dec = FakeDockerEngine::new().client();
+
+            let request = CreateContainerRequest::default()
+                .image("nginx:latest");
+
+            match dec.containers().create(request).await.unwrap_err() {
+                DecUseError::NotFound { message } => assert_eq!("No such image: nginx:latest", message),
+                other => panic!("Unexpected error: {:?}", other)
+            }
+        }
+
+        #[tokio::test]
+        async fn waits_until_exit() {
+            let engine = FakeDockerEngine::new()
+                .with_image("nginx:latest");
+
+            let dec = engine.client();
+
+            let created = dec.containers()
+                .create(CreateContainerRequest::default().image("nginx:latest"))
+                .await
+                .unwrap();
+
+            dec.container(&created.id).start().await.unwrap();
+
+            let waiter = {
+                let container = dec.container(&created.id).into_owned();
+
+                tokio::spawn(async move {
+                    container.wait(WaitCondition::NotRunning).await
+                })
+            };
+
+            tokio::task::yield_now().await;
+            assert!(!waiter.is_finished());
+
+            engine.exit_container(&created.id, 3);
+
+            let waited = waiter
+                .await
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(3, waited.exit_code());
+        }
+
+    }
+
+    mod images {
+        use crate::errors::DecUseError;
+        use crate::fake::FakeDockerEngine;
+
+        #[tokio::test]
+        async fn pulls_tags_and_untags() {
+            let dec = FakeDockerEngine::new().client();
+
+            dec.images().pull("alpine", "3.19").await.unwrap();
+            dec.images().tag("alpine:3.19", "example/alpine", "test").await.unwrap();
+
+            let inspected = dec.images().inspect("example/alpine:test").await.unwrap();
+            assert!(inspected.repo_tags.contains(&"alpine:3.19".to_string()));
+
+            assert_eq!(1, dec.images().list().await.unwrap().len());
+
+            dec.images().untag("alpine:3.19").await.unwrap();
+            dec.images().untag("example/alpine:test").await.unwrap();
+
+            match dec.images().inspect("alpine:3.19").await.unwrap_err() {
+                DecUseError::NotFound { .. } => {},
+                other => panic!("Unexpected error: {:?}", other)
+            }
+        }
+
+    }
+
+    mod networks {
+        use crate::errors::DecUseError;
+        use crate::fake::FakeDockerEngine;
+        use crate::requests::{ConnectNetworkRequest, CreateContainerRequest, CreateNetworkRequest, HostConfig};
+
+        #[tokio::test]
+        async fn connects_containers() {
+            let dec = FakeDockerEngine::new()
+                .with_image("nginx")
+                .client();
+
+            for name in ["front", "back"] {
+                dec.networks()
+                    .create(CreateNetworkRequest::default().name(name))
+                    .await
+                    .unwrap();
+            }
+
+            let request = CreateContainerRequest::default()
+                .name("web")
+                .image("nginx")
+                .host_config(HostConfig::default().network_mode("front"));
+
+            dec.containers().create(request).await.unwrap();
+
+            dec.network("back")
+                .connect(ConnectNetworkRequest::default().container("web"))
+                .await
+                .unwrap();
+
+            dec.container("web").start().await.unwrap();
+
+            let inspected = dec.container("web").inspect().await.unwrap();
+
+            let mut networks: Vec<&String> = inspected.network_settings.networks.keys().collect();
+            networks.sort();
+
+            assert_eq!(vec!["back", "front"], networks);
+
+            match dec.network("front").remove().await.unwrap_err() {
+                DecUseError::Forbidden { .. } => {},
+                other => panic!("Unexpected error: {:?}", other)
+            }
+
+            let listed: Vec<String> = dec.networks()
+                .list()
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|n| n.name)
+                .collect();
+
+            assert!(listed.contains(&"back".to_string()));
+            assert!(listed.contains(&"bridge".to_string()));
+        }
+
+        #[tokio::test]
+        async fn creates_and_removes() {
+            let dec = FakeDockerEngine::new().client();
+
+            let created = dec.networks()
+                .create(CreateNetworkRequest::default().name("backend"))
+                .await
+                .unwrap();
+
+            let inspected = dec.network("backend").inspect().await.unwrap();
+            assert_eq!(created.id, inspected.id);
+
+            dec.network(&created.id).remove().await.unwrap();
+
+            match dec.network("bridge").remove().await.unwrap_err() {
+                DecUseError::Forbidden { .. } => {},
+                other => panic!("Unexpected error: {:?}", other)
+            }
+        }
+
+    }
+
+    mod volumes {
+        use crate::fake::FakeDockerEngine;
+        use crate::requests::CreateVolumeRequest;
+
+        #[tokio::test]
+        async fn creates_lists_and_prunes() {
+            let dec = FakeDockerEngine::new().client();
+
+            let named = dec.volumes()
+                .create(CreateVolumeRequest::default().name("data"))
+                .await
+                .unwrap();
+
+            assert_eq!("data", named.name);
+
+            let anonymous = dec.volumes()
+                .create(CreateVolumeRequest::default())
+                .await
+                .unwrap();
+
+            assert_eq!(64, anonymous.name.len());
+
+            assert_eq!(2, dec.volumes().list().await.unwrap().volumes.len());
+
+            dec.volume("data").remove(false).await.unwrap();
+
+            let pruned = dec.volumes().prune().await.unwrap();
+            assert_eq!(vec![anonymous.name], pruned.volumes_deleted);
+        }
+
+    }
+
+}
@@ -0,0 +1,532 @@
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hyper::StatusCode;
+use serde_json::{json, Value};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::model::StreamKind;
+
+// Templates for responses, shared with the json mapping tests.
+const CONTAINER_TEMPLATE: &str = include_str!("../../tests/fixtures/fedora-inspect-running-container-response.json");
+const CONTAINER_TEMPLATE_ID: &str = "0325c3004bba5d70787cc51f76380ebe11e11b42e6548a3dfe5bd1c3c56a249e";
+const IMAGE_TEMPLATE: &str = include_str!("../../tests/fixtures/image-inspect-response.json");
+const NETWORK_TEMPLATE: &str = include_str!("../../tests/fixtures/network-inspect-response.json");
+pub(super) const VERSION_TEMPLATE: &str = include_str!("../../tests/fixtures/version-response.json");
+
+/// A failed request, answered with an error status and a Docker Engine style error body.
+#[derive(Debug)]
+pub(super) struct Failure {
+    pub status: StatusCode,
+    pub message: String
+}
+
+impl Failure {
+
+    pub fn new<M: Into<String>>(status: StatusCode, message: M) -> Self {
+        Self {
+            status,
+            message: message.into()
+        }
+    }
+
+    pub fn bad_request<M: Into<String>>(message: M) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+
+    pub fn conflict<M: Into<String>>(message: M) -> Self {
+        Self::new(StatusCode::CONFLICT, message)
+    }
+
+    pub fn not_found<M: Into<String>>(message: M) -> Self {
+        Self::new(StatusCode::NOT_FOUND, message)
+    }
+
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum ContainerStatus {
+    Created,
+    Running,
+    Paused,
+    Exited
+}
+
+impl ContainerStatus {
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Running => "running",
+            Self::Paused => "paused",
+            Self::Exited => "exited"
+        }
+    }
+
+}
+
+#[derive(Debug)]
+pub(super) struct FakeContainer {
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    pub image_id: String,
+    pub cmd: Vec<String>,
+    pub labels: HashMap<String, String>,
+    pub created: SystemTime,
+    pub status: ContainerStatus,
+    pub exit_code: i64,
+    /// Number of times the container has stopped running.
+    pub exits: u64,
+    pub started_at: Option<SystemTime>,
+    pub finished_at: Option<SystemTime>,
+    /// Status of the health check, if the container has one.
+    pub health: Option<String>,
+    pub ip_address: String,
+    /// Names of the user-defined networks the container is connected to. When empty, the
+    /// container is on the default bridge network.
+    pub networks: Vec<String>,
+    /// Published ports, as container port and protocol, and host port.
+    pub ports: Vec<(String, String)>,
+    pub logs: Vec<(StreamKind, SystemTime, String)>
+}
+
+impl FakeContainer {
+
+    pub fn is_running(&self) -> bool {
+        matches!(self.status, ContainerStatus::Running | ContainerStatus::Paused)
+    }
+
+    /// Stop the container's process.
+    pub fn exit(&mut self, exit_code: i64) {
+        self.status = ContainerStatus::Exited;
+        self.exit_code = exit_code;
+        self.exits += 1;
+        self.finished_at = Some(SystemTime::now());
+    }
+
+    fn status_text(&self) -> String {
+        match self.status {
+            ContainerStatus::Created => "Created".into(),
+            ContainerStatus::Running => "Up".into(),
+            ContainerStatus::Paused => "Up (Paused)".into(),
+            ContainerStatus::Exited => format!("Exited ({})", self.exit_code)
+        }
+    }
+
+    pub fn inspect(&self) -> Value {
+        let mut result: Value = serde_json::from_str(&CONTAINER_TEMPLATE.replace(CONTAINER_TEMPLATE_ID, &self.id))
+            .expect("Invalid container template");
+
+        let ip_address = if self.is_running() { self.ip_address.as_str() } else { "" };
+
+        result["Name"] = json!(format!("/{}", self.name));
+        result["Created"] = json!(timestamp(self.created));
+        result["Path"] = json!(self.cmd.first().cloned().unwrap_or_default());
+        result["Args"] = json!(self.cmd.iter().skip(1).collect::<Vec<_>>());
+        result["Image"] = json!(self.image_id);
+
+        result["State"] = json!({
+            "Status": self.status.name(),
+            "Running": self.is_running(),
+            "Paused": self.status == ContainerStatus::Paused,
+            "Restarting": false,
+            "OOMKilled": false,
+            "Dead": false,
+            "Pid": if self.is_running() { 1000 } else { 0 },
+            "ExitCode": self.exit_code,
+            "Error": "",
+            "StartedAt": self.started_at.map(timestamp).unwrap_or_else(zero_timestamp),
+            "FinishedAt": self.finished_at.map(timestamp).unwrap_or_else(zero_timestamp)
+        });
+
+        if let Some(status) = &self.health {
+            result["State"]["Health"] = json!({
+                "Status": status,
+                "FailingStreak": if status == "unhealthy" { 3 } else { 0 },
+                "Log": []
+            });
+        }
+
+        result["Config"]["Hostname"] = json!(self.id[..12]);
+        result["Config"]["Image"] = json!(self.image);
+        result["Config"]["Cmd"] = json!(self.cmd);
+        result["Config"]["Labels"] = json!(self.labels);
+
+        let mut ports = serde_json::Map::new();
+
+        if self.is_running() {
+            for (container_port, host_port) in &self.ports {
+                ports.insert(container_port.clone(), json!([{ "HostIp": "0.0.0.0", "HostPort": host_port }]));
+            }
+        }
+
+        result["NetworkSettings"]["IPAddress"] = json!(ip_address);
+        result["NetworkSettings"]["Ports"] = Value::Object(ports);
+        result["NetworkSettings"]["Networks"]["bridge"]["IPAddress"] = json!(ip_address);
+
+        if !self.networks.is_empty() {
+            let endpoint = result["NetworkSettings"]["Networks"]["bridge"].clone();
+
+            result["NetworkSettings"]["Networks"] = self.networks
+                .iter()
+                .map(|name| (name.clone(), endpoint.clone()))
+                .collect();
+        }
+
+        result
+    }
+
+    pub fn listed(&self) -> Value {
+        json!({
+            "Id": self.id,
+            "Names": [format!("/{}", self.name)],
+            "Image": self.image,
+            "ImageID": self.image_id,
+            "Command": self.cmd.join(" "),
+            "Created": unix_seconds(self.created),
+            "Ports": [],
+            "Labels": self.labels,
+            "State": self.status.name(),
+            "Status": self.status_text(),
+            "HostConfig": {
+                "NetworkMode": "default"
+            },
+            "NetworkSettings": {
+                "Networks": {}
+            },
+            "Mounts": []
+        })
+    }
+
+    /// Log lines in the multiplexed stream format, optionally prefixed with timestamps.
+    /// Lines logged at or after since, if it is set, as the Docker Engine does.
+    pub fn log_stream(&self, stdout: bool, stderr: bool, timestamps: bool, since: Option<SystemTime>) -> Vec<u8> {
+        let mut result = Vec::new();
+
+        for (kind, at, text) in &self.logs {
+            if since.is_some_and(|since| *at < since) {
+                continue;
+            }
+
+            let stream_type = match kind {
+                StreamKind::StdOut if stdout => 1u8,
+                StreamKind::StdErr if stderr => 2u8,
+                _ => continue
+            };
+
+            let line = if timestamps {
+                format!("{} {}", log_timestamp(*at), text)
+            }
+            else {
+                text.clone()
+            };
+
+            result.extend_from_slice(&[stream_type, 0, 0, 0]);
+            result.extend_from_slice(&(line.len() as u32).to_be_bytes());
+            result.extend_from_slice(line.as_bytes());
+        }
+
+        result
+    }
+
+}
+
+#[derive(Debug)]
+pub(super) struct FakeImage {
+    pub id: String,
+    pub repo_tags: Vec<String>,
+    pub labels: HashMap<String, String>,
+    pub created: SystemTime
+}
+
+impl FakeImage {
+
+    pub fn inspect(&self) -> Value {
+        let mut result: Value = serde_json::from_str(IMAGE_TEMPLATE)
+            .expect("Invalid image template");
+
+        result["Id"] = json!(self.id);
+        result["RepoTags"] = json!(self.repo_tags);
+        result["RepoDigests"] = json!([]);
+        result["Created"] = json!(timestamp(self.created));
+        result["Config"]["Labels"] = json!(self.labels);
+
+        result
+    }
+
+    pub fn listed(&self) -> Value {
+        json!({
+            "Id": self.id,
+            "ParentId": "",
+            "RepoTags": self.repo_tags,
+            "RepoDigests": [],
+            "Created": unix_seconds(self.created),
+            "Size": 4039416,
+            "SharedSize": -1,
+            "VirtualSize": 4039416,
+            "Labels": self.labels,
+            "Containers": -1
+        })
+    }
+
+}
+
+#[derive(Debug)]
+pub(super) struct FakeNetwork {
+    pub id: String,
+    pub name: String,
+    pub driver: String,
+    pub labels: HashMap<String, String>,
+    pub created: SystemTime,
+    /// The bridge, host and none networks, which cannot be removed.
+    pub predefined: bool
+}
+
+impl FakeNetwork {
+
+    pub fn inspect(&self) -> Value {
+        let mut result: Value = serde_json::from_str(NETWORK_TEMPLATE)
+            .expect("Invalid network template");
+
+        result["Name"] = json!(self.name);
+        result["Id"] = json!(self.id);
+        result["Created"] = json!(timestamp(self.created));
+        result["Driver"] = json!(self.driver);
+        result["Labels"] = json!(self.labels);
+
+        result
+    }
+
+}
+
+#[derive(Debug)]
+pub(super) struct FakeVolume {
+    pub name: String,
+    pub driver: String,
+    pub labels: HashMap<String, String>,
+    pub options: HashMap<String, String>,
+    pub created: SystemTime
+}
+
+impl FakeVolume {
+
+    pub fn inspect(&self) -> Value {
+        json!({
+            "CreatedAt": timestamp(self.created),
+            "Driver": self.driver,
+            "Labels": self.labels,
+            "Mountpoint": format!("/var/lib/docker/volumes/{}/_data", self.name),
+            "Name": self.name,
+            "Options": self.options,
+            "Scope": "local"
+        })
+    }
+
+}
+
+/// Everything the fake Docker Engine knows about.
+#[derive(Debug)]
+pub(super) struct FakeState {
+    sequence: u64,
+    host_ports: u16,
+    pub containers: Vec<FakeContainer>,
+    pub images: Vec<FakeImage>,
+    pub networks: Vec<FakeNetwork>,
+    pub volumes: Vec<FakeVolume>
+}
+
+impl Default for FakeState {
+    fn default() -> Self {
+        let mut result = Self {
+            sequence: 0,
+            host_ports: 0,
+            containers: Vec::new(),
+            images: Vec::new(),
+            networks: Vec::new(),
+            volumes: Vec::new()
+        };
+
+        for (name, driver) in [("bridge", "bridge"), ("host", "host"), ("none", "null")] {
+            let id = result.new_id();
+
+            result.networks.push(FakeNetwork {
+                id,
+                name: name.into(),
+                driver: driver.into(),
+                labels: HashMap::new(),
+                created: SystemTime::now(),
+                predefined: true
+            });
+        }
+
+        result
+    }
+}
+
+impl FakeState {
+
+    /// A random 64 digit hex ID, like those of the Docker Engine.
+    pub fn new_id(&mut self) -> String {
+        self.sequence += 1;
+
+        (0..4u64)
+            .map(|part| {
+                let mut hasher = RandomState::new().build_hasher();
+                hasher.write_u64(self.sequence);
+                hasher.write_u64(part);
+                format!("{:016x}", hasher.finish())
+            })
+            .collect()
+    }
+
+    /// A name for a container created without one, like the Docker Engine's adjective_surname.
+    pub fn new_container_name(&mut self) -> String {
+        format!("fake_container_{}", self.sequence + 1)
+    }
+
+    /// An ephemeral port, for a port binding without a host port.
+    pub fn new_host_port(&mut self) -> String {
+        self.host_ports += 1;
+
+        (32767 + self.host_ports).to_string()
+    }
+
+    pub fn new_ip_address(&self) -> String {
+        format!("172.17.{}.{}", (self.sequence / 250) % 250, self.sequence % 250 + 2)
+    }
+
+    pub fn container_index(&self, name_or_id: &str) -> Result<usize, Failure> {
+        let name = name_or_id.trim_start_matches('/');
+
+        let exact = self.containers
+            .iter()
+            .position(|c| c.id == name_or_id || c.name == name);
+
+        if let Some(index) = exact {
+            return Ok(index);
+        }
+
+        let by_prefix: Vec<usize> = self.containers
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !name_or_id.is_empty() && c.id.starts_with(name_or_id))
+            .map(|(i, _)| i)
+            .collect();
+
+        match by_prefix.as_slice() {
+            [index] => Ok(*index),
+            [] => Err(Failure::not_found(format!("No such container: {}", name_or_id))),
+            _ => Err(Failure::not_found(format!("Multiple IDs found with provided prefix: {}", name_or_id)))
+        }
+    }
+
+    pub fn container(&mut self, name_or_id: &str) -> Result<&mut FakeContainer, Failure> {
+        let index = self.container_index(name_or_id)?;
+
+        Ok(&mut self.containers[index])
+    }
+
+    pub fn image_index(&self, name_or_id: &str) -> Result<usize, Failure> {
+        let reference = normalize_reference(name_or_id);
+        let id = name_or_id.strip_prefix("sha256:").unwrap_or(name_or_id);
+
+        self.images
+            .iter()
+            .position(|i| {
+                i.repo_tags.contains(&reference)
+                    || (!id.is_empty() && i.id.trim_start_matches("sha256:").starts_with(id))
+            })
+            .ok_or_else(|| Failure::not_found(format!("No such image: {}", name_or_id)))
+    }
+
+    /// Add an image, or move the tag of an existing one.
+    pub fn add_image(&mut self, reference: &str) -> &FakeImage {
+        let reference = normalize_reference(reference);
+
+        if let Ok(index) = self.image_index(&reference) {
+            return &self.images[index];
+        }
+
+        let id = format!("sha256:{}", self.new_id());
+
+        self.images.push(FakeImage {
+            id,
+            repo_tags: vec![reference],
+            labels: HashMap::new(),
+            created: SystemTime::now()
+        });
+
+        self.images.last().unwrap()
+    }
+
+    pub fn network_index(&self, name_or_id: &str) -> Result<usize, Failure> {
+        self.networks
+            .iter()
+            .position(|n| n.id == name_or_id || n.name == name_or_id || (name_or_id.len() >= 12 && n.id.starts_with(name_or_id)))
+            .ok_or_else(|| Failure::not_found(format!("network {} not found", name_or_id)))
+    }
+
+    pub fn volume_index(&self, name: &str) -> Result<usize, Failure> {
+        self.volumes
+            .iter()
+            .position(|v| v.name == name)
+            .ok_or_else(|| Failure::not_found(format!("get {}: no such volume", name)))
+    }
+
+}
+
+/// Add the implied latest tag to an image reference without one.
+pub(super) fn normalize_reference(reference: &str) -> String {
+    let name = reference
+        .rsplit('/')
+        .next()
+        .unwrap_or(reference);
+
+    if name.contains(':') || name.contains('@') {
+        reference.to_string()
+    }
+    else {
+        format!("{}:latest", reference)
+    }
+}
+
+pub(super) fn labels(value: &Value) -> HashMap<String, String> {
+    serde_json::from_value(value.clone())
+        .unwrap_or_default()
+}
+
+fn unix_seconds(at: SystemTime) -> u64 {
+    at.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn timestamp(at: SystemTime) -> String {
+    OffsetDateTime::from(at)
+        .format(&Rfc3339)
+        .expect("Unformattable timestamp")
+}
+
+fn zero_timestamp() -> String {
+    "0001-01-01T00:00:00Z".into()
+}
+
+/// Timestamp of a log line, always with nine digits of fractional seconds, like the Docker Engine.
+fn log_timestamp(at: SystemTime) -> String {
+    let at = OffsetDateTime::from(at);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:09}Z",
+        at.year(),
+        at.month() as u8,
+        at.day(),
+        at.hour(),
+        at.minute(),
+        at.second(),
+        at.nanosecond()
+    )
+}
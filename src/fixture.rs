@@ -0,0 +1,326 @@
+//! Containers for integration tests, declared up front, started and checked for readiness
+//! in one call, and removed when dropped.
+//!
+//! Requires the test-util cargo feature.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use passivized_docker_engine_client::DockerEngineClient;
+//! use passivized_docker_engine_client::errors::DecError;
+//! use passivized_docker_engine_client::fixture::ContainerFixture;
+//! use passivized_docker_engine_client::model::Readiness;
+//!
+//! async fn example() -> Result<(), DecError> {
+//!     let dec = DockerEngineClient::new()?;
+//!
+//!     let nginx = ContainerFixture::new("nginx:1.20")
+//!         .port("80")
+//!         .ready_when(Readiness::LogContains("start worker process".into()))
+//!         .start(&dec)
+//!         .await?;
+//!
+//!     let url = format!("http://localhost:{}", nginx.host_port("80").unwrap());
+//!
+//!     // Use the server at url. The container is removed when nginx goes out of scope,
+//!     // even if the test panics.
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::time::Duration;
+
+use crate::DockerEngineClient;
+use crate::client::DecContainer;
+use crate::client::shared::{remove_container_blocking, split_image};
+use crate::errors::DecUseError;
+use crate::model::{ContainerId, Readiness};
+use crate::requests::CreateContainerRequest;
+use crate::responses::InspectContainerResponse;
+
+/// How long ContainerFixture::start waits for readiness, unless set.
+pub const DEFAULT_READY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A declaration of a container for a test. See ContainerFixture::start.
+#[derive(Clone, Debug)]
+pub struct ContainerFixture {
+    ports: Vec<String>,
+    pull: bool,
+    readiness: Readiness,
+    request: CreateContainerRequest,
+    timeout: Duration
+}
+
+impl ContainerFixture {
+
+    /// A fixture for a container of an image, such as "nginx:1.20". Without a tag, "latest" is used.
+    pub fn new<I: Into<String>>(image: I) -> Self {
+        Self::from_request(CreateContainerRequest::default().image(image))
+    }
+
+    /// A fixture for a container with all the settings of a create request. The request must
+    /// have an image.
+    pub fn from_request(request: CreateContainerRequest) -> Self {
+        Self {
+            ports: Vec::new(),
+            pull: true,
+            readiness: Readiness::Running,
+            request,
+            timeout: DEFAULT_READY_TIMEOUT
+        }
+    }
+
+    /// Set the command to run.
+    pub fn cmd<V: ToString>(mut self, v: Vec<V>) -> Self {
+        self.request = self.request.cmd(v);
+        self
+    }
+
+    /// Add an environment variable.
+    pub fn env<K: Into<String>, V: Into<String>>(mut self, k: K, v: V) -> Self {
+        self.request = self.request.env(format!("{}={}", k.into(), v.into()));
+        self
+    }
+
+    pub fn label<K: Into<String>, V: Into<String>>(mut self, k: K, v: V) -> Self {
+        self.request = self.request.label(k.into(), v.into());
+        self
+    }
+
+    /// Publish a container port, such as "80" or "53/udp", on a host port chosen by the
+    /// Docker Engine. See FixtureGuard::host_port.
+    pub fn port<P: Into<String>>(mut self, container_port: P) -> Self {
+        self.ports.push(with_protocol(&container_port.into()));
+        self
+    }
+
+    /// Whether to pull the image if it is not present. Defaults to true.
+    pub fn pull(mut self, v: bool) -> Self {
+        self.pull = v;
+        self
+    }
+
+    /// When the container is ready. Defaults to Readiness::Running. See DecContainer::wait_until.
+    pub fn ready_when(mut self, v: Readiness) -> Self {
+        self.readiness = v;
+        self
+    }
+
+    /// How long to wait for the container to become ready. Defaults to DEFAULT_READY_TIMEOUT.
+    pub fn timeout(mut self, v: Duration) -> Self {
+        self.timeout = v;
+        self
+    }
+
+    /// Pull the image if needed, then create and start the container, and wait until it is ready.
+    ///
+    /// If any step fails, a container that was created is removed.
+    pub async fn start(self, dec: &DockerEngineClient) -> Result<FixtureGuard, DecUseError> {
+        let mut request = self.request;
+
+        if self.pull {
+            let image = request.image
+                .as_deref()
+                .unwrap_or_default();
+
+            let (repo, tag) = split_image(image);
+
+            dec.images().pull_if_not_present(repo, tag).await?;
+        }
+
+        if !self.ports.is_empty() {
+            let mut host_config = request.host_config
+                .take()
+                .unwrap_or_default();
+
+            for port in self.ports {
+                request = request.expose_port(port.clone());
+                host_config = host_config.bind_port(port, "");
+            }
+
+            request = request.host_config(host_config);
+        }
+
+        let created = dec.containers().create(request).await?;
+
+        // From here on, dropping the guard removes the container.
+        let mut guard = FixtureGuard {
+            client: dec.clone(),
+            id: created.id,
+            inspected: None
+        };
+
+        guard.container().start().await?;
+
+        let inspected = guard.container()
+            .wait_until(&self.readiness, self.timeout)
+            .await?;
+
+        guard.inspected = Some(inspected);
+
+        Ok(guard)
+    }
+
+}
+
+/// A container started by ContainerFixture::start.
+///
+/// When dropped, including while a test panics, the container is force-removed, along with
+/// its anonymous volumes. Removal runs to completion on a separate thread before drop returns.
+#[derive(Debug)]
+pub struct FixtureGuard {
+    client: DockerEngineClient,
+    id: ContainerId,
+    inspected: Option<InspectContainerResponse>
+}
+
+impl FixtureGuard {
+
+    /// Access the container, for example to read its logs or run a command in it.
+    pub fn container(&self) -> DecContainer<'_> {
+        self.client.container(&self.id)
+    }
+
+    /// Host port that a published container port, such as "80" or "53/udp", is mapped to.
+    pub fn host_port<P: AsRef<str>>(&self, container_port: P) -> Option<u16> {
+        self.inspected()
+            .network_settings
+            .host_port(container_port.as_ref())
+    }
+
+    pub fn id(&self) -> &ContainerId {
+        &self.id
+    }
+
+    /// Details of the container, as inspected once it became ready.
+    pub fn inspected(&self) -> &InspectContainerResponse {
+        self.inspected
+            .as_ref()
+            .expect("FixtureGuard is only returned once ready")
+    }
+
+    /// IP address of the container on its first network. See NetworkSettings::first_ip_address.
+    pub fn ip_address(&self) -> Option<&str> {
+        self.inspected().first_ip_address()
+    }
+
+}
+
+impl Drop for FixtureGuard {
+    fn drop(&mut self) {
+        remove_container_blocking(&self.client, &self.id);
+    }
+}
+
+/// Container port with a protocol, as keyed in NetworkSettings::ports.
+fn with_protocol(container_port: &str) -> String {
+    if container_port.contains('/') {
+        container_port.to_string()
+    }
+    else {
+        format!("{}/tcp", container_port)
+    }
+}
+
+#[cfg(test)]
+mod test_fixture {
+
+    mod start {
+        use std::time::Duration;
+
+        use crate::errors::DecUseError;
+        use crate::fake::FakeDockerEngine;
+        use crate::fixture::ContainerFixture;
+        use crate::model::Readiness;
+        use crate::requests::ListContainersRequest;
+
+        #[tokio::test]
+        async fn maps_ports_and_removes_on_drop() {
+            let engine = FakeDockerEngine::new();
+            let dec = engine.client();
+
+            let guard = ContainerFixture::new("nginx:1.20")
+                .port("80")
+                .port("53/udp")
+                .env("NGINX_PORT", "80")
+                .start(&dec)
+                .await
+                .unwrap();
+
+            let mut ports = [guard.host_port("80/tcp").unwrap(), guard.host_port("53/udp").unwrap()];
+            ports.sort();
+
+            assert_eq!([32768, 32769], ports);
+            assert_eq!(None, guard.host_port("443"));
+            assert!(guard.ip_address().is_some());
+            assert!(guard.container().inspect().await.unwrap().state.running);
+
+            drop(guard);
+
+            let remaining = dec.containers()
+                .list(ListContainersRequest::default().all(true))
+                .await
+                .unwrap();
+
+            assert!(remaining.is_empty());
+        }
+
+        #[tokio::test]
+        async fn removes_after_panic() {
+            let engine = FakeDockerEngine::new();
+
+            let failed = {
+                let dec = engine.client();
+
+                tokio::spawn(async move {
+                    let _guard = ContainerFixture::new("nginx")
+                        .start(&dec)
+                        .await
+                        .unwrap();
+
+                    panic!("Test failed");
+                })
+            };
+
+            assert!(failed.await.unwrap_err().is_panic());
+
+            let remaining = engine.client()
+                .containers()
+                .list(ListContainersRequest::default().all(true))
+                .await
+                .unwrap();
+
+            assert!(remaining.is_empty());
+        }
+
+        #[tokio::test]
+        async fn removes_when_not_ready() {
+            let engine = FakeDockerEngine::new();
+            let dec = engine.client();
+
+            let error = ContainerFixture::new("nginx")
+                .ready_when(Readiness::LogContains("ready".into()))
+                .timeout(Duration::from_millis(250))
+                .start(&dec)
+                .await
+                .unwrap_err();
+
+            match error {
+                DecUseError::NotReady { message, .. } =>
+                    assert_eq!("timed out after 250ms waiting until log contains \"ready\": the container has not logged anything", message),
+                other =>
+                    panic!("Unexpected error: {:?}", other)
+            }
+
+            let remaining = dec.containers()
+                .list(ListContainersRequest::default().all(true))
+                .await
+                .unwrap();
+
+            assert!(remaining.is_empty());
+        }
+
+    }
+
+}
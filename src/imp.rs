@@ -2,13 +2,27 @@
 
 pub(crate) mod api;
 pub(crate) mod content_type;
+pub(crate) mod context;
 pub(crate) mod env;
+pub(crate) mod forward_proxy;
 pub(crate) mod http_proxy;
 pub(crate) mod hyper_proxy;
+pub(crate) mod probe;
 pub(crate) mod serde;
+pub(crate) mod ssh;
+pub(crate) mod timestamp;
+pub(crate) mod tls;
 pub(crate) mod url;
 pub(crate) mod url_parser;
 
 // Internal to imp crate
 mod hyper_shims;
 mod other;
+
+#[cfg(feature = "native-tls")]
+mod tls_native;
+
+// When both TLS features are enabled, rustls is only used for a caller supplied ClientConfig.
+#[cfg(feature = "rustls")]
+#[cfg_attr(feature = "native-tls", allow(dead_code))]
+mod tls_rustls;
@@ -7,12 +7,17 @@ use serde::Serialize;
 
 use crate::client::DOCKER_ENGINE_VERSION;
 use crate::errors::DecCreateError;
+use crate::imp::ssh::{SSH_TUNNEL_URL, SshDestination};
 use crate::imp::url::UrlBuilder;
-use crate::imp::url_parser::is_http;
-use crate::requests::{BuildImageRequest, CreateImageRequest, ListContainersRequest, LogsArgs, RemoveContainerArgs, WaitCondition};
+use crate::imp::url_parser::{is_http, is_ssh};
+use crate::model::Platform;
+use crate::requests::{BuildImageRequest, CommitRequest, CreateImageRequest, ListContainersRequest, LogsArgs, RemoveContainerArgs, SearchImagesFilters, WaitCondition};
 
 pub(crate) const DOCKER_ENGINE_VERSION_PATH: &str = concat!("/", DOCKER_ENGINE_VERSION);
 
+/// Api version that added cluster volumes, and updating volumes.
+const CLUSTER_VOLUME_VERSION: &str = "v1.42";
+
 /// Docker Engine connection reference:
 ///
 /// https://docs.docker.com/desktop/faqs/general/
@@ -21,6 +26,12 @@ pub(crate) enum DockerEngineApiBase {
     /// Explicit HTTP or HTTPS
     Http(String),
 
+    /// HTTP tunneled over ssh, such as ssh://user@host:22
+    Ssh {
+        display_url: String,
+        destination: SshDestination
+    },
+
     /// Implicit HTTP. This is common on Windows, such as tcp://localhost:2375
     Tcp {
         display_url: String,
@@ -36,6 +47,7 @@ impl DockerEngineApiBase {
     pub fn display_url(&self) -> &String {
         match self {
             DockerEngineApiBase::Http(url) => url,
+            DockerEngineApiBase::Ssh { display_url, destination: _ } => display_url,
             DockerEngineApiBase::Tcp { display_url, implied_url: _ } => display_url,
 
             #[cfg(unix)]
@@ -53,6 +65,9 @@ impl DockerEngineApiBase {
                     SchemedUrl::Http(url.clone())
                 },
 
+            DockerEngineApiBase::Ssh { display_url: _display_url, destination } =>
+                SchemedUrl::Ssh(destination.clone()),
+
             DockerEngineApiBase::Tcp { display_url: _display_url, implied_url } =>
                 SchemedUrl::Http(implied_url.clone()),
 
@@ -135,6 +150,12 @@ impl DockerEngineServer {
                         if is_http(&server) {
                             Ok(DockerEngineApiBase::Http(server))
                         }
+                        else if is_ssh(&server) {
+                            Ok(DockerEngineApiBase::Ssh {
+                                destination: SshDestination::parse(&server)?,
+                                display_url: server
+                            })
+                        }
                         else {
                             Err(DecCreateError::UnsupportedUrlScheme)
                         }
@@ -144,11 +165,31 @@ impl DockerEngineServer {
         }
     }
 
+    /// Host where ports published by containers can be reached, assuming that they are
+    /// published on all of the Docker Engine's addresses.
+    fn published_host(&self) -> String {
+        let url_host = |url: &str| url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.trim_start_matches('[').trim_end_matches(']').to_string()))
+            .unwrap_or_else(|| "localhost".to_string());
+
+        match &self.base {
+            DockerEngineApiBase::Http(url) => url_host(url),
+            DockerEngineApiBase::Ssh { destination, .. } => destination.host.clone(),
+            DockerEngineApiBase::Tcp { implied_url, .. } => url_host(implied_url),
+            #[cfg(unix)]
+            DockerEngineApiBase::Unix(_) => "localhost".to_string()
+        }
+    }
+
     fn as_string(&self) -> String {
         match &self.base {
             DockerEngineApiBase::Http(url) => {
                 url.to_string()
             },
+            DockerEngineApiBase::Ssh { .. } => {
+                SSH_TUNNEL_URL.to_string()
+            },
             DockerEngineApiBase::Tcp { implied_url, ..} => {
                 implied_url.to_string()
             },
@@ -169,11 +210,20 @@ pub(crate) struct DockerEngineApi {
     // Validated and cleaned URL without trailing forward slash
     base: String,
 
+    // Base without the api version
+    server: String,
+
+    // Api version in the base, such as "v1.41", or empty if there is none
+    version: String,
+
     // Validated but original URL provided by application.
     //
     // Windows clients may provide a tcp:// url that connects to a http server,
     // and Unix clients may provide a user-friendly path that is resolved to a file descriptor URL.
-    display_url: String
+    display_url: String,
+
+    // Host for connecting to ports published by containers.
+    published_host: String
 }
 
 impl Display for DockerEngineApi {
@@ -184,9 +234,17 @@ impl Display for DockerEngineApi {
 
 impl DockerEngineApi {
     pub(crate) fn new(server: DockerEngineServer) -> Self {
+        Self::with_version(server, DOCKER_ENGINE_VERSION)
+    }
+
+    /// Use a Docker Engine api version other than DOCKER_ENGINE_VERSION, such as "v1.43".
+    pub(crate) fn with_version(server: DockerEngineServer, version: &str) -> Self {
         Self {
-            base: format!("{}{}", server.as_string(), DOCKER_ENGINE_VERSION_PATH),
-            display_url: server.base.display_url().clone()
+            base: format!("{}/{}", server.as_string(), version),
+            server: server.as_string(),
+            version: version.to_string(),
+            display_url: server.base.display_url().clone(),
+            published_host: server.published_host()
         }
     }
 
@@ -197,10 +255,14 @@ impl DockerEngineApi {
             .unwrap()
             .to_string();
 
+        self.version = String::new();
+
         self
     }
 
-    pub(crate) fn with_server(server: String) -> Result<Self, DecCreateError> {
+    /// Only for testing url builders
+    #[cfg(test)]
+    fn with_server(server: String) -> Result<Self, DecCreateError> {
         Ok(Self::new(DockerEngineServer::new(server)?))
     }
 
@@ -209,14 +271,35 @@ impl DockerEngineApi {
     fn without_server() -> Self {
         Self {
             base: "".into(),
-            display_url: "".into()
+            server: "".into(),
+            version: "".into(),
+            display_url: "".into(),
+            published_host: "localhost".into()
         }
     }
 
+    /// Host where ports published by containers can be reached.
+    pub fn published_host(&self) -> &str {
+        &self.published_host
+    }
+
     fn at(&self, path: String) -> String {
         format!("{}{}", self.base, path)
     }
 
+    /// Use a newer api version for an api that was added, or changed, in that version, such as "v1.42".
+    /// A newer version configured with DockerEngineClientBuilder::api_version is kept.
+    fn at_least(&self, minimum: &str) -> Self {
+        match (api_version_number(&self.version), api_version_number(minimum)) {
+            (Some(current), Some(required)) if current < required => Self {
+                base: format!("{}/{}", self.server, minimum),
+                version: minimum.to_string(),
+                ..self.clone()
+            },
+            _ => self.clone()
+        }
+    }
+
     fn builder(&self) -> Result<UrlBuilder, url::ParseError> {
         UrlBuilder::from_str(&self.base)
     }
@@ -225,6 +308,10 @@ impl DockerEngineApi {
         DockerEngineApiPathContainers { base: self.clone() }
     }
 
+    pub fn distribution(&self) -> DockerEngineApiPathDistribution {
+        DockerEngineApiPathDistribution { base: self.clone() }
+    }
+
     pub fn exec(&self) -> DockerEngineApiPathExec {
         DockerEngineApiPathExec { base: self.clone() }
     }
@@ -251,14 +338,29 @@ pub(crate) struct DockerEngineApiPathContainers {
 }
 
 impl DockerEngineApiPathContainers {
-    pub fn create(&self, name: Option<&String>) -> String {
-        self.base.at(
-            format!(
-                "/containers/create{}",
-                name
-                    .map(|n| format!("?name={}", n))
-                    .unwrap_or_default()
-            )
+    pub fn commit<ID: Into<String>>(&self, name_or_id: ID, request: &CommitRequest) -> Result<String, url::ParseError> {
+        Ok(self.base.builder()?
+            .join("commit")?
+            .query()
+            .append("container", name_or_id.into())
+            .option("repo", request.repo.clone())
+            .option("tag", request.tag.clone())
+            .option("comment", request.comment.clone())
+            .option("author", request.author.clone())
+            .option("pause", request.pause)
+            .append_all("changes", request.changes.clone())
+            .to_string()
+        )
+    }
+
+    pub fn create(&self, name: Option<&String>, platform: Option<&Platform>) -> Result<String, url::ParseError> {
+        Ok(self.base.builder()?
+            .join("containers")?
+            .join("create")?
+            .query()
+            .option("name", name)
+            .option("platform", platform)
+            .to_string()
         )
     }
 
@@ -317,6 +419,7 @@ impl DockerEngineApiPathContainers {
            .append("stdout", args.stdout)
            .append("stderr", args.stderr)
            .append("timestamps", args.timestamps)
+           .option("since", args.since.map(|at| format!("{}.{:09}", at.unix_timestamp(), at.nanosecond())))
            .to_string()
         )
     }
@@ -376,6 +479,18 @@ impl DockerEngineApiPathContainers {
     }
 }
 
+pub(crate) struct DockerEngineApiPathDistribution {
+    base: DockerEngineApi
+}
+
+impl DockerEngineApiPathDistribution {
+
+    pub fn inspect<R: Into<String>>(&self, image_reference: R) -> String {
+        self.base.at(format!("/distribution/{}/json", image_reference.into()))
+    }
+
+}
+
 pub(crate) struct DockerEngineApiPathExec {
     base: DockerEngineApi
 }
@@ -493,6 +608,10 @@ impl DockerEngineApiPathImages {
         )
     }
 
+    pub fn inspect<ID: Into<String>>(&self, image_id_or_name: ID) -> String {
+        self.base.at(format!("/images/{}/json", image_id_or_name.into()))
+    }
+
     pub fn list(&self) -> String {
         self.base.at("/images/json".into())
     }
@@ -501,10 +620,32 @@ impl DockerEngineApiPathImages {
         self.base.at(format!("/images/{}/push?tag={}", repo.into(), tag.into()))
     }
 
+    pub fn search<T: Into<String>>(&self, term: T, limit: Option<usize>, filters: SearchImagesFilters) -> Result<String, DockerEngineApiBuilderError> {
+        let filters = if filters.is_empty() {
+            None
+        }
+        else {
+            Some(serde_json::to_string(&filters)?)
+        };
+
+        let builder = self.base.builder()?
+            .join("images/search")?
+            .query()
+            .append("term", term.into())
+            .option("limit", limit)
+            .option("filters", filters);
+
+        Ok(builder.to_string())
+    }
+
     pub fn tag<ID: Into<String>, R: Into<String>, T: Into<String>>(&self, image_id_or_name_and_tag: ID, new_repo: R, new_tag: T) -> String {
         self.base.at(format!("/images/{}/tag?repo={}&tag={}", image_id_or_name_and_tag.into(), new_repo.into(), new_tag.into()))
     }
 
+    pub fn remove<ID: Into<String>>(&self, image_id_or_name: ID, force: bool) -> String {
+        self.base.at(format!("/images/{}?force={}", image_id_or_name.into(), force))
+    }
+
     pub fn untag<ID: Into<String>>(&self, image_id_or_name_and_tag: ID) -> String {
         self.base.at(format!("/images/{}", image_id_or_name_and_tag.into()))
     }
@@ -516,6 +657,10 @@ pub(crate) struct DockerEngineApiPathNetworks {
 
 impl DockerEngineApiPathNetworks {
 
+    pub fn connect<ID: Into<String>>(&self, name_or_id: ID) -> String {
+        self.base.at(format!("/networks/{}/connect", name_or_id.into()))
+    }
+
     pub fn create(&self) -> String {
         self.base.at("/networks/create".into())
     }
@@ -533,6 +678,10 @@ impl DockerEngineApiPathNetworks {
         )
     }
 
+    pub fn list(&self) -> String {
+        self.base.at("/networks".into())
+    }
+
     pub fn remove<ID: Into<String>>(&self, name_or_id: ID) -> String {
         self.base.at(format!("/networks/{}", name_or_id.into()))
     }
@@ -545,8 +694,14 @@ pub(crate) struct DockerEngineApiPathVolumes {
 
 impl DockerEngineApiPathVolumes {
 
-    pub fn create(&self) -> String {
-        self.base.at("/volumes/create".into())
+    /// Cluster volumes require api version 1.42.
+    pub fn create(&self, cluster_volume: bool) -> String {
+        if cluster_volume {
+            self.base.at_least(CLUSTER_VOLUME_VERSION).at("/volumes/create".into())
+        }
+        else {
+            self.base.at("/volumes/create".into())
+        }
     }
 
     pub fn inspect<ID: Into<String>>(&self, name_or_id: ID) -> String {
@@ -564,6 +719,20 @@ impl DockerEngineApiPathVolumes {
     pub fn remove<ID: Into<String>>(&self, name_or_id: ID, force: bool) -> String {
         self.base.at(format!("/volumes/{}?force={}", name_or_id.into(), force))
     }
+
+    /// Updating a volume requires api version 1.42.
+    pub fn update<ID: Into<String>>(&self, name_or_id: ID, version: u64) -> String {
+        self.base.at_least(CLUSTER_VOLUME_VERSION).at(format!("/volumes/{}?version={}", name_or_id.into(), version))
+    }
+}
+
+/// Major and minor numbers of an api version such as "v1.41".
+fn api_version_number(version: &str) -> Option<(u32, u32)> {
+    let (major, minor) = version
+        .strip_prefix('v')?
+        .split_once('.')?;
+
+    Some((major.parse().ok()?, minor.parse().ok()?))
 }
 
 /// Indicates URL type. Helps with choosing how to create and configure a hyper http client.
@@ -574,6 +743,9 @@ pub(crate) enum SchemedUrl {
     /// A url starting with https://
     Https(String),
 
+    /// A url starting with ssh://
+    Ssh(SshDestination),
+
     /// A reference starting with unix:// or simply /
     #[cfg(unix)]
     Unix(String)
@@ -581,12 +753,13 @@ pub(crate) enum SchemedUrl {
 
 impl ToString for SchemedUrl {
     fn to_string(&self) -> String {
-        (match self {
-            SchemedUrl::Http(url) => url,
-            SchemedUrl::Https(url) => url,
+        match self {
+            SchemedUrl::Http(url) => url.to_string(),
+            SchemedUrl::Https(url) => url.to_string(),
+            SchemedUrl::Ssh(destination) => destination.to_string(),
             #[cfg(unix)]
-            SchemedUrl::Unix(url) => url
-        }).to_string()
+            SchemedUrl::Unix(url) => url.to_string()
+        }
     }
 }
 
@@ -637,6 +810,15 @@ mod test_docker_engine_api {
             assert_eq!("Docker engine at tcp://a:123".to_string(), actual);
         }
 
+        #[test]
+        pub fn ssh() {
+            let api = api_at("ssh://alice@builder:2222");
+            let actual = format!("{}", api);
+
+            assert_eq!("http://localhost".to_string(), api.base);
+            assert_eq!("Docker engine at ssh://alice@builder:2222".to_string(), actual);
+        }
+
         #[test]
         #[cfg(unix)]
         pub fn unix_explicit() {
@@ -658,6 +840,32 @@ mod test_docker_engine_api {
         }
     }
 
+    mod published_host {
+        use super::super::test_utils::api_at;
+
+        #[test]
+        pub fn http() {
+            assert_eq!("a", api_at("http://a:123").published_host());
+            assert_eq!("::1", api_at("https://[::1]:2376").published_host());
+        }
+
+        #[test]
+        pub fn ssh() {
+            assert_eq!("builder", api_at("ssh://alice@builder:2222").published_host());
+        }
+
+        #[test]
+        pub fn tcp() {
+            assert_eq!("a", api_at("tcp://a:123").published_host());
+        }
+
+        #[test]
+        #[cfg(unix)]
+        pub fn unix() {
+            assert_eq!("localhost", api_at("/some/path.sock").published_host());
+        }
+    }
+
     #[test]
     pub fn version() {
         use super::DockerEngineApi;
@@ -714,9 +922,40 @@ mod test_docker_engine_api_path {
     }
 
     mod containers {
-        use crate::requests::{ListContainersRequest, LogsArgs, RemoveContainerArgs, WaitCondition};
+        use time::OffsetDateTime;
+
+        use crate::model::Platform;
+        use crate::requests::{CommitRequest, ListContainersRequest, LogsArgs, RemoveContainerArgs, WaitCondition};
         use super::super::DockerEngineApi;
 
+        #[test]
+        pub fn commit() {
+            let api = DockerEngineApi::with_server("http://a".into())
+                .unwrap()
+                .without_version();
+            let request = CommitRequest::default()
+                .repo("seeded")
+                .tag("v1")
+                .pause(false)
+                .change("ENV A=1")
+                .change("EXPOSE 80");
+            let actual = api.containers().commit("db", &request)
+                .unwrap();
+
+            assert_eq!("http://a/commit?container=db&repo=seeded&tag=v1&pause=false&changes=ENV+A%3D1&changes=EXPOSE+80", actual);
+        }
+
+        #[test]
+        pub fn commit_container_only() {
+            let api = DockerEngineApi::with_server("http://a".into())
+                .unwrap()
+                .without_version();
+            let actual = api.containers().commit("db", &CommitRequest::default())
+                .unwrap();
+
+            assert_eq!("http://a/commit?container=db", actual);
+        }
+
         #[test]
         pub fn create() {
             let api = DockerEngineApi::without_server();
@@ -727,18 +966,59 @@ mod test_docker_engine_api_path {
 
         #[test]
         pub fn create_named() {
-            let api = DockerEngineApi::without_server();
-            let actual = api.containers().create(Some(&"foo".to_string()));
+            let api = DockerEngineApi::with_server("http://a".into())
+                .unwrap()
+                .without_version();
+            let actual = api.containers().create(Some(&"foo".to_string()), None)
+                .unwrap();
+
+            assert_eq!("http://a/containers/create?name=foo", &actual);
+        }
+
+        #[test]
+        pub fn create_named_for_platform() {
+            let api = DockerEngineApi::with_server("http://a".into())
+                .unwrap()
+                .without_version();
+            let platform = Platform::new("linux", "arm64").variant("v8");
+            let actual = api.containers().create(Some(&"foo".to_string()), Some(&platform))
+                .unwrap();
+
+            assert_eq!("http://a/containers/create?name=foo&platform=linux%2Farm64%2Fv8", &actual);
+        }
 
-            assert_eq!("/containers/create?name=foo", &actual);
+        #[test]
+        pub fn create_no_name_for_platform() {
+            let api = DockerEngineApi::with_server("http://a".into())
+                .unwrap()
+                .without_version();
+            let platform = Platform::new("linux", "amd64");
+            let actual = api.containers().create(None, Some(&platform))
+                .unwrap();
+
+            assert_eq!("http://a/containers/create?platform=linux%2Famd64", &actual);
         }
 
         #[test]
         pub fn create_no_name() {
-            let api = DockerEngineApi::without_server();
-            let actual = api.containers().create(None);
+            let api = DockerEngineApi::with_server("http://a".into())
+                .unwrap()
+                .without_version();
+            let actual = api.containers().create(None, None)
+                .unwrap();
 
-            assert_eq!("/containers/create", &actual);
+            assert_eq!("http://a/containers/create", &actual);
+        }
+
+        #[test]
+        pub fn create_escapes_name() {
+            let api = DockerEngineApi::with_server("http://a".into())
+                .unwrap()
+                .without_version();
+            let actual = api.containers().create(Some(&"foo&platform=windows".to_string()), None)
+                .unwrap();
+
+            assert_eq!("http://a/containers/create?name=foo%26platform%3Dwindows", &actual);
         }
 
         #[test]
@@ -832,6 +1112,21 @@ mod test_docker_engine_api_path {
             assert_eq!("http://a/containers/chatty/logs?stdout=true&stderr=true&timestamps=false", &actual);
         }
 
+        #[test]
+        pub fn logs_since() {
+            let api = DockerEngineApi::with_server("http://a".into())
+                .unwrap()
+                .without_version();
+
+            let since = OffsetDateTime::from_unix_timestamp_nanos(1_700_000_000_000_000_123)
+                .unwrap();
+
+            let actual = api.containers().logs("chatty", LogsArgs::default().timestamps().since(Some(since)))
+                .unwrap();
+
+            assert_eq!("http://a/containers/chatty/logs?stdout=true&stderr=true&timestamps=true&since=1700000000.000000123", &actual);
+        }
+
         #[test]
         #[cfg(not(windows))]  // Docker for Windows does not support pausing containers.
         pub fn pause() {
@@ -921,7 +1216,8 @@ mod test_docker_engine_api_path {
 
     mod images {
         use crate::imp::api::{DOCKER_ENGINE_VERSION_PATH, DockerEngineApi};
-        use crate::requests::BuildImageRequest;
+        use crate::model::Platform;
+        use crate::requests::{BuildImageRequest, CreateImageRequest, SearchImagesFilters};
 
         #[test]
         pub fn build_typical() {
@@ -958,6 +1254,45 @@ mod test_docker_engine_api_path {
             assert!(actual.contains('z'));
         }
 
+        #[test]
+        pub fn build_for_platform() {
+            let api = DockerEngineApi::with_server("http://a".into())
+                .unwrap();
+
+            let request = BuildImageRequest::default()
+                .tag("foo")
+                .platform(Platform::new("linux", "arm").variant("v7"));
+
+            let actual = api.images().build(request)
+                .unwrap();
+
+            assert_eq!(format!("http://a{}/build?t=foo&platform=linux%2Farm%2Fv7", DOCKER_ENGINE_VERSION_PATH), actual);
+        }
+
+        #[test]
+        pub fn create_for_platform() {
+            let api = DockerEngineApi::with_server("http://a".into())
+                .unwrap();
+
+            let request = CreateImageRequest::default()
+                .image("busybox")
+                .tag("latest")
+                .platform(Platform::new("linux", "arm64"));
+
+            let actual = api.images().create(request)
+                .unwrap();
+
+            assert_eq!(format!("http://a{}/images/create?fromImage=busybox&tag=latest&platform=linux%2Farm64", DOCKER_ENGINE_VERSION_PATH), actual);
+        }
+
+        #[test]
+        pub fn inspect() {
+            let api = DockerEngineApi::without_server();
+            let actual = api.images().inspect("foo:bar");
+
+            assert_eq!("/images/foo:bar/json", &actual);
+        }
+
         #[test]
         pub fn json() {
             let api = DockerEngineApi::without_server();
@@ -966,6 +1301,34 @@ mod test_docker_engine_api_path {
             assert_eq!("/images/json", &actual);
         }
 
+        #[test]
+        pub fn search() {
+            let api = DockerEngineApi::with_server("http://a".into())
+                .unwrap()
+                .without_version();
+
+            let actual = api.images().search("nginx", None, SearchImagesFilters::default())
+                .unwrap();
+
+            assert_eq!("http://a/images/search?term=nginx", actual);
+        }
+
+        #[test]
+        pub fn search_filtered() {
+            let api = DockerEngineApi::with_server("http://a".into())
+                .unwrap()
+                .without_version();
+
+            let filters = SearchImagesFilters::default()
+                .is_official(true)
+                .stars(10);
+
+            let actual = api.images().search("nginx", Some(5), filters)
+                .unwrap();
+
+            assert_eq!("http://a/images/search?term=nginx&limit=5&filters=%7B%22is-official%22%3A%5B%22true%22%5D%2C%22stars%22%3A%5B%2210%22%5D%7D", actual);
+        }
+
         #[test]
         pub fn tag() {
             let api = DockerEngineApi::without_server();
@@ -974,6 +1337,14 @@ mod test_docker_engine_api_path {
             assert_eq!("/images/a:b/tag?repo=c.com/def&tag=ghi", &actual);
         }
 
+        #[test]
+        pub fn remove() {
+            let api = DockerEngineApi::without_server();
+            let actual = api.images().remove("sha256:abc", true);
+
+            assert_eq!("/images/sha256:abc?force=true", &actual);
+        }
+
         #[test]
         pub fn untag() {
             let api = DockerEngineApi::without_server();
@@ -983,6 +1354,19 @@ mod test_docker_engine_api_path {
         }
     }
 
+    mod distribution {
+        use crate::imp::api::DockerEngineApi;
+
+        #[test]
+        pub fn inspect() {
+            let api = DockerEngineApi::without_server();
+            let actual = api.distribution().inspect("registry.locallan/foo:1.0");
+
+            assert_eq!("/distribution/registry.locallan/foo:1.0/json", &actual);
+        }
+
+    }
+
     mod exec {
         use crate::imp::api::DockerEngineApi;
 
@@ -1007,6 +1391,14 @@ mod test_docker_engine_api_path {
     mod networks {
         use crate::imp::api::DockerEngineApi;
 
+        #[test]
+        pub fn connect() {
+            let api = DockerEngineApi::without_server();
+            let actual = api.networks().connect("abc");
+
+            assert_eq!("/networks/abc/connect", &actual);
+        }
+
         #[test]
         pub fn create() {
             let api = DockerEngineApi::without_server();
@@ -1031,6 +1423,14 @@ mod test_docker_engine_api_path {
             assert_eq!("/networks/xyz?verbose=true&scope=qwerty", &actual);
         }
 
+        #[test]
+        pub fn list() {
+            let api = DockerEngineApi::without_server();
+            let actual = api.networks().list();
+
+            assert_eq!("/networks", &actual);
+        }
+
         #[test]
         pub fn remove() {
             let api = DockerEngineApi::without_server();
@@ -1047,11 +1447,19 @@ mod test_docker_engine_api_path {
         #[test]
         pub fn create() {
             let api = DockerEngineApi::without_server();
-            let actual = api.volumes().create();
+            let actual = api.volumes().create(false);
 
             assert_eq!("/volumes/create", &actual);
         }
 
+        #[test]
+        pub fn create_cluster_volume() {
+            let api = DockerEngineApi::with_server("http://foo".into()).unwrap();
+            let actual = api.volumes().create(true);
+
+            assert_eq!("http://foo/v1.42/volumes/create", &actual);
+        }
+
         #[test]
         pub fn inspect() {
             let api = DockerEngineApi::without_server();
@@ -1084,6 +1492,22 @@ mod test_docker_engine_api_path {
             assert_eq!("/volumes/foo?force=true", &actual);
         }
 
+
+        #[test]
+        pub fn update() {
+            let api = DockerEngineApi::without_server();
+            let actual = api.volumes().update("foo", 12);
+
+            assert_eq!("/volumes/foo?version=12", &actual);
+        }
+
+        #[test]
+        pub fn update_requires_version_1_42() {
+            let api = DockerEngineApi::with_server("http://foo".into()).unwrap();
+            let actual = api.volumes().update("foo", 12);
+
+            assert_eq!("http://foo/v1.42/volumes/foo?version=12", &actual);
+        }
     }
 }
 
@@ -1106,6 +1530,37 @@ mod test_docker_engine_server {
         assert_eq!(concat!("http://foo", DOCKER_ENGINE_VERSION_PATH), api.base);
     }
 
+    #[test]
+    fn adds_other_version_to_base_url() {
+        let server = DockerEngineServer::new("http://foo")
+            .unwrap();
+
+        let api = DockerEngineApi::with_version(server, "v1.43");
+
+        assert_eq!("http://foo/v1.43", api.base);
+    }
+
+    #[test]
+    fn at_least_raises_older_version() {
+        let server = DockerEngineServer::new("http://foo")
+            .unwrap();
+
+        let api = DockerEngineApi::with_version(server, "v1.41");
+
+        assert_eq!("http://foo/v1.42", api.at_least("v1.42").base);
+    }
+
+    #[test]
+    fn at_least_keeps_newer_version() {
+        let server = DockerEngineServer::new("http://foo")
+            .unwrap();
+
+        let api = DockerEngineApi::with_version(server, "v1.43");
+
+        assert_eq!("http://foo/v1.43", api.at_least("v1.42").base);
+        assert_eq!("http://foo/v1.43", api.at_least("v1.9").base);
+    }
+
 }
 
 #[cfg(test)]
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use crate::errors::DecCreateError;
+
+/// Name of the context that represents DOCKER_HOST, or the platform's default Docker Engine.
+pub(crate) const DEFAULT_CONTEXT: &str = "default";
+
+/// Endpoint of a Docker CLI context, as stored by `docker context create`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct DockerContext {
+    pub name: String,
+    pub host: String,
+    pub skip_tls_verify: bool,
+    pub tls: Option<DockerContextTls>
+}
+
+/// PEM encoded TLS material of a context. Any of the files may be absent.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct DockerContextTls {
+    pub ca: Option<Vec<u8>>,
+    pub cert: Option<Vec<u8>>,
+    pub key: Option<Vec<u8>>
+}
+
+/// The subset of ~/.docker/config.json that is relevant to contexts.
+#[derive(Deserialize)]
+struct DockerConfigFile {
+    #[serde(rename = "currentContext", default)]
+    current_context: Option<String>
+}
+
+/// Content of contexts/meta/{id}/meta.json
+#[derive(Deserialize)]
+struct ContextMetaFile {
+    #[serde(rename = "Name")]
+    name: String,
+
+    #[serde(rename = "Endpoints", default)]
+    endpoints: HashMap<String, ContextEndpointFile>
+}
+
+#[derive(Deserialize)]
+struct ContextEndpointFile {
+    #[serde(rename = "Host", default)]
+    host: Option<String>,
+
+    #[serde(rename = "SkipTLSVerify", default)]
+    skip_tls_verify: bool
+}
+
+/// The Docker CLI configuration directory, usually ~/.docker, and the context store within it.
+pub(crate) struct ContextStore {
+    config_dir: PathBuf
+}
+
+impl ContextStore {
+
+    pub fn new<P: Into<PathBuf>>(config_dir: P) -> Self {
+        Self {
+            config_dir: config_dir.into()
+        }
+    }
+
+    fn read_json<A: DeserializeOwned>(path: &Path) -> Result<Option<A>, DecCreateError> {
+        let store_error = |message: String| DecCreateError::ContextStore {
+            path: path.display().to_string(),
+            message
+        };
+
+        match std::fs::read(path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound =>
+                Ok(None),
+            Err(e) =>
+                Err(store_error(e.to_string())),
+            Ok(content) =>
+                serde_json::from_slice(&content)
+                    .map(Some)
+                    .map_err(|e| store_error(e.to_string()))
+        }
+    }
+
+    fn read_optional(path: PathBuf) -> Result<Option<Vec<u8>>, DecCreateError> {
+        match std::fs::read(&path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound =>
+                Ok(None),
+            Err(e) =>
+                Err(DecCreateError::ContextStore {
+                    path: path.display().to_string(),
+                    message: e.to_string()
+                }),
+            Ok(content) =>
+                Ok(Some(content))
+        }
+    }
+
+    /// Name of the context selected by `docker context use`, if any.
+    pub fn current_context_name(&self) -> Result<Option<String>, DecCreateError> {
+        let config: Option<DockerConfigFile> = Self::read_json(&self.config_dir.join("config.json"))?;
+
+        Ok(config
+            .and_then(|c| c.current_context)
+            .filter(|name| !name.is_empty())
+        )
+    }
+
+    /// Load a context by name.
+    ///
+    /// The Docker CLI names context directories after a hash of the context name; rather
+    /// than computing the hash, search the meta.json files for a matching name.
+    pub fn load(&self, name: &str) -> Result<DockerContext, DecCreateError> {
+        let meta_dir = self.config_dir.join("contexts").join("meta");
+
+        let entries = match std::fs::read_dir(&meta_dir) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound =>
+                return Err(DecCreateError::ContextNotFound(name.to_string())),
+            Err(e) =>
+                return Err(DecCreateError::ContextStore {
+                    path: meta_dir.display().to_string(),
+                    message: e.to_string()
+                }),
+            Ok(entries) =>
+                entries
+        };
+
+        for entry in entries.flatten() {
+            let meta: Option<ContextMetaFile> = Self::read_json(&entry.path().join("meta.json"))?;
+
+            if let Some(mut meta) = meta.filter(|m| m.name == name) {
+                let endpoint = meta.endpoints.remove("docker")
+                    .ok_or_else(|| DecCreateError::ContextWithoutEndpoint(name.to_string()))?;
+
+                let host = endpoint.host
+                    .filter(|h| !h.is_empty())
+                    .ok_or_else(|| DecCreateError::ContextWithoutEndpoint(name.to_string()))?;
+
+                let tls_dir = self.config_dir
+                    .join("contexts")
+                    .join("tls")
+                    .join(entry.file_name())
+                    .join("docker");
+
+                let tls = DockerContextTls {
+                    ca: Self::read_optional(tls_dir.join("ca.pem"))?,
+                    cert: Self::read_optional(tls_dir.join("cert.pem"))?,
+                    key: Self::read_optional(tls_dir.join("key.pem"))?
+                };
+
+                return Ok(DockerContext {
+                    name: meta.name,
+                    host,
+                    skip_tls_verify: endpoint.skip_tls_verify,
+                    tls: Some(tls).filter(|t| t != &DockerContextTls::default())
+                });
+            }
+        }
+
+        Err(DecCreateError::ContextNotFound(name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test_context_store {
+    use std::path::Path;
+
+    use super::ContextStore;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap())
+            .unwrap();
+
+        std::fs::write(path, content)
+            .unwrap();
+    }
+
+    mod current_context_name {
+        use super::super::ContextStore;
+        use super::write;
+
+        #[test]
+        fn none_without_config_file() {
+            let dir = tempfile::tempdir()
+                .unwrap();
+
+            let actual = ContextStore::new(dir.path()).current_context_name()
+                .unwrap();
+
+            assert_eq!(None, actual);
+        }
+
+        #[test]
+        fn none_when_not_set() {
+            let dir = tempfile::tempdir()
+                .unwrap();
+
+            write(&dir.path().join("config.json"), r#"{"auths": {}}"#);
+
+            let actual = ContextStore::new(dir.path()).current_context_name()
+                .unwrap();
+
+            assert_eq!(None, actual);
+        }
+
+        #[test]
+        fn from_config_file() {
+            let dir = tempfile::tempdir()
+                .unwrap();
+
+            write(&dir.path().join("config.json"), r#"{"auths": {}, "currentContext": "colima"}"#);
+
+            let actual = ContextStore::new(dir.path()).current_context_name()
+                .unwrap();
+
+            assert_eq!(Some("colima".to_string()), actual);
+        }
+    }
+
+    #[test]
+    fn loads_unix_socket_context() {
+        let dir = tempfile::tempdir()
+            .unwrap();
+
+        write(
+            &dir.path().join("contexts/meta/abc123/meta.json"),
+            r#"{"Name":"colima","Metadata":{"Description":"colima"},"Endpoints":{"docker":{"Host":"unix:///home/me/.colima/default/docker.sock","SkipTLSVerify":false}}}"#
+        );
+
+        let actual = ContextStore::new(dir.path()).load("colima")
+            .unwrap();
+
+        assert_eq!("colima", actual.name);
+        assert_eq!("unix:///home/me/.colima/default/docker.sock", actual.host);
+        assert!(!actual.skip_tls_verify);
+        assert_eq!(None, actual.tls);
+    }
+
+    #[test]
+    fn loads_tls_context() {
+        let dir = tempfile::tempdir()
+            .unwrap();
+
+        write(
+            &dir.path().join("contexts/meta/def456/meta.json"),
+            r#"{"Name":"remote","Metadata":{},"Endpoints":{"docker":{"Host":"tcp://builder:2376","SkipTLSVerify":true}}}"#
+        );
+        write(&dir.path().join("contexts/tls/def456/docker/ca.pem"), "CA");
+        write(&dir.path().join("contexts/tls/def456/docker/cert.pem"), "CERT");
+        write(&dir.path().join("contexts/tls/def456/docker/key.pem"), "KEY");
+
+        let actual = ContextStore::new(dir.path()).load("remote")
+            .unwrap();
+
+        assert_eq!("tcp://builder:2376", actual.host);
+        assert!(actual.skip_tls_verify);
+
+        let tls = actual.tls
+            .unwrap();
+
+        assert_eq!(Some(b"CA".to_vec()), tls.ca);
+        assert_eq!(Some(b"CERT".to_vec()), tls.cert);
+        assert_eq!(Some(b"KEY".to_vec()), tls.key);
+    }
+
+    #[test]
+    fn fails_when_not_found() {
+        let dir = tempfile::tempdir()
+            .unwrap();
+
+        write(
+            &dir.path().join("contexts/meta/abc123/meta.json"),
+            r#"{"Name":"colima","Endpoints":{"docker":{"Host":"unix:///tmp/docker.sock"}}}"#
+        );
+
+        let actual = ContextStore::new(dir.path()).load("rootless")
+            .unwrap_err();
+
+        assert_eq!("Docker context rootless not found", actual.to_string());
+    }
+
+    #[test]
+    fn fails_without_store() {
+        let dir = tempfile::tempdir()
+            .unwrap();
+
+        ContextStore::new(dir.path()).load("colima")
+            .unwrap_err();
+    }
+}
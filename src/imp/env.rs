@@ -1,4 +1,6 @@
 use std::env::VarError;
+use std::path::PathBuf;
+
 use log::warn;
 
 /// A default configuration is only available on Linux and Mac systems.
@@ -14,15 +16,84 @@ pub(crate) fn default_server() -> String {
     return "tcp://localhost:2375".to_string();
 }
 
+/// Directory of the Docker CLI configuration, including its context store.
+///
+/// Either $DOCKER_CONFIG, or .docker in the user's home directory.
+pub(crate) fn docker_config_dir() -> Option<PathBuf> {
+    match var("DOCKER_CONFIG") {
+        Some(value) => Some(PathBuf::from(value)),
+        None => {
+            #[cfg(windows)]
+            let home = var("USERPROFILE");
+
+            #[cfg(not(windows))]
+            let home = var("HOME");
+
+            home.map(|h| PathBuf::from(h).join(".docker"))
+        }
+    }
+}
+
+/// Directory containing ca.pem, cert.pem, and key.pem
+pub(crate) fn docker_cert_path() -> Option<PathBuf> {
+    var("DOCKER_CERT_PATH")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+pub(crate) fn docker_context() -> Option<String> {
+    var("DOCKER_CONTEXT")
+}
+
 pub(crate) fn docker_host() -> Option<String> {
-    match std::env::var("DOCKER_HOST") {
+    var("DOCKER_HOST")
+}
+
+/// Any non-empty value enables TLS, even "0" or "false".
+pub(crate) fn docker_tls() -> bool {
+    var("DOCKER_TLS")
+        .map(|v| !v.is_empty())
+        .unwrap_or_default()
+}
+
+/// Like the docker cli, any non-empty value enables verification, even "0" or "false".
+pub(crate) fn docker_tls_verify() -> bool {
+    var("DOCKER_TLS_VERIFY")
+        .map(|v| !v.is_empty())
+        .unwrap_or_default()
+}
+
+/// Proxy for http:// and tcp:// urls, from HTTP_PROXY or http_proxy.
+pub(crate) fn http_proxy() -> Option<String> {
+    var_any_case("HTTP_PROXY")
+}
+
+/// Proxy for https:// urls, from HTTPS_PROXY or https_proxy.
+pub(crate) fn https_proxy() -> Option<String> {
+    var_any_case("HTTPS_PROXY")
+}
+
+/// Comma separated hosts and domains that are not reached through a proxy.
+pub(crate) fn no_proxy() -> Option<String> {
+    var_any_case("NO_PROXY")
+}
+
+/// The upper case variable, or if not set, the lower case variable. Empty values count as not set.
+fn var_any_case(upper_case_name: &str) -> Option<String> {
+    var(upper_case_name)
+        .or_else(|| var(&upper_case_name.to_lowercase()))
+        .filter(|v| !v.is_empty())
+}
+
+fn var(name: &str) -> Option<String> {
+    match std::env::var(name) {
         Err(e) => {
             match e {
                 VarError::NotPresent => {
                     None
                 }
                 _ => {
-                    warn!("Unable to read DOCKER_HOST environment variable: {}", e);
+                    warn!("Unable to read {} environment variable: {}", name, e);
                     None
                 }
             }
@@ -1,9 +1,13 @@
 use std::collections::HashMap;
+use std::error::Error;
 use std::string::FromUtf8Error;
-use http_body_util::Full;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use hyper::http::HeaderMap;
 use hyper::http::header::CONTENT_TYPE;
 
-use hyper::{Request, StatusCode};
+use hyper::{Method, Request, StatusCode};
 use hyper::body::Bytes;
 use log::debug;
 use serde::de::DeserializeOwned;
@@ -12,48 +16,104 @@ use serde_json::Deserializer;
 
 use crate::errors::{DecLibraryError, DecUseError};
 use crate::imp::content_type;
-use crate::imp::hyper_proxy::HyperHttpClient;
-use crate::imp::hyper_shims::incoming_bytes;
+use crate::client::{DockerEngineTransport, TransportError};
 use crate::imp::other::{base64_encode, converge};
-use crate::model::{RegistryAuth, RegistryConfig};
+use crate::model::{Cancellation, RegistryAuth, RegistryConfig, RetryPolicy};
 use crate::responses::ErrorResponse;
 
+/// Settings applied to every request of a client.
+#[derive(Clone, Debug)]
+pub(crate) struct DockerEngineHttpSettings {
+    /// Only used to report a connect timeout; the timeout itself is enforced by the connector.
+    pub connect_timeout: Option<Duration>,
+
+    /// Time limit for a request and its response, including reading the response body.
+    pub request_timeout: Option<Duration>,
+
+    /// Time limit for requests that stream until the Docker Engine finishes a long running
+    /// task, such as pulling an image or waiting for a container.
+    pub streaming_timeout: Option<Duration>,
+
+    /// Headers added to each request, unless the request sets them itself.
+    pub default_headers: HeaderMap,
+
+    /// Retries of idempotent requests.
+    pub retry_policy: RetryPolicy
+}
+
 /// A proxy class that provides a basic REST-based DSL for interacting
 /// with a Docker Engine HTTP API endpoint. Most payloads are JSON.
 #[derive(Clone, Debug)]
 pub(crate) struct DockerEngineHttpClient {
-    client: HyperHttpClient
+    client: Arc<dyn DockerEngineTransport>,
+    settings: Arc<DockerEngineHttpSettings>
 }
 
 impl DockerEngineHttpClient {
 
-    pub fn new(client: HyperHttpClient) -> Self {
+    pub fn new(client: Arc<dyn DockerEngineTransport>, settings: DockerEngineHttpSettings) -> Self {
+        Self {
+            client,
+            settings: Arc::new(settings)
+        }
+    }
+
+    pub fn with_retry_policy(mut self, v: RetryPolicy) -> Self {
+        Arc::make_mut(&mut self.settings).retry_policy = v;
+        self
+    }
+
+    /// Same settings, without sharing open connections. See DockerEngineTransport::detached.
+    pub fn detached(&self) -> Self {
         Self {
-            client
+            client: self.client.detached().unwrap_or_else(|| self.client.clone()),
+            settings: self.settings.clone()
         }
     }
 
-    fn build_delete(uri: &str) -> Result<Request<Full<Bytes>>, DecLibraryError> {
+    fn build_delete(uri: &str) -> Result<Request<Bytes>, DecLibraryError> {
         Request::delete(uri)
-            .body(Full::new(Bytes::new()))
+            .body(Bytes::new())
             .map_err(DecLibraryError::HttpRequestBuilderError)
     }
 
-    fn build_get(uri: &str) -> Result<Request<Full<Bytes>>, DecLibraryError> {
+    fn build_get(uri: &str) -> Result<Request<Bytes>, DecLibraryError> {
         Request::get(uri.to_string())
-            .body(Full::new(Bytes::new()))
+            .body(Bytes::new())
+            .map_err(DecLibraryError::HttpRequestBuilderError)
+    }
+
+    fn build_get_with_auth(uri: &str, registry_auth: &Option<RegistryAuth>) -> Result<Request<Bytes>, DecLibraryError> {
+        let mut builder = Request::get(uri.to_string());
+
+        if let Some(value) = Self::x_registry_auth(registry_auth)? {
+            builder = builder.header("X-Registry-Auth", value);
+        }
+
+        builder
+            .body(Bytes::new())
             .map_err(DecLibraryError::HttpRequestBuilderError)
     }
 
     #[cfg(not(windows))]
-    fn build_put(uri: &str, content_type: &str, content: Vec<u8>) -> Result<Request<Full<Bytes>>, DecLibraryError> {
+    fn build_put(uri: &str, content_type: &str, content: Vec<u8>) -> Result<Request<Bytes>, DecLibraryError> {
         Request::put(uri)
             .header(CONTENT_TYPE, content_type)
-            .body(Full::new(content.into()))
+            .body(content.into())
+            .map_err(DecLibraryError::HttpRequestBuilderError)
+    }
+
+    fn build_put_json<B: Serialize>(uri: &str, body: &B) -> Result<Request<Bytes>, DecLibraryError> {
+        let json = serde_json::to_string(body)
+            .map_err(DecLibraryError::RequestSerializationError)?;
+
+        Request::put(uri)
+            .header(CONTENT_TYPE, content_type::JSON)
+            .body(Bytes::from(json))
             .map_err(DecLibraryError::HttpRequestBuilderError)
     }
 
-    fn build_post_json<B: Serialize>(uri: &str, body: &B) -> Result<Request<Full<Bytes>>, DecLibraryError> {
+    fn build_post_json<B: Serialize>(uri: &str, body: &B) -> Result<Request<Bytes>, DecLibraryError> {
         let json = serde_json::to_string(body)
             .map_err(DecLibraryError::RequestSerializationError)?;
 
@@ -61,11 +121,11 @@ impl DockerEngineHttpClient {
 
         Request::post(uri.to_string())
             .header(CONTENT_TYPE, content_type::JSON)
-            .body(Full::new(body))
+            .body(body)
             .map_err(DecLibraryError::HttpRequestBuilderError)
     }
 
-    fn build_post_with_auth(uri: &str, registry_auth: &Option<RegistryAuth>) -> Result<Request<Full<Bytes>>, DecLibraryError> {
+    fn build_post_with_auth(uri: &str, registry_auth: &Option<RegistryAuth>) -> Result<Request<Bytes>, DecLibraryError> {
         let mut builder = Request::post(uri.to_string());
 
         if let Some(value) = Self::x_registry_auth(registry_auth)? {
@@ -73,7 +133,7 @@ impl DockerEngineHttpClient {
         }
 
         builder
-            .body(Full::new(Bytes::new()))
+            .body(Bytes::new())
             .map_err(DecLibraryError::HttpRequestBuilderError)
     }
 
@@ -81,7 +141,7 @@ impl DockerEngineHttpClient {
         uri: &str,
         registry_config: &HashMap<String, RegistryConfig>,
         content_type: &str,
-        body: Vec<u8>) -> Result<Request<Full<Bytes>>, DecLibraryError>
+        body: Vec<u8>) -> Result<Request<Bytes>, DecLibraryError>
     {
         let mut builder = Request::post(uri.to_string())
             .header(CONTENT_TYPE, content_type);
@@ -91,20 +151,29 @@ impl DockerEngineHttpClient {
         }
 
         builder
-            .body(Full::new(body.into()))
+            .body(body.into())
             .map_err(DecLibraryError::HttpRequestBuilderError)
     }
 
     fn build_request<U, F>(&self, uri: U, request_from_uri: F) -> Result<DockerEngineHttpRequest, DecLibraryError>
     where
         U: ToString,
-        F: FnOnce(&str) -> Result<Request<Full<Bytes>>, DecLibraryError>
+        F: FnOnce(&str) -> Result<Request<Bytes>, DecLibraryError>
     {
         let u = uri.to_string();
 
+        let mut request = request_from_uri(&u)?;
+
+        for (name, value) in &self.settings.default_headers {
+            if !request.headers().contains_key(name) {
+                request.headers_mut().insert(name, value.clone());
+            }
+        }
+
         Ok(DockerEngineHttpRequest {
             client: self.client.clone(),
-            request: request_from_uri(&u)?,
+            request,
+            settings: self.settings.clone(),
             uri: u
         })
     }
@@ -117,6 +186,10 @@ impl DockerEngineHttpClient {
         self.build_request(uri, Self::build_get)
     }
 
+    pub fn get_with_auth<U: ToString>(&self, uri: U, registry_auth: &Option<RegistryAuth>) -> Result<DockerEngineHttpRequest, DecLibraryError> {
+        self.build_request(uri, |u| Self::build_get_with_auth(u, registry_auth))
+    }
+
     pub fn post<U: ToString>(&self, uri: U) -> Result<DockerEngineHttpRequest, DecLibraryError> {
         self.build_request(uri, |u| Self::build_post_with_auth(u, &None))
     }
@@ -150,6 +223,10 @@ impl DockerEngineHttpClient {
         self.build_request(uri, |u| Self::build_put(u, content_type, content))
     }
 
+    pub fn put_json<U: ToString, B: Serialize>(&self, uri: U, body: &B) -> Result<DockerEngineHttpRequest, DecLibraryError> {
+        self.build_request(uri, |u| Self::build_put_json(u, body))
+    }
+
     fn x_registry_auth(registry_auth: &Option<RegistryAuth>) -> Result<Option<String>, DecLibraryError> {
         match registry_auth {
             None => Ok(None),
@@ -177,22 +254,127 @@ impl DockerEngineHttpClient {
 
 #[derive(Debug)]
 pub(crate) struct DockerEngineHttpRequest {
-    client: HyperHttpClient,
-    request: Request<Full<Bytes>>,
+    client: Arc<dyn DockerEngineTransport>,
+    request: Request<Bytes>,
+    settings: Arc<DockerEngineHttpSettings>,
     uri: String
 }
 
 impl DockerEngineHttpRequest {
 
+    /// Execute a request that the Docker Engine answers promptly, within the request timeout.
     pub async fn execute(self) -> Result<DockerEngineHttpResponse, DecUseError> {
+        let timeout = self.settings.request_timeout;
+
+        self.execute_within(timeout, &Cancellation::default()).await
+    }
+
+    /// Execute a request whose response is streamed until a long running task
+    /// finishes, within the streaming timeout, unless cancelled first.
+    pub async fn execute_streaming(self, cancellation: &Cancellation) -> Result<DockerEngineHttpResponse, DecUseError> {
+        let timeout = self.settings.streaming_timeout;
+
+        self.execute_within(timeout, cancellation).await
+    }
+
+    /// Stops at the earlier of the timeout and the cancellation deadline, or when the
+    /// cancellation token is cancelled. Stopping drops the request, which closes the
+    /// connection instead of returning it to the pool.
+    async fn execute_within(self, timeout: Option<Duration>, cancellation: &Cancellation) -> Result<DockerEngineHttpResponse, DecUseError> {
+        let started = Instant::now();
+        let uri = self.uri.clone();
+
+        let deadline = match (timeout.map(|t| started + t), cancellation.deadline) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b)
+        };
+
+        let expired = async {
+            match deadline {
+                None => std::future::pending().await,
+                Some(d) => tokio::time::sleep_until(d.into()).await
+            }
+        };
+
+        let cancelled = async {
+            match &cancellation.token {
+                None => std::future::pending().await,
+                Some(token) => token.cancelled().await
+            }
+        };
+
+        tokio::select! {
+            result = self.execute_unbounded() =>
+                result,
+            _ = expired =>
+                Err(DecUseError::Timeout {
+                    uri,
+                    timeout: deadline
+                        .unwrap_or(started)
+                        .saturating_duration_since(started)
+                }),
+            _ = cancelled =>
+                Err(DecUseError::Cancelled { uri })
+        }
+    }
+
+    /// Send the request, and for idempotent requests, retry according to the retry policy.
+    async fn execute_unbounded(self) -> Result<DockerEngineHttpResponse, DecUseError> {
+        let policy = &self.settings.retry_policy;
+        let idempotent = self.request.method() == Method::GET || self.request.method() == Method::HEAD;
+
+        if !idempotent || policy.max_retries == 0 {
+            return self.send(Self::copy_request(&self.request)).await;
+        }
+
+        let mut retry = 0;
+
+        loop {
+            let result = self.send(Self::copy_request(&self.request)).await;
+
+            let retryable = match &result {
+                Ok(response) => response.status.is_server_error(),
+                Err(DecUseError::HttpClientError(_)) | Err(DecUseError::HttpClientError2(_)) | Err(DecUseError::Transport(_)) => true,
+                Err(_) => false
+            };
+
+            if !retryable || retry >= policy.max_retries {
+                return result;
+            }
+
+            let delay = policy.delay(retry);
+
+            debug!("Retrying {} {} in {:?} after attempt {}", self.request.method(), self.uri, delay, retry + 1);
+
+            tokio::time::sleep(delay).await;
+
+            retry += 1;
+        }
+    }
+
+    fn copy_request(request: &Request<Bytes>) -> Request<Bytes> {
+        let mut result = Request::new(request.body().clone());
+
+        *result.method_mut() = request.method().clone();
+        *result.uri_mut() = request.uri().clone();
+        *result.version_mut() = request.version();
+        *result.headers_mut() = request.headers().clone();
+
+        result
+    }
+
+    async fn send(&self, request: Request<Bytes>) -> Result<DockerEngineHttpResponse, DecUseError> {
+        let request_method = request.method().clone();
+
         let response = self.client
-            .apply(self.request)
+            .send(request)
             .await
-            .map_err(DecUseError::HttpClientError2)?;
+            .map_err(|e| self.transport_error(e))?;
 
         Ok(
             DockerEngineHttpResponse {
-                request_uri: self.uri,
+                request_method,
+                request_uri: self.uri.clone(),
                 status: response.status(),
                 content_type: match response.headers().get("Content-Type") {
                     None => None,
@@ -201,18 +383,53 @@ impl DockerEngineHttpRequest {
                         Err(_) => None
                     }
                 },
-                body: incoming_bytes(response)
-                    .await
-                    .map_err(DecUseError::HttpClientError)?
-                    .into()
+                body: response.into_body()
             }
         )
     }
 
+    /// Recover the errors of the Hyper transport, to report them as before transports were pluggable.
+    fn transport_error(&self, error: TransportError) -> DecUseError {
+        let error = match error.downcast::<hyper_util::client::legacy::Error>() {
+            Ok(e) => return match self.settings.connect_timeout {
+                Some(timeout) if e.is_connect() && Self::is_timeout(&e) =>
+                    DecUseError::Timeout {
+                        uri: self.uri.clone(),
+                        timeout
+                    },
+                _ =>
+                    DecUseError::HttpClientError2(*e)
+            },
+            Err(other) => other
+        };
+
+        match error.downcast::<hyper::Error>() {
+            Ok(e) => DecUseError::HttpClientError(*e),
+            Err(other) => DecUseError::Transport(other)
+        }
+    }
+
+    fn is_timeout(error: &hyper_util::client::legacy::Error) -> bool {
+        let mut source = error.source();
+
+        while let Some(e) = source {
+            if let Some(io_error) = e.downcast_ref::<std::io::Error>() {
+                if io_error.kind() == std::io::ErrorKind::TimedOut {
+                    return true;
+                }
+            }
+
+            source = e.source();
+        }
+
+        false
+    }
+
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct DockerEngineHttpResponse {
+    pub(crate) request_method: Method,
     pub(crate) request_uri: String,
     pub(crate) status: StatusCode,
     pub(crate) content_type: Option<String>,
@@ -401,16 +618,22 @@ impl DockerEngineHttpResponse {
     }
 
     fn unexpected_status(self) -> DecUseError {
+        // A 304 Not Modified response has no body.
+        if self.status == StatusCode::NOT_MODIFIED {
+            return DecUseError::NotModified {
+                method: self.request_method,
+                uri: self.request_uri
+            };
+        }
+
         let status = self.status;
+        let method = self.request_method.clone();
+        let uri = self.request_uri.clone();
         let parse_result: Result<ErrorResponse, DecUseError> = self.parse();
 
         match parse_result {
             Err(e) => e,
-            Ok(parsed) =>
-                DecUseError::Rejected {
-                    status,
-                    message: parsed.message
-                }
+            Ok(parsed) => DecUseError::from_status(method, uri, status, parsed.message)
         }
     }
 
@@ -426,12 +649,13 @@ pub struct DockerEngineResponseNotUtf8 {
 #[cfg(test)]
 mod test_der {
     use hyper::body::Bytes;
-    use hyper::StatusCode;
+    use hyper::{Method, StatusCode};
 
     use super::DockerEngineHttpResponse;
 
     fn arbitrary() -> DockerEngineHttpResponse {
         DockerEngineHttpResponse {
+            request_method: Method::GET,
             request_uri: "foo".into(),
             status: StatusCode::from_u16(123).unwrap(),
             content_type: Some("arbitrary".into()),
@@ -441,7 +665,7 @@ mod test_der {
 
     mod assert_item_status {
         use hyper::body::Bytes;
-        use hyper::StatusCode;
+        use hyper::{Method, StatusCode};
         use crate::errors::DecUseError;
         use crate::imp::content_type::JSON;
         use crate::imp::http_proxy::DockerEngineHttpResponse;
@@ -478,7 +702,7 @@ mod test_der {
             let actual = response.assert_item_status(StatusCode::ACCEPTED)
                 .unwrap_err();
 
-            if let DecUseError::Rejected { status, message } = actual {
+            if let DecUseError::Rejected { status, message, .. } = actual {
                 assert_eq!(StatusCode::CREATED, status);
                 assert_eq!("boom", message);
             }
@@ -487,6 +711,43 @@ mod test_der {
             }
         }
 
+        #[test]
+        fn fails_with_conflict() {
+            let response = DockerEngineHttpResponse {
+                content_type: Some(JSON.into()),
+                request_method: Method::POST,
+                request_uri: "/containers/create?name=web".into(),
+                status: StatusCode::CONFLICT,
+                body: Bytes::from(&b"{ \"message\": \"name is already in use\" }"[..]),
+            };
+
+            let actual = response.assert_item_status(StatusCode::CREATED)
+                .unwrap_err();
+
+            if let DecUseError::Conflict { method, uri, message } = actual {
+                assert_eq!(Method::POST, method);
+                assert_eq!("/containers/create?name=web", uri);
+                assert_eq!("name is already in use", message);
+            }
+            else {
+                panic!("Unexpected error: {}", actual);
+            }
+        }
+
+        #[test]
+        fn fails_with_not_modified_without_body() {
+            let response = DockerEngineHttpResponse {
+                status: StatusCode::NOT_MODIFIED,
+                body: Bytes::new(),
+                ..super::arbitrary()
+            };
+
+            let actual = response.assert_item_status(StatusCode::NO_CONTENT)
+                .unwrap_err();
+
+            assert!(matches!(actual, DecUseError::NotModified { .. }), "Unexpected error: {}", actual);
+        }
+
         #[test]
         fn passes_when_equal() {
             let response = DockerEngineHttpResponse {
@@ -518,7 +779,7 @@ mod test_der {
             let actual = response.assert_item_status_in(&[StatusCode::OK, StatusCode::ACCEPTED])
                 .unwrap_err();
 
-            if let DecUseError::Rejected { status, message } = actual {
+            if let DecUseError::Rejected { status, message, .. } = actual {
                 assert_eq!(StatusCode::CREATED, status);
                 assert_eq!("boom", message);
             }
@@ -540,13 +801,14 @@ mod test_der {
     }
 
     mod assert_list_status {
-        use hyper::StatusCode;
+        use hyper::{Method, StatusCode};
         use crate::errors::DecUseError;
         use crate::imp::http_proxy::DockerEngineHttpResponse;
 
         #[test]
         fn fails_when_api_not_found() {
             let response = DockerEngineHttpResponse {
+                request_method: Method::GET,
                 request_uri: "some-uri".into(),
                 content_type: None,
                 status: StatusCode::NOT_FOUND,
@@ -702,13 +964,14 @@ mod test_der {
     }
 
     mod parse_other_item_response {
-        use hyper::StatusCode;
+        use hyper::{Method, StatusCode};
         use crate::errors::DecUseError;
         use crate::imp::http_proxy::DockerEngineHttpResponse;
 
         #[test]
         fn maps_status_to_error_when_api_not_implemented() {
             let response = DockerEngineHttpResponse {
+                request_method: Method::GET,
                 request_uri: "bar".into(),
                 status: StatusCode::NOT_IMPLEMENTED,
                 content_type: Some("foo".into()),
@@ -727,13 +990,14 @@ mod test_der {
     }
 
     mod parse_other_list_response {
-        use hyper::StatusCode;
+        use hyper::{Method, StatusCode};
         use crate::errors::DecUseError;
         use super::super::DockerEngineHttpResponse;
 
         #[test]
         fn maps_status_to_error_when_api_not_found() {
             let response = DockerEngineHttpResponse {
+                request_method: Method::GET,
                 request_uri: "bar".into(),
                 status: StatusCode::NOT_FOUND,
                 content_type: Some("foo".into()),
@@ -753,6 +1017,7 @@ mod test_der {
         #[test]
         fn maps_status_to_error_when_api_not_implemented() {
             let response = DockerEngineHttpResponse {
+                request_method: Method::GET,
                 request_uri: "bar".into(),
                 status: StatusCode::NOT_IMPLEMENTED,
                 content_type: Some("foo".into()),
@@ -770,4 +1035,148 @@ mod test_der {
         }
     }
 
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test_docker_engine_http_request {
+
+    mod execute_with_retry_policy {
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        use hyper::StatusCode;
+
+        use crate::DockerEngineClient;
+        use crate::imp::http_proxy::{DockerEngineHttpClient, DockerEngineHttpSettings};
+        use crate::imp::hyper_proxy::{HyperHttpClient, HyperHttpClientSettings};
+        use crate::model::RetryPolicy;
+
+        fn retrying_client() -> DockerEngineHttpClient {
+            let settings = DockerEngineHttpSettings {
+                connect_timeout: None,
+                request_timeout: None,
+                streaming_timeout: None,
+                default_headers: Default::default(),
+                retry_policy: RetryPolicy::default()
+                    .max_retries(2)
+                    .initial_delay(Duration::from_millis(1))
+                    .jitter(false)
+            };
+
+            DockerEngineHttpClient::new(Arc::new(HyperHttpClient::http(&HyperHttpClientSettings::default())), settings)
+        }
+
+        #[tokio::test]
+        async fn retries_get_on_server_error() {
+            let mut server = mockito::Server::new_async().await;
+            let http = retrying_client();
+
+            let mock = server.mock("GET", "/foo")
+                .with_status(StatusCode::SERVICE_UNAVAILABLE.as_u16() as usize)
+                .expect(3)
+                .create_async()
+                .await;
+
+            let response = http.get(format!("{}/foo", server.url()))
+                .unwrap()
+                .execute()
+                .await
+                .unwrap();
+
+            assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status);
+
+            mock.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn does_not_retry_get_on_client_error() {
+            let mut server = mockito::Server::new_async().await;
+            let http = retrying_client();
+
+            let mock = server.mock("GET", "/foo")
+                .with_status(StatusCode::NOT_FOUND.as_u16() as usize)
+                .expect(1)
+                .create_async()
+                .await;
+
+            http.get(format!("{}/foo", server.url()))
+                .unwrap()
+                .execute()
+                .await
+                .unwrap();
+
+            mock.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn does_not_retry_post() {
+            let mut server = mockito::Server::new_async().await;
+            let http = retrying_client();
+
+            let mock = server.mock("POST", "/foo")
+                .with_status(StatusCode::INTERNAL_SERVER_ERROR.as_u16() as usize)
+                .expect(1)
+                .create_async()
+                .await;
+
+            http.post(format!("{}/foo", server.url()))
+                .unwrap()
+                .execute()
+                .await
+                .unwrap();
+
+            mock.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn overridden_per_client_copy() {
+            let mut server = mockito::Server::new_async().await;
+            let http = retrying_client()
+                .with_retry_policy(RetryPolicy::none());
+
+            let mock = server.mock("GET", "/foo")
+                .with_status(StatusCode::SERVICE_UNAVAILABLE.as_u16() as usize)
+                .expect(1)
+                .create_async()
+                .await;
+
+            http.get(format!("{}/foo", server.url()))
+                .unwrap()
+                .execute()
+                .await
+                .unwrap();
+
+            mock.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn retries_connection_error() {
+            // Reserve a port, then close it so that connections are refused.
+            let listener = std::net::TcpListener::bind("127.0.0.1:0")
+                .unwrap();
+            let address = listener.local_addr()
+                .unwrap();
+            drop(listener);
+
+            let dec = DockerEngineClient::builder()
+                .server(format!("http://{}", address))
+                .retry_policy(
+                    RetryPolicy::default()
+                        .max_retries(2)
+                        .initial_delay(Duration::from_millis(50))
+                        .jitter(false)
+                )
+                .build()
+                .unwrap();
+
+            let started = std::time::Instant::now();
+
+            dec.version()
+                .await
+                .unwrap_err();
+
+            // Two retries, after 50ms and 100ms
+            assert!(started.elapsed() >= Duration::from_millis(150));
+        }
+    }
+}
@@ -1,37 +1,105 @@
-use http_body_util::Full;
+use std::sync::Arc;
+use std::time::Duration;
+
+use http_body_util::{BodyExt, Full};
 use hyper::{Request, Response};
 use hyper::body::{Bytes, Incoming};
+#[cfg(feature = "native-tls")]
 use hyper_tls::HttpsConnector;
-use hyper_tls::native_tls::TlsConnector;
 use hyper_util::client::legacy::Client;
-use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::client::legacy::connect::{Connect, HttpConnector};
+use hyper_util::rt::TokioTimer;
 #[cfg(unix)]
 use hyperlocal::UnixConnector;
 
+use crate::client::{DockerEngineTransport, TransportFuture};
+use crate::imp::forward_proxy::{ProxyConnector, ProxySettings};
 use crate::imp::hyper_shims::default_executor;
+use crate::imp::ssh::{SshConnector, SshDestination};
+use crate::model::TlsConfig;
+
+pub(crate) const POOL_MAX_IDLE_PER_HOST: usize = 10;
+
+/// Connection and pool settings of the underlying Hyper client.
+#[derive(Clone, Debug)]
+pub(crate) struct HyperHttpClientSettings {
+    /// Applies to TCP connections, which includes http and https urls, but not unix sockets or ssh.
+    pub connect_timeout: Option<Duration>,
+    pub pool_max_idle_per_host: usize,
+    /// When not set, Hyper's default applies.
+    pub pool_idle_timeout: Option<Duration>,
+    pub http2_only: bool,
+    /// Forward proxies for http and https urls.
+    pub proxy: Arc<ProxySettings>
+}
+
+impl Default for HyperHttpClientSettings {
+    fn default() -> Self {
+        Self {
+            connect_timeout: None,
+            pool_max_idle_per_host: POOL_MAX_IDLE_PER_HOST,
+            pool_idle_timeout: None,
+            http2_only: false,
+            proxy: Arc::new(ProxySettings::default())
+        }
+    }
+}
+
+impl HyperHttpClientSettings {
+
+    fn client<C: Connect + Clone>(&self, connector: C) -> Client<C, Full<Bytes>> {
+        let mut builder = Client::builder(default_executor());
+
+        builder
+            .pool_timer(TokioTimer::new())
+            .pool_max_idle_per_host(self.pool_max_idle_per_host)
+            .http2_only(self.http2_only);
+
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder.pool_idle_timeout(timeout);
+        }
 
-const POOL_MAX_IDLE_PER_HOST: usize = 10;
+        builder.build(connector)
+    }
+
+    fn tcp_connector(&self) -> ProxyConnector {
+        let mut direct = HttpConnector::new();
+        direct.set_connect_timeout(self.connect_timeout);
+
+        ProxyConnector::new(direct, self.proxy.clone())
+    }
+
+}
 
 /// A thin proxy class that abstracts details and idiosyncrasies of the Hyper http client library.
 ///
 /// Presents a unified interface for interacting with http servers, https servers, and unix sockets.
 #[derive(Debug, Clone)]
 pub(crate) struct HyperHttpClient {
-    config: HyperHttpClientConfig
+    config: HyperHttpClientConfig,
+    settings: HyperHttpClientSettings
 }
 
 impl HyperHttpClient {
 
     pub(super) async fn apply(&self, request: Request<Full<Bytes>>) -> Result<Response<Incoming>, hyper_util::client::legacy::Error> {
         let future = match self.config {
-            HyperHttpClientConfig::Http { ref client, ..} =>
+            HyperHttpClientConfig::Http { ref client, .. } =>
+                client.request(request),
+
+            #[cfg(feature = "native-tls")]
+            HyperHttpClientConfig::Https { ref client, .. } =>
+                client.request(request),
+
+            #[cfg(feature = "rustls")]
+            HyperHttpClientConfig::Rustls { ref client, .. } =>
                 client.request(request),
 
-            HyperHttpClientConfig::Https { ref client, ..} =>
+            HyperHttpClientConfig::Ssh { ref client, .. } =>
                 client.request(request),
 
             #[cfg(unix)]
-            HyperHttpClientConfig::Unix { ref client, ..} =>
+            HyperHttpClientConfig::Unix { ref client, .. } =>
                 client.request(request)
 
         };
@@ -39,70 +107,168 @@ impl HyperHttpClient {
         future.await
     }
 
-    pub(crate) fn http() -> Self {
+    pub(crate) fn http(settings: &HyperHttpClientSettings) -> Self {
         Self {
-            config: HyperHttpClientConfig::http()
+            config: HyperHttpClientConfig::http(settings),
+            settings: settings.clone()
         }
     }
 
-    pub(crate) fn https(tls: TlsConnector) -> Self {
+    // Without a TLS feature, TlsConfig has no variants and this is never called.
+    #[cfg_attr(not(any(feature = "native-tls", feature = "rustls")), allow(unreachable_code, unused_variables))]
+    pub(crate) fn https(tls: TlsConfig, settings: &HyperHttpClientSettings) -> Self {
+        let config = match tls {
+            #[cfg(feature = "native-tls")]
+            TlsConfig::NativeTls(connector) =>
+                HyperHttpClientConfig::https(connector, settings),
+
+            #[cfg(feature = "rustls")]
+            TlsConfig::Rustls(client_config) =>
+                HyperHttpClientConfig::rustls(client_config, settings),
+        };
+
         Self {
-            config: HyperHttpClientConfig::https(tls)
+            config,
+            settings: settings.clone()
+        }
+    }
+
+    pub(crate) fn ssh(destination: SshDestination, settings: &HyperHttpClientSettings) -> Self {
+        Self {
+            config: HyperHttpClientConfig::ssh(SshConnector::new(destination), settings),
+            settings: settings.clone()
         }
     }
 
     #[cfg(unix)]
-    pub(crate) fn unix() -> Self {
+    pub(crate) fn unix(settings: &HyperHttpClientSettings) -> Self {
         Self {
-            config: HyperHttpClientConfig::unix()
+            config: HyperHttpClientConfig::unix(settings),
+            settings: settings.clone()
         }
     }
 
 }
 
+impl DockerEngineTransport for HyperHttpClient {
+
+    fn send(&self, request: Request<Bytes>) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let response = self.apply(request.map(Full::new)).await?;
+            let (parts, body) = response.into_parts();
+            let bytes = body.collect().await?.to_bytes();
+
+            Ok(Response::from_parts(parts, bytes))
+        })
+    }
+
+    fn detached(&self) -> Option<Arc<dyn DockerEngineTransport>> {
+        Some(Arc::new(Self {
+            config: self.config.detached(&self.settings),
+            settings: self.settings.clone()
+        }))
+    }
+
+}
+
 /// Internal configuration of the proxy class.
 #[derive(Debug, Clone)]
 enum HyperHttpClientConfig {
     Http {
-        client: Client<HttpConnector, Full<Bytes>>,
+        client: Client<ProxyConnector, Full<Bytes>>,
+        connector: ProxyConnector
     },
+    #[cfg(feature = "native-tls")]
     Https {
-        client: Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
+        client: Client<HttpsConnector<ProxyConnector>, Full<Bytes>>,
+        connector: HttpsConnector<ProxyConnector>
+    },
+    #[cfg(feature = "rustls")]
+    Rustls {
+        client: Client<hyper_rustls::HttpsConnector<ProxyConnector>, Full<Bytes>>,
+        connector: hyper_rustls::HttpsConnector<ProxyConnector>
+    },
+    Ssh {
+        client: Client<SshConnector, Full<Bytes>>,
+        connector: SshConnector
     },
     #[cfg(unix)]
     Unix {
         client: Client<UnixConnector, Full<Bytes>>,
+        connector: UnixConnector
     }
 }
 
 impl HyperHttpClientConfig {
-    fn http() -> Self {
+
+    /// Same connectors, with a new connection pool.
+    fn detached(&self, settings: &HyperHttpClientSettings) -> Self {
+        match self {
+            Self::Http { connector, .. } =>
+                Self::Http { client: settings.client(connector.clone()), connector: connector.clone() },
+
+            #[cfg(feature = "native-tls")]
+            Self::Https { connector, .. } =>
+                Self::Https { client: settings.client(connector.clone()), connector: connector.clone() },
+
+            #[cfg(feature = "rustls")]
+            Self::Rustls { connector, .. } =>
+                Self::Rustls { client: settings.client(connector.clone()), connector: connector.clone() },
+
+            Self::Ssh { connector, .. } =>
+                Self::Ssh { client: settings.client(connector.clone()), connector: connector.clone() },
+
+            #[cfg(unix)]
+            Self::Unix { connector, .. } =>
+                Self::Unix { client: settings.client(*connector), connector: *connector }
+        }
+    }
+
+    fn http(settings: &HyperHttpClientSettings) -> Self {
+        let connector = settings.tcp_connector();
+
         Self::Http {
-            client: Client::builder(default_executor())
-                .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
-                .build::<_, Full<Bytes>>(HttpConnector::new())
+            client: settings.client(connector.clone()),
+            connector
         }
     }
 
-    fn https(tls: TlsConnector) -> Self {
-        let mut inner = HttpConnector::new();
-        inner.enforce_http(false);
+    #[cfg(feature = "native-tls")]
+    fn https(tls: hyper_tls::native_tls::TlsConnector, settings: &HyperHttpClientSettings) -> Self {
+        let inner = settings.tcp_connector();
 
         let connector = HttpsConnector::from((inner, tls.into()));
 
         Self::Https {
-            client: Client::builder(default_executor())
-                .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
-                .build::<_, Full<Bytes>>(connector)
+            client: settings.client(connector.clone()),
+            connector
+        }
+    }
+
+    #[cfg(feature = "rustls")]
+    fn rustls(client_config: std::sync::Arc<rustls::ClientConfig>, settings: &HyperHttpClientSettings) -> Self {
+        let inner = settings.tcp_connector();
+
+        let connector = hyper_rustls::HttpsConnector::from((inner, client_config));
+
+        Self::Rustls {
+            client: settings.client(connector.clone()),
+            connector
+        }
+    }
+
+    fn ssh(connector: SshConnector, settings: &HyperHttpClientSettings) -> Self {
+        Self::Ssh {
+            client: settings.client(connector.clone()),
+            connector
         }
     }
 
     #[cfg(unix)]
-    fn unix() -> Self {
+    fn unix(settings: &HyperHttpClientSettings) -> Self {
         Self::Unix {
-            client: Client::builder(default_executor())
-                .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
-                .build::<_, Full<Bytes>>(UnixConnector {})
+            client: settings.client(UnixConnector {}),
+            connector: UnixConnector {}
         }
     }
 
@@ -114,7 +280,7 @@ mod test_hyper_http_client {
     use hyper::body::Bytes;
     use hyper::{Request, StatusCode};
 
-    use crate::imp::hyper_proxy::HyperHttpClient;
+    use crate::imp::hyper_proxy::{HyperHttpClient, HyperHttpClientSettings};
 
     #[tokio::test]
     async fn gets_from_http_server() {
@@ -126,7 +292,7 @@ mod test_hyper_http_client {
             .create_async()
             .await;
 
-        let client = HyperHttpClient::http();
+        let client = HyperHttpClient::http(&HyperHttpClientSettings::default());
 
         let request = Request::get(format!("{}{}", server.url(), path))
             .body(Full::new(Bytes::new()))
@@ -140,4 +306,107 @@ mod test_hyper_http_client {
         assert_eq!(StatusCode::OK, response.status());
     }
 
-}
\ No newline at end of file
+    // A pooled connection belongs to the runtime that opened it, which is blocked here while
+    // another thread and runtime use the detached client.
+    #[tokio::test]
+    async fn detached_does_not_share_connections() {
+        use crate::client::DockerEngineTransport;
+
+        let mut server = mockito::Server::new_async().await;
+        let path = "/some/file";
+
+        server.mock("GET", path)
+            .with_status(200)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let url = format!("{}{}", server.url(), path);
+
+        let client = HyperHttpClient::http(&HyperHttpClientSettings::default());
+
+        let response = client
+            .send(Request::get(&url).body(Bytes::new()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(StatusCode::OK, response.status());
+
+        let detached = client
+            .detached()
+            .unwrap();
+
+        let status = std::thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(detached.send(Request::get(&url).body(Bytes::new()).unwrap()))
+                .unwrap()
+                .status()
+        })
+            .join()
+            .unwrap();
+
+        assert_eq!(StatusCode::OK, status);
+    }
+
+    // Stand-in for ssh that ignores stdin after the request line, and responds
+    // with the arguments it was started with.
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn gets_over_ssh_stand_in() {
+        use std::os::unix::fs::PermissionsExt;
+
+        use crate::client::DockerEngineTransport;
+        use crate::imp::ssh::{SSH_TUNNEL_URL, SshConnector, SshDestination};
+        use super::{HyperHttpClientConfig, HyperHttpClientSettings};
+
+        let dir = tempfile::tempdir()
+            .unwrap();
+
+        let program = dir.path().join("ssh");
+
+        std::fs::write(
+            &program,
+            "#!/bin/sh\n\
+            read -r line\n\
+            body=\"$*\"\n\
+            printf 'HTTP/1.1 200 OK\\r\\nContent-Length: %d\\r\\nConnection: close\\r\\n\\r\\n%s' \"${#body}\" \"$body\"\n"
+        )
+            .unwrap();
+
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755))
+            .unwrap();
+
+        let destination = SshDestination::parse("ssh://alice@builder:2222")
+            .unwrap();
+
+        let settings = HyperHttpClientSettings::default();
+
+        let client = HyperHttpClient {
+            config: HyperHttpClientConfig::ssh(
+                SshConnector::with_program(destination, program.to_str().unwrap()),
+                &settings
+            ),
+            settings
+        };
+
+        let request = Request::get(format!("{}/version", SSH_TUNNEL_URL))
+            .body(Bytes::new())
+            .unwrap();
+
+        let response = client
+            .send(request)
+            .await
+            .unwrap();
+
+        assert_eq!(StatusCode::OK, response.status());
+
+        assert_eq!(
+            "-p 2222 -l alice -- builder docker system dial-stdio",
+            String::from_utf8(response.into_body().to_vec()).unwrap()
+        );
+    }
+
+}
@@ -8,6 +8,15 @@ pub(crate) fn base64_encode<T: AsRef<[u8]>>(input: T) -> String {
     STANDARD.encode(input)
 }
 
+/// Convenience wrapper over base64 crate's breaking changes
+#[cfg(feature = "native-tls")]
+pub(crate) fn base64_decode<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, base64::DecodeError> {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+
+    STANDARD.decode(input)
+}
+
 /// Some functions convert/extract input parameters that represent failure information.
 /// That information is converted to an error result type.
 ///
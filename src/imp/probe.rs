@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use http_body_util::Empty;
+use hyper::{Request, StatusCode};
+use hyper::body::Bytes;
+use hyper_util::client::legacy::Client;
+use tokio::net::TcpStream;
+
+use crate::imp::hyper_shims::default_executor;
+
+/// Connect to a TCP port, and immediately disconnect.
+pub(crate) async fn tcp_connects(host: &str, port: u16, timeout: Duration) -> Result<(), String> {
+    match tokio::time::timeout(timeout, TcpStream::connect((host, port))).await {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(e)) => Err(format!("connecting to {}:{} failed: {}", host, port, e)),
+        Err(_) => Err(format!("connecting to {}:{} timed out after {:?}", host, port, timeout))
+    }
+}
+
+/// Status of a GET request to a plain http url, without a proxy.
+pub(crate) async fn http_status(url: &str, timeout: Duration) -> Result<StatusCode, String> {
+    let client = Client::builder(default_executor())
+        .build_http::<Empty<Bytes>>();
+
+    let request = Request::get(url)
+        .body(Empty::new())
+        .map_err(|e| format!("invalid url {}: {}", url, e))?;
+
+    match tokio::time::timeout(timeout, client.request(request)).await {
+        Ok(Ok(response)) => Ok(response.status()),
+        Ok(Err(e)) => Err(format!("GET {} failed: {}", url, e)),
+        Err(_) => Err(format!("GET {} timed out after {:?}", url, timeout))
+    }
+}
+
+#[cfg(test)]
+mod test_probe {
+    use std::time::Duration;
+
+    use hyper::StatusCode;
+
+    use super::{http_status, tcp_connects};
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[tokio::test]
+    async fn gets_status() {
+        let mut server = mockito::Server::new_async().await;
+
+        server.mock("GET", "/health")
+            .with_status(503)
+            .create_async()
+            .await;
+
+        let status = http_status(&format!("{}/health", server.url()), TIMEOUT)
+            .await
+            .unwrap();
+
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, status);
+    }
+
+    #[tokio::test]
+    async fn refused() {
+        let port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+
+        let error = tcp_connects("127.0.0.1", port, TIMEOUT)
+            .await
+            .unwrap_err();
+
+        assert!(error.starts_with(&format!("connecting to 127.0.0.1:{} failed: ", port)), "{}", error);
+    }
+
+}
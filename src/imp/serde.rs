@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::iter::{FromIterator, zip};
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serializer};
 use serde::de::{DeserializeOwned, Error};
 use serde_json::Value;
 use crate::model::Unit;
@@ -14,6 +14,14 @@ pub(crate) fn dz_empty_as_none<'de, D>(deserializer: D) -> Result<Option<String>
     Ok(result)
 }
 
+/// Inverse of dz_empty_as_none, writing None as an empty string.
+pub(crate) fn sz_none_as_empty<S>(value: &Option<String>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+{
+    serializer.serialize_str(value.as_deref().unwrap_or_default())
+}
+
 /// Treat empty JSON map {} the same as being null or absent.
 pub(crate) fn dz_empty_object_as_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
 where
@@ -202,6 +210,32 @@ mod test_dz_empty_as_none {
 
 }
 
+#[cfg(test)]
+mod test_sz_none_as_empty {
+    use serde::Serialize;
+    use super::sz_none_as_empty;
+
+    #[derive(Serialize)]
+    struct HasFoo {
+        #[serde(serialize_with = "sz_none_as_empty")]
+        pub foo: Option<String>
+    }
+
+    #[test]
+    fn when_none() {
+        let actual = serde_json::to_string(&HasFoo { foo: None }).unwrap();
+
+        assert_eq!(r#"{"foo":""}"#, actual);
+    }
+
+    #[test]
+    fn when_some() {
+        let actual = serde_json::to_string(&HasFoo { foo: Some("bar".into()) }).unwrap();
+
+        assert_eq!(r#"{"foo":"bar"}"#, actual);
+    }
+}
+
 #[cfg(test)]
 mod test_dz_empty_object_as_none {
     use serde::Deserialize;
@@ -0,0 +1,66 @@
+use time::OffsetDateTime;
+use time::format_description::well_known::Iso8601;
+
+/// What the Docker Engine reports for a time that has not happened, such as the
+/// finish time of a container that has never stopped.
+const ZERO_TIME_PREFIX: &str = "0001-01-01T00:00:00";
+
+/// Parse a time reported by the Docker Engine, such as 2022-11-28T00:34:45.107901180Z.
+///
+/// Returns None for an empty string, the zero time, or text that is not a time.
+pub(crate) fn parse_engine_time(text: &str) -> Option<OffsetDateTime> {
+    if text.is_empty() || text.starts_with(ZERO_TIME_PREFIX) {
+        return None;
+    }
+
+    OffsetDateTime::parse(text, &Iso8601::DEFAULT).ok()
+}
+
+/// Convert seconds since the unix epoch, as reported by list apis.
+pub(crate) fn from_unix_seconds(seconds: i64) -> Option<OffsetDateTime> {
+    OffsetDateTime::from_unix_timestamp(seconds).ok()
+}
+
+#[cfg(test)]
+mod test_parse_engine_time {
+    use time::{Date, Month, PrimitiveDateTime, Time};
+
+    use super::parse_engine_time;
+
+    #[test]
+    fn parses_nanoseconds() {
+        let actual = parse_engine_time("2022-11-28T00:34:45.107901180Z");
+
+        assert_eq!(
+            Some(
+                PrimitiveDateTime::new(
+                    Date::from_calendar_date(2022, Month::November, 28)
+                        .unwrap(),
+                    Time::from_hms_nano(0, 34, 45, 107901180)
+                        .unwrap()
+                )
+                    .assume_utc()
+            ),
+            actual
+        );
+    }
+
+    #[test]
+    fn parses_offset() {
+        let actual = parse_engine_time("2023-05-01T10:00:00+02:00")
+            .unwrap();
+
+        assert_eq!(1682928000, actual.unix_timestamp());
+    }
+
+    #[test]
+    fn zero_time_is_none() {
+        assert_eq!(None, parse_engine_time("0001-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn empty_or_invalid_is_none() {
+        assert_eq!(None, parse_engine_time(""));
+        assert_eq!(None, parse_engine_time("yesterday"));
+    }
+}
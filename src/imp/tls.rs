@@ -0,0 +1,46 @@
+use crate::errors::DecCreateError;
+use crate::model::TlsConfig;
+
+/// TLS configuration for https urls without explicit TLS configuration, which trusts
+/// the certificate authorities of the platform.
+///
+/// When both TLS cargo features are enabled, native-tls is used.
+pub(crate) fn default_tls_config() -> Result<TlsConfig, DecCreateError> {
+    #[cfg(feature = "native-tls")]
+    {
+        Ok(TlsConfig::NativeTls(super::tls_native::default_connector()?))
+    }
+
+    #[cfg(all(feature = "rustls", not(feature = "native-tls")))]
+    {
+        Ok(TlsConfig::Rustls(super::tls_rustls::default_config()?))
+    }
+
+    #[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+    {
+        Err(DecCreateError::TlsFeatureDisabled)
+    }
+}
+
+/// Build a TLS configuration from PEM encoded certificates and key, the way the docker
+/// cli does for DOCKER_CERT_PATH and for contexts. A supplied CA is trusted in addition to
+/// the certificate authorities of the platform.
+///
+/// When both TLS cargo features are enabled, native-tls is used.
+#[cfg_attr(not(any(feature = "native-tls", feature = "rustls")), allow(unused_variables))]
+pub(crate) fn tls_config(ca: Option<&[u8]>, cert_and_key: Option<(&[u8], &[u8])>, verify: bool) -> Result<TlsConfig, DecCreateError> {
+    #[cfg(feature = "native-tls")]
+    {
+        Ok(TlsConfig::NativeTls(super::tls_native::connector(ca, cert_and_key, verify)?))
+    }
+
+    #[cfg(all(feature = "rustls", not(feature = "native-tls")))]
+    {
+        Ok(TlsConfig::Rustls(super::tls_rustls::config(ca, cert_and_key, verify)?))
+    }
+
+    #[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+    {
+        Err(DecCreateError::TlsFeatureDisabled)
+    }
+}
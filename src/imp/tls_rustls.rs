@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use log::warn;
+use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{CryptoProvider, verify_tls12_signature, verify_tls13_signature};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::pki_types::pem::PemObject;
+
+use crate::errors::DecCreateError;
+
+fn provider() -> Arc<CryptoProvider> {
+    Arc::new(rustls::crypto::ring::default_provider())
+}
+
+fn native_roots() -> RootCertStore {
+    let loaded = rustls_native_certs::load_native_certs();
+
+    for error in loaded.errors {
+        warn!("Unable to load platform certificate: {}", error);
+    }
+
+    let mut roots = RootCertStore::empty();
+    let (added, ignored) = roots.add_parsable_certificates(loaded.certs);
+
+    if ignored > 0 {
+        warn!("Ignored {} of {} platform certificates", ignored, added + ignored);
+    }
+
+    roots
+}
+
+fn pem_certificates(pem: &[u8]) -> Result<Vec<CertificateDer<'static>>, DecCreateError> {
+    let certificates = CertificateDer::pem_slice_iter(pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| DecCreateError::TlsCertificate(e.to_string()))?;
+
+    if certificates.is_empty() {
+        return Err(DecCreateError::TlsCertificate("no PEM certificates found".into()));
+    }
+
+    Ok(certificates)
+}
+
+pub(super) fn default_config() -> Result<Arc<ClientConfig>, DecCreateError> {
+    let config = ClientConfig::builder_with_provider(provider())
+        .with_safe_default_protocol_versions()?
+        .with_root_certificates(native_roots())
+        .with_no_client_auth();
+
+    Ok(Arc::new(config))
+}
+
+pub(super) fn config(ca: Option<&[u8]>, cert_and_key: Option<(&[u8], &[u8])>, verify: bool) -> Result<Arc<ClientConfig>, DecCreateError> {
+    let provider = provider();
+
+    let builder = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?;
+
+    let builder = if verify {
+        // A supplied CA is trusted in addition to the platform's, as with native-tls.
+        let mut roots = native_roots();
+
+        if let Some(pem) = ca {
+            for cert in pem_certificates(pem)? {
+                roots.add(cert)
+                    .map_err(|e| DecCreateError::TlsCertificate(e.to_string()))?;
+            }
+        }
+
+        builder.with_root_certificates(roots)
+    }
+    else {
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(AcceptAnyServerCertificate(provider)))
+    };
+
+    let config = match cert_and_key {
+        None => builder.with_no_client_auth(),
+        Some((cert, key)) => {
+            // Accepts PKCS #1, PKCS #8, and SEC1 keys
+            let private_key = PrivateKeyDer::from_pem_slice(key)
+                .map_err(|e| DecCreateError::TlsKey(e.to_string()))?;
+
+            builder.with_client_auth_cert(pem_certificates(cert)?, private_key)?
+        }
+    };
+
+    Ok(Arc::new(config))
+}
+
+/// Equivalent of the docker cli's InsecureSkipVerify, for a context with SkipTLSVerify
+/// or TlsPemFiles::verify(false). TLS configured from the environment always verifies.
+/// Signatures are still checked, so that the handshake itself is valid.
+#[derive(Debug)]
+struct AcceptAnyServerCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyServerCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(&self, message: &[u8], cert: &CertificateDer<'_>, dss: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(&self, message: &[u8], cert: &CertificateDer<'_>, dss: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod test_tls_rustls {
+    use crate::errors::DecCreateError;
+    use super::config;
+
+    #[test]
+    fn builds_without_verification() {
+        config(None, None, false)
+            .unwrap();
+    }
+
+    #[test]
+    fn fails_with_invalid_ca() {
+        let error = config(Some(b"-----BEGIN CERTIFICATE-----\nnot base64!\n-----END CERTIFICATE-----\n"), None, true)
+            .unwrap_err();
+
+        assert!(matches!(error, DecCreateError::TlsCertificate(_)), "Unexpected error: {}", error);
+    }
+
+    #[test]
+    fn fails_without_ca_certificates() {
+        let error = config(Some(b"not a certificate"), None, true)
+            .unwrap_err();
+
+        assert!(matches!(error, DecCreateError::TlsCertificate(_)), "Unexpected error: {}", error);
+    }
+}
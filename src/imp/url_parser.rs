@@ -3,6 +3,10 @@ pub(crate) fn is_http(uri: &str) -> bool {
     uri.starts_with("http://") || uri.starts_with("https://")
 }
 
+pub(crate) fn is_ssh(uri: &str) -> bool {
+    uri.starts_with("ssh://")
+}
+
 #[cfg(test)]
 pub mod test_is_http {
 
@@ -38,4 +42,19 @@ pub mod test_is_http {
             assert!(is_http("https://foo"))
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+pub mod test_is_ssh {
+    use crate::imp::url_parser::is_ssh;
+
+    #[test]
+    pub fn when_http() {
+        assert!(!is_ssh("http://foo"))
+    }
+
+    #[test]
+    pub fn when_ssh() {
+        assert!(is_ssh("ssh://user@foo:22"))
+    }
+}
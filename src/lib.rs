@@ -26,7 +26,13 @@
 //! ```
 
 pub mod client;
+#[cfg(feature = "compose")]
+pub mod compose;
 pub mod errors;
+#[cfg(any(test, feature = "test-util"))]
+pub mod fake;
+#[cfg(any(test, feature = "test-util"))]
+pub mod fixture;
 pub mod model;
 pub mod requests;
 pub mod responses;
@@ -1,21 +1,35 @@
 mod archive;
+mod cancellation;
 mod container;
 mod container_ipam;
 mod health_check;
+mod identifiers;
 mod mount;
 mod network_ipam;
 mod other;
+mod platform;
+mod proxy;
+mod readiness;
 mod registry;
+mod retry;
 mod streams;
+mod tls;
 mod volume;
 
 pub use archive::*;
+pub use cancellation::*;
 pub use container::*;
 pub use container_ipam::*;
 pub use health_check::*;
+pub use identifiers::*;
 pub use mount::*;
 pub use network_ipam::*;
 pub use other::*;
+pub use platform::*;
+pub use proxy::*;
+pub use readiness::*;
 pub use registry::*;
+pub use retry::*;
 pub use streams::*;
+pub use tls::*;
 pub use volume::*;
@@ -0,0 +1,61 @@
+use std::time::{Duration, Instant};
+
+pub use tokio_util::sync::CancellationToken;
+
+/// Stops a long running call, such as building or pulling an image, waiting for a
+/// container, or running an exec, at a deadline or when a token is cancelled.
+///
+/// When a call is stopped, its connection to the Docker Engine is closed, which makes
+/// the Docker Engine abort the build or pull.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use passivized_docker_engine_client::DockerEngineClient;
+/// use passivized_docker_engine_client::errors::DecError;
+/// use passivized_docker_engine_client::model::{Cancellation, CancellationToken};
+///
+/// async fn example(token: CancellationToken) -> Result<(), DecError> {
+///     let dec = DockerEngineClient::new()?;
+///
+///     let cancellation = Cancellation::default()
+///         .timeout(Duration::from_secs(300))
+///         .token(token);
+///
+///     dec.images()
+///         .with_cancellation(cancellation)
+///         .pull("nginx", "latest")
+///         .await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Cancellation {
+    pub(crate) deadline: Option<Instant>,
+    pub(crate) token: Option<CancellationToken>
+}
+
+impl Cancellation {
+
+    /// Stop the call at a specific time.
+    pub fn deadline(mut self, v: Instant) -> Self {
+        self.deadline = Some(v);
+        self
+    }
+
+    /// Stop the call if it is still running after a duration, measured from now.
+    pub fn timeout(mut self, v: Duration) -> Self {
+        self.deadline = Some(Instant::now() + v);
+        self
+    }
+
+    /// Stop the call when the token is cancelled.
+    pub fn token(mut self, v: CancellationToken) -> Self {
+        self.token = Some(v);
+        self
+    }
+
+}
@@ -0,0 +1,352 @@
+use std::borrow::Borrow;
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Length of the short form of an ID, as shown by the docker cli.
+const SHORT_ID_LENGTH: usize = 12;
+
+const SHA256_PREFIX: &str = "sha256:";
+
+macro_rules! identifier {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn into_string(self) -> String {
+                self.0
+            }
+
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl From<&$name> for $name {
+            fn from(other: &$name) -> Self {
+                other.clone()
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(other: &str) -> Self {
+                Self(other.to_string())
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(other: String) -> Self {
+                Self(other)
+            }
+        }
+
+        impl From<&String> for $name {
+            fn from(other: &String) -> Self {
+                Self(other.clone())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(other: $name) -> Self {
+                other.0
+            }
+        }
+
+        impl From<&$name> for String {
+            fn from(other: &$name) -> Self {
+                other.0.clone()
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                &self.0 == other
+            }
+        }
+
+        impl PartialEq<$name> for str {
+            fn eq(&self, other: &$name) -> bool {
+                self == other.0
+            }
+        }
+
+        impl PartialEq<$name> for &str {
+            fn eq(&self, other: &$name) -> bool {
+                *self == other.0
+            }
+        }
+
+        impl PartialEq<$name> for String {
+            fn eq(&self, other: &$name) -> bool {
+                self == &other.0
+            }
+        }
+    };
+}
+
+macro_rules! hex_identifier {
+    ($name:ident) => {
+        impl $name {
+
+            /// The first 12 characters of the ID, as shown by the docker cli. Names are not shortened.
+            pub fn short(&self) -> &str {
+                short_hex(&self.0)
+            }
+
+            /// Whether this ID starts with a prefix of at least one character, as the Docker Engine
+            /// matches a short or partial ID.
+            pub fn matches_prefix(&self, prefix: &str) -> bool {
+                !prefix.is_empty() && self.0.starts_with(prefix)
+            }
+
+        }
+    };
+}
+
+identifier!(
+    /// ID or name of a container.
+    ///
+    /// DockerEngineClient::container accepts one of these, or a string, but not an image ID:
+    ///
+    /// ```compile_fail
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::model::ImageId;
+    ///
+    /// fn example(dec: &DockerEngineClient, image: ImageId) {
+    ///     dec.container(image);
+    /// }
+    /// ```
+    ContainerId
+);
+
+hex_identifier!(ContainerId);
+
+identifier!(
+    /// ID of a container exec instance.
+    ExecId
+);
+
+hex_identifier!(ExecId);
+
+identifier!(
+    /// ID of an image, a digest such as "sha256:4c0f...". Image names and tags are not IDs.
+    ImageId
+);
+
+identifier!(
+    /// ID of an image, or a reference to one by name, tag or digest, such as "nginx:1.25".
+    ///
+    /// DockerEngineClient::images accepts one of these, an ImageId, or a string, but not
+    /// the ID of a container, network, exec or volume:
+    ///
+    /// ```compile_fail
+    /// use passivized_docker_engine_client::DockerEngineClient;
+    /// use passivized_docker_engine_client::model::ContainerId;
+    ///
+    /// async fn example(dec: &DockerEngineClient, container: ContainerId) {
+    ///     dec.images().remove(container, true).await.unwrap();
+    /// }
+    /// ```
+    ImageRef
+);
+
+impl From<ImageId> for ImageRef {
+    fn from(other: ImageId) -> Self {
+        Self(other.0)
+    }
+}
+
+impl From<&ImageId> for ImageRef {
+    fn from(other: &ImageId) -> Self {
+        Self(other.0.clone())
+    }
+}
+
+identifier!(
+    /// ID or name of a network.
+    NetworkId
+);
+
+hex_identifier!(NetworkId);
+
+identifier!(
+    /// Name of a volume. Volumes do not have separate IDs.
+    VolumeName
+);
+
+impl ImageId {
+
+    /// Parse an image ID, either a sha256 digest such as "sha256:4c0f...", or its 64 hex characters.
+    pub fn parse(text: &str) -> Result<Self, ImageIdParseError> {
+        let hex = text.strip_prefix(SHA256_PREFIX).unwrap_or(text);
+
+        if hex.len() == 64 && hex.chars().all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c)) {
+            Ok(Self(format!("{}{}", SHA256_PREFIX, hex)))
+        }
+        else {
+            Err(ImageIdParseError(text.to_string()))
+        }
+    }
+
+    /// Hex characters of the digest, without its algorithm.
+    pub fn digest(&self) -> &str {
+        self.0.strip_prefix(SHA256_PREFIX).unwrap_or(&self.0)
+    }
+
+    /// The first 12 hex characters of the digest, as shown by the docker cli.
+    pub fn short(&self) -> &str {
+        short_hex(self.digest())
+    }
+
+    /// Whether this ID starts with a prefix of at least one character, with or without "sha256:".
+    pub fn matches_prefix(&self, prefix: &str) -> bool {
+        let prefix = prefix.strip_prefix(SHA256_PREFIX).unwrap_or(prefix);
+
+        !prefix.is_empty() && self.digest().starts_with(prefix)
+    }
+
+}
+
+impl FromStr for ImageId {
+    type Err = ImageIdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Text that is not an image ID.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImageIdParseError(pub String);
+
+impl Display for ImageIdParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Not an image ID: {}", self.0)
+    }
+}
+
+impl std::error::Error for ImageIdParseError {}
+
+fn short_hex(id: &str) -> &str {
+    if id.len() > SHORT_ID_LENGTH && id.chars().all(|c| c.is_ascii_hexdigit()) {
+        &id[..SHORT_ID_LENGTH]
+    }
+    else {
+        id
+    }
+}
+
+#[cfg(test)]
+mod test_identifiers {
+
+    mod container_id {
+        use crate::model::ContainerId;
+
+        const ID: &str = "4c0f6ecd8e5b0d0c0a5bd3d2b7bba4e31c3c4a8ccd9c2bf9bd9e0d3c0f6d7a1b";
+
+        #[test]
+        fn shortens_ids_but_not_names() {
+            assert_eq!("4c0f6ecd8e5b", ContainerId::from(ID).short());
+            assert_eq!("some_long_container_name", ContainerId::from("some_long_container_name").short());
+        }
+
+        #[test]
+        fn matches_prefix() {
+            let id = ContainerId::from(ID);
+
+            assert!(id.matches_prefix("4c0f"));
+            assert!(!id.matches_prefix("4c1"));
+            assert!(!id.matches_prefix(""));
+        }
+
+        #[test]
+        fn compares_with_strings() {
+            let id = ContainerId::from(ID);
+
+            assert_eq!(ID, id);
+            assert_eq!(id, ID.to_string());
+        }
+    }
+
+    mod image_id {
+        use crate::model::ImageId;
+
+        const HEX: &str = "a3ed95caeb02ffe68cdd9fd84406680ae93d633cb16422d00e8a7c22955b46d4";
+
+        #[test]
+        fn parses_digest() {
+            let id = ImageId::parse(&format!("sha256:{}", HEX)).unwrap();
+
+            assert_eq!(HEX, id.digest());
+            assert_eq!("a3ed95caeb02", id.short());
+            assert_eq!(format!("sha256:{}", HEX), id.to_string());
+        }
+
+        #[test]
+        fn parses_hex_as_sha256() {
+            let id: ImageId = HEX.parse().unwrap();
+
+            assert_eq!(format!("sha256:{}", HEX), id.as_str());
+        }
+
+        #[test]
+        fn rejects_names() {
+            assert!(ImageId::parse("nginx:latest").is_err());
+            assert!(ImageId::parse("sha256:abc").is_err());
+        }
+
+        #[test]
+        fn matches_prefix_with_or_without_algorithm() {
+            let id = ImageId::parse(HEX).unwrap();
+
+            assert!(id.matches_prefix("a3ed95"));
+            assert!(id.matches_prefix("sha256:a3ed95"));
+            assert!(!id.matches_prefix("sha256:"));
+        }
+    }
+}
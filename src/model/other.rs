@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+use serde::ser::SerializeMap;
 
 /// Some of the maps in the Docker REST api have an empty object {} as their value.
 ///
@@ -13,5 +14,12 @@ use serde::{Deserialize, Serialize};
 ///
 /// For example, the ExposedPorts property of a container creation request,
 /// described at https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerCreate.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
 pub struct Unit;
+
+impl Serialize for Unit {
+    /// Serialize as an empty object {}, rather than null.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_map(Some(0))?.end()
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// An image platform: operating system, CPU architecture, and optionally a CPU variant.
+///
+/// Written as `os/architecture[/variant]`, the same format the docker CLI
+/// accepts for `--platform`.
+///
+/// # Example
+///
+/// ```rust
+/// use passivized_docker_engine_client::model::Platform;
+///
+/// let platform: Platform = "linux/arm64/v8".parse().unwrap();
+///
+/// assert_eq!("linux", platform.os);
+/// assert_eq!("arm64", platform.architecture);
+/// assert_eq!(Some("v8".to_string()), platform.variant);
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Platform {
+    pub os: String,
+    pub architecture: String,
+    pub variant: Option<String>
+}
+
+impl Platform {
+
+    pub fn new<O: Into<String>, A: Into<String>>(os: O, architecture: A) -> Self {
+        Self {
+            os: os.into(),
+            architecture: architecture.into(),
+            variant: None
+        }
+    }
+
+    pub fn variant<V: Into<String>>(mut self, v: V) -> Self {
+        self.variant = Some(v.into());
+        self
+    }
+
+    /// Compare to another platform, where a missing variant on either side matches any variant.
+    ///
+    /// Docker Engine does not always report a variant for images, even when the
+    /// image was pulled for a platform that has one (e.g. linux/arm64/v8).
+    pub fn matches(&self, other: &Platform) -> bool {
+        self.os == other.os
+            && self.architecture == other.architecture
+            && match (&self.variant, &other.variant) {
+                (Some(a), Some(b)) => a == b,
+                _ => true
+            }
+    }
+
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.variant {
+            None => write!(f, "{}/{}", self.os, self.architecture),
+            Some(variant) => write!(f, "{}/{}/{}", self.os, self.architecture, variant)
+        }
+    }
+}
+
+impl FromStr for Platform {
+    type Err = PlatformParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split('/').collect();
+
+        if parts.iter().any(|part| part.is_empty()) {
+            return Err(PlatformParseError(s.to_string()));
+        }
+
+        match parts.as_slice() {
+            [os, architecture] =>
+                Ok(Platform::new(*os, *architecture)),
+
+            [os, architecture, variant] =>
+                Ok(Platform::new(*os, *architecture).variant(*variant)),
+
+            _ =>
+                Err(PlatformParseError(s.to_string()))
+        }
+    }
+}
+
+/// Text could not be parsed as `os/architecture[/variant]`.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+#[error("Invalid platform '{0}'; expected os/architecture or os/architecture/variant")]
+pub struct PlatformParseError(pub String);
+
+#[cfg(test)]
+mod test_platform {
+
+    mod display {
+        use super::super::Platform;
+
+        #[test]
+        fn without_variant() {
+            assert_eq!("linux/amd64", Platform::new("linux", "amd64").to_string());
+        }
+
+        #[test]
+        fn with_variant() {
+            assert_eq!("linux/arm/v7", Platform::new("linux", "arm").variant("v7").to_string());
+        }
+    }
+
+    mod from_str {
+        use super::super::{Platform, PlatformParseError};
+
+        #[test]
+        fn os_and_architecture() {
+            let actual: Platform = "windows/amd64".parse()
+                .unwrap();
+
+            assert_eq!(Platform::new("windows", "amd64"), actual);
+        }
+
+        #[test]
+        fn os_architecture_and_variant() {
+            let actual: Platform = "linux/arm64/v8".parse()
+                .unwrap();
+
+            assert_eq!(Platform::new("linux", "arm64").variant("v8"), actual);
+        }
+
+        #[test]
+        fn fails_when_architecture_missing() {
+            let actual = "linux".parse::<Platform>()
+                .unwrap_err();
+
+            assert_eq!(PlatformParseError("linux".into()), actual);
+        }
+
+        #[test]
+        fn fails_when_segment_empty() {
+            let actual = "linux//v8".parse::<Platform>()
+                .unwrap_err();
+
+            assert_eq!(PlatformParseError("linux//v8".into()), actual);
+        }
+
+        #[test]
+        fn fails_when_too_many_segments() {
+            "linux/arm64/v8/extra".parse::<Platform>()
+                .unwrap_err();
+        }
+    }
+
+    mod matches {
+        use super::super::Platform;
+
+        #[test]
+        fn when_equal() {
+            let a = Platform::new("linux", "arm").variant("v7");
+
+            assert!(a.matches(&a.clone()));
+        }
+
+        #[test]
+        fn when_variant_missing() {
+            let a = Platform::new("linux", "arm64");
+            let b = Platform::new("linux", "arm64").variant("v8");
+
+            assert!(a.matches(&b));
+            assert!(b.matches(&a));
+        }
+
+        #[test]
+        fn not_when_variant_differs() {
+            let a = Platform::new("linux", "arm").variant("v6");
+            let b = Platform::new("linux", "arm").variant("v7");
+
+            assert!(!a.matches(&b));
+        }
+
+        #[test]
+        fn not_when_architecture_differs() {
+            let a = Platform::new("linux", "amd64");
+            let b = Platform::new("linux", "arm64");
+
+            assert!(!a.matches(&b));
+        }
+    }
+}
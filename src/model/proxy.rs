@@ -0,0 +1,103 @@
+use crate::errors::DecCreateError;
+use crate::imp::forward_proxy::{NoProxy, ProxyServer, ProxySettings};
+
+/// Forward proxy for reaching a Docker Engine at a http://, tcp:// or https:// url.
+///
+/// Requests to http:// and tcp:// urls are forwarded by the proxy; connections to
+/// https:// urls are tunneled through the proxy with CONNECT. Proxy urls must start
+/// with http://, and may include a user name and password.
+///
+/// Unix sockets and ssh urls never use a proxy, and neither do localhost and loopback
+/// addresses.
+///
+/// # Example
+///
+/// ```rust
+/// use passivized_docker_engine_client::DockerEngineClient;
+/// use passivized_docker_engine_client::errors::DecCreateError;
+/// use passivized_docker_engine_client::model::Proxy;
+///
+/// fn example() -> Result<(), DecCreateError> {
+///     let dec = DockerEngineClient::builder()
+///         .server("https://builder.corp.example:2376")
+///         .proxy(
+///             Proxy::all("http://proxy.corp.example:3128")
+///                 .no_proxy(".internal.example")
+///         )
+///         .build()?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Proxy {
+    /// Proxy for http:// and tcp:// urls
+    pub http: Option<String>,
+
+    /// Proxy for https:// urls
+    pub https: Option<String>,
+
+    /// Hosts that are reached directly. An entry matches the host itself and, unless it
+    /// includes a port, its subdomains. A single "*" matches all hosts.
+    pub no_proxy: Vec<String>
+}
+
+impl Proxy {
+
+    /// Use one proxy for http://, tcp:// and https:// urls.
+    pub fn all<U: Into<String>>(url: U) -> Self {
+        let url = url.into();
+
+        Self {
+            http: Some(url.clone()),
+            https: Some(url),
+            no_proxy: Vec::new()
+        }
+    }
+
+    /// Like the docker cli, use HTTP_PROXY, HTTPS_PROXY and NO_PROXY, or their lower case
+    /// equivalents. This is the default of a DockerEngineClient.
+    pub fn from_env() -> Self {
+        Self {
+            http: crate::imp::env::http_proxy(),
+            https: crate::imp::env::https_proxy(),
+            no_proxy: crate::imp::env::no_proxy()
+                .map(|v| NoProxy::split(&v))
+                .unwrap_or_default()
+        }
+    }
+
+    /// Always connect directly.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    pub fn http<U: Into<String>>(mut self, url: U) -> Self {
+        self.http = Some(url.into());
+        self
+    }
+
+    pub fn https<U: Into<String>>(mut self, url: U) -> Self {
+        self.https = Some(url.into());
+        self
+    }
+
+    pub fn no_proxy<H: Into<String>>(mut self, host: H) -> Self {
+        self.no_proxy.push(host.into());
+        self
+    }
+
+    pub(crate) fn settings(&self) -> Result<ProxySettings, DecCreateError> {
+        let parse = |url: &Option<String>| url
+            .as_deref()
+            .map(ProxyServer::parse)
+            .transpose();
+
+        Ok(ProxySettings {
+            http: parse(&self.http)?,
+            https: parse(&self.https)?,
+            no_proxy: NoProxy::new(&self.no_proxy)
+        })
+    }
+
+}
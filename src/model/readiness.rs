@@ -0,0 +1,129 @@
+use std::fmt::{Display, Formatter};
+
+use hyper::StatusCode;
+use regex::Regex;
+
+/// When a running container is ready for use. See DecContainer::wait_until.
+///
+/// Conditions on ports apply to container ports, such as "80" or "53/udp", that are published
+/// on the host. They are checked at the host of the Docker Engine, or localhost for a unix socket.
+///
+/// # Example
+///
+/// ```rust
+/// use hyper::StatusCode;
+///
+/// use passivized_docker_engine_client::model::Readiness;
+///
+/// let ready = Readiness::all(vec![
+///     Readiness::Healthy,
+///     Readiness::http("8080", "/status", StatusCode::OK)
+/// ]);
+/// ```
+#[derive(Clone, Debug)]
+pub enum Readiness {
+    /// As soon as the container is running.
+    Running,
+
+    /// Once a line the container has written to stdout or stderr contains the text.
+    LogContains(String),
+
+    /// Once a line of the container's stdout or stderr matches the regular expression.
+    LogMatches(Regex),
+
+    /// Once the container's health check has passed. The image, or the create request,
+    /// must define a health check.
+    Healthy,
+
+    /// Once a published port accepts TCP connections.
+    ///
+    /// Some Docker Engines accept connections to published ports before the container
+    /// listens on them. Prefer an HTTP or log condition where possible.
+    Port(String),
+
+    /// Once a GET request for a path on a published port returns a status.
+    Http {
+        port: String,
+        path: String,
+        status: StatusCode
+    },
+
+    /// Once all of the conditions are met.
+    All(Vec<Readiness>)
+}
+
+impl Readiness {
+
+    pub fn all(conditions: Vec<Readiness>) -> Self {
+        Self::All(conditions)
+    }
+
+    pub fn http<P: Into<String>, U: Into<String>>(port: P, path: U, status: StatusCode) -> Self {
+        Self::Http {
+            port: port.into(),
+            path: path.into(),
+            status
+        }
+    }
+
+    pub fn port<P: Into<String>>(port: P) -> Self {
+        Self::Port(port.into())
+    }
+
+}
+
+impl Display for Readiness {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Running =>
+                write!(f, "running"),
+            Self::LogContains(text) =>
+                write!(f, "log contains {:?}", text),
+            Self::LogMatches(pattern) =>
+                write!(f, "log matches /{}/", pattern),
+            Self::Healthy =>
+                write!(f, "healthy"),
+            Self::Port(port) =>
+                write!(f, "port {} accepts connections", port),
+            Self::Http { port, path, status } =>
+                write!(f, "GET {} on port {} returns {}", path, port, status.as_u16()),
+            Self::All(conditions) => {
+                let described: Vec<String> = conditions
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect();
+
+                write!(f, "{}", described.join(" and "))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_readiness {
+
+    mod display {
+        use hyper::StatusCode;
+        use regex::Regex;
+
+        use crate::model::Readiness;
+
+        #[test]
+        fn all() {
+            let readiness = Readiness::all(vec![
+                Readiness::Healthy,
+                Readiness::LogMatches(Regex::new("^ready$").unwrap()),
+                Readiness::http("80", "/", StatusCode::NO_CONTENT)
+            ]);
+
+            assert_eq!("healthy and log matches /^ready$/ and GET / on port 80 returns 204", readiness.to_string());
+        }
+
+        #[test]
+        fn log_contains() {
+            assert_eq!("log contains \"started\"", Readiness::LogContains("started".into()).to_string());
+        }
+
+    }
+
+}
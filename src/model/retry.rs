@@ -0,0 +1,161 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// How to retry idempotent requests (GET and HEAD, such as inspect and list) that fail
+/// with a connection error or a 5xx response, for example while the Docker Engine restarts.
+///
+/// Other requests are never retried, because they may have taken effect.
+///
+/// Delays grow exponentially from initial_delay, up to max_delay. With jitter, each
+/// delay is a random duration between half and all of the exponential delay.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use passivized_docker_engine_client::DockerEngineClient;
+/// use passivized_docker_engine_client::errors::DecCreateError;
+/// use passivized_docker_engine_client::model::RetryPolicy;
+///
+/// fn example() -> Result<(), DecCreateError> {
+///     let dec = DockerEngineClient::builder()
+///         .retry_policy(
+///             RetryPolicy::default()
+///                 .max_retries(5)
+///                 .max_delay(Duration::from_secs(10))
+///         )
+///         .build()?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: f64,
+    pub jitter: bool
+}
+
+/// Retry up to 3 times, after delays starting at 100ms and doubling up to 5s, with jitter.
+///
+/// This is not the default of a DockerEngineClient, which does not retry; see RetryPolicy::none.
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            multiplier: 2.0,
+            jitter: true
+        }
+    }
+}
+
+impl RetryPolicy {
+
+    /// Do not retry. DockerEngineClientBuilder uses this unless given another policy,
+    /// so it is the default of a DockerEngineClient.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    pub fn initial_delay(mut self, v: Duration) -> Self {
+        self.initial_delay = v;
+        self
+    }
+
+    pub fn jitter(mut self, v: bool) -> Self {
+        self.jitter = v;
+        self
+    }
+
+    pub fn max_delay(mut self, v: Duration) -> Self {
+        self.max_delay = v;
+        self
+    }
+
+    pub fn max_retries(mut self, v: u32) -> Self {
+        self.max_retries = v;
+        self
+    }
+
+    pub fn multiplier(mut self, v: f64) -> Self {
+        self.multiplier = v;
+        self
+    }
+
+    /// Delay before a retry, where the first retry is retry 0.
+    pub(crate) fn delay(&self, retry: u32) -> Duration {
+        let exponential = self.initial_delay
+            .as_secs_f64() * self.multiplier.max(1.0).powi(retry.min(i32::MAX as u32) as i32);
+
+        let capped = Duration::from_secs_f64(exponential.min(self.max_delay.as_secs_f64()));
+
+        if self.jitter {
+            // Equal jitter
+            capped.mul_f64(0.5 + random_fraction() / 2.0)
+        }
+        else {
+            capped
+        }
+    }
+
+}
+
+/// A random number from 0.0 up to, but not including, 1.0, with a precision of 0.001.
+///
+/// Each RandomState has different keys, starting from a random seed, so hashing nothing
+/// with a new one gives a different number each time. That is enough to spread out retries, without depending on a random
+/// number generator crate; it is not suitable where unpredictability matters.
+fn random_fraction() -> f64 {
+    (RandomState::new().build_hasher().finish() % 1_000) as f64 / 1_000.0
+}
+
+#[cfg(test)]
+mod test_retry_policy {
+
+    mod delay {
+        use std::time::Duration;
+
+        use crate::model::RetryPolicy;
+
+        #[test]
+        fn grows_exponentially() {
+            let policy = RetryPolicy::default()
+                .jitter(false);
+
+            assert_eq!(Duration::from_millis(100), policy.delay(0));
+            assert_eq!(Duration::from_millis(200), policy.delay(1));
+            assert_eq!(Duration::from_millis(400), policy.delay(2));
+        }
+
+        #[test]
+        fn capped_at_max_delay() {
+            let policy = RetryPolicy::default()
+                .jitter(false)
+                .max_delay(Duration::from_millis(300));
+
+            assert_eq!(Duration::from_millis(300), policy.delay(2));
+            assert_eq!(Duration::from_millis(300), policy.delay(1_000));
+        }
+
+        #[test]
+        fn jitter_within_upper_half() {
+            let policy = RetryPolicy::default();
+
+            for _ in 0..100 {
+                let actual = policy.delay(1);
+
+                assert!(actual >= Duration::from_millis(100), "{:?}", actual);
+                assert!(actual <= Duration::from_millis(200), "{:?}", actual);
+            }
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use std::path::{Path, PathBuf};
+#[cfg(feature = "rustls")]
+use std::sync::Arc;
+
+use crate::errors::DecCreateError;
+use crate::imp::tls::tls_config;
+
+/// TLS client configuration, for one of the TLS implementations enabled by
+/// the crate's cargo features: native-tls (the default) or rustls.
+#[derive(Clone, Debug)]
+pub enum TlsConfig {
+    #[cfg(feature = "native-tls")]
+    NativeTls(hyper_tls::native_tls::TlsConnector),
+
+    #[cfg(feature = "rustls")]
+    Rustls(Arc<rustls::ClientConfig>),
+}
+
+#[cfg(feature = "native-tls")]
+impl From<hyper_tls::native_tls::TlsConnector> for TlsConfig {
+    fn from(other: hyper_tls::native_tls::TlsConnector) -> Self {
+        TlsConfig::NativeTls(other)
+    }
+}
+
+#[cfg(feature = "rustls")]
+impl From<rustls::ClientConfig> for TlsConfig {
+    fn from(other: rustls::ClientConfig) -> Self {
+        TlsConfig::Rustls(Arc::new(other))
+    }
+}
+
+#[cfg(feature = "rustls")]
+impl From<Arc<rustls::ClientConfig>> for TlsConfig {
+    fn from(other: Arc<rustls::ClientConfig>) -> Self {
+        TlsConfig::Rustls(other)
+    }
+}
+
+/// PEM files for connecting to a TLS-secured Docker Engine, optionally with
+/// a client certificate for mutual TLS.
+///
+/// # Example
+///
+/// ```rust
+/// use passivized_docker_engine_client::DockerEngineClient;
+/// use passivized_docker_engine_client::errors::DecCreateError;
+/// use passivized_docker_engine_client::model::TlsPemFiles;
+///
+/// fn example() -> Result<(), DecCreateError> {
+///     let files = TlsPemFiles::default()
+///         .ca("/etc/engine-certs/ca.pem")
+///         .cert("/etc/engine-certs/client-cert.pem")
+///         .key("/etc/engine-certs/client-key.pem");
+///
+///     let dec = DockerEngineClient::with_tls_files("tcp://builder:2376", files)?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TlsPemFiles {
+    pub ca: Option<PathBuf>,
+    pub cert: Option<PathBuf>,
+    pub key: Option<PathBuf>,
+
+    /// Verify the Docker Engine's certificate. Defaults to true.
+    pub verify: bool
+}
+
+impl Default for TlsPemFiles {
+    fn default() -> Self {
+        Self {
+            ca: None,
+            cert: None,
+            key: None,
+            verify: true
+        }
+    }
+}
+
+impl TlsPemFiles {
+
+    /// Use ca.pem, cert.pem, and key.pem from a directory, the same as the
+    /// docker cli does for DOCKER_CERT_PATH. Files that do not exist are skipped,
+    /// but cert.pem and key.pem must either both exist or both be missing.
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Self, DecCreateError> {
+        let existing = |name: &str| Some(dir.as_ref().join(name))
+            .filter(|path| path.is_file());
+
+        let files = Self {
+            ca: existing("ca.pem"),
+            cert: existing("cert.pem"),
+            key: existing("key.pem"),
+            verify: true
+        };
+
+        files.check_client_identity()?;
+
+        Ok(files)
+    }
+
+    /// Certificate authority that signed the Docker Engine's certificate.
+    pub fn ca<P: Into<PathBuf>>(mut self, v: P) -> Self {
+        self.ca = Some(v.into());
+        self
+    }
+
+    /// Client certificate. Requires a key.
+    pub fn cert<P: Into<PathBuf>>(mut self, v: P) -> Self {
+        self.cert = Some(v.into());
+        self
+    }
+
+    /// Client private key, in PKCS #8 or PKCS #1 (RSA) format. Requires a cert.
+    pub fn key<P: Into<PathBuf>>(mut self, v: P) -> Self {
+        self.key = Some(v.into());
+        self
+    }
+
+    pub fn verify(mut self, v: bool) -> Self {
+        self.verify = v;
+        self
+    }
+
+    fn read(path: &Option<PathBuf>) -> Result<Option<Vec<u8>>, DecCreateError> {
+        match path {
+            None => Ok(None),
+            Some(p) => std::fs::read(p)
+                .map(Some)
+                .map_err(|e| DecCreateError::TlsFile {
+                    path: p.display().to_string(),
+                    message: e.to_string()
+                })
+        }
+    }
+
+    /// A client certificate is useless without its key, and the reverse.
+    fn check_client_identity(&self) -> Result<(), DecCreateError> {
+        match (&self.cert, &self.key) {
+            (Some(cert), None) =>
+                Err(DecCreateError::TlsClientIdentity(format!("certificate {} has no key", cert.display()))),
+            (None, Some(key)) =>
+                Err(DecCreateError::TlsClientIdentity(format!("key {} has no certificate", key.display()))),
+            _ =>
+                Ok(())
+        }
+    }
+
+    pub(crate) fn config(&self) -> Result<TlsConfig, DecCreateError> {
+        self.check_client_identity()?;
+
+        let ca = Self::read(&self.ca)?;
+        let cert = Self::read(&self.cert)?;
+        let key = Self::read(&self.key)?;
+
+        let cert_and_key = match (&cert, &key) {
+            (Some(c), Some(k)) => Some((c.as_slice(), k.as_slice())),
+            _ => None
+        };
+
+        tls_config(ca.as_deref(), cert_and_key, self.verify)
+    }
+}
+
+#[cfg(test)]
+mod test_tls_pem_files {
+    use crate::errors::DecCreateError;
+    use super::TlsPemFiles;
+
+    #[test]
+    fn from_dir_skips_missing_files() {
+        let dir = tempfile::tempdir()
+            .unwrap();
+
+        std::fs::write(dir.path().join("ca.pem"), "CA")
+            .unwrap();
+
+        let actual = TlsPemFiles::from_dir(dir.path())
+            .unwrap();
+
+        assert_eq!(
+            TlsPemFiles::default()
+                .ca(dir.path().join("ca.pem")),
+            actual
+        );
+    }
+
+    #[test]
+    fn connector_fails_when_file_missing() {
+        let dir = tempfile::tempdir()
+            .unwrap();
+
+        let path = dir.path().join("missing.pem");
+
+        let actual = TlsPemFiles::default()
+            .ca(&path)
+            .config()
+            .unwrap_err();
+
+        assert!(actual.to_string().starts_with(&format!("Failed to read TLS file {}", path.display())));
+    }
+
+    #[test]
+    fn from_dir_fails_with_cert_without_key() {
+        let dir = tempfile::tempdir()
+            .unwrap();
+
+        std::fs::write(dir.path().join("cert.pem"), "CERT")
+            .unwrap();
+
+        let actual = TlsPemFiles::from_dir(dir.path())
+            .unwrap_err();
+
+        assert!(matches!(actual, DecCreateError::TlsClientIdentity(_)), "Unexpected error: {}", actual);
+    }
+
+    #[test]
+    fn config_fails_with_cert_without_key() {
+        let actual = TlsPemFiles::default()
+            .cert("cert.pem")
+            .config()
+            .unwrap_err();
+
+        assert!(matches!(actual, DecCreateError::TlsClientIdentity(_)), "Unexpected error: {}", actual);
+    }
+
+    #[test]
+    fn config_fails_with_key_without_cert() {
+        let actual = TlsPemFiles::default()
+            .key("key.pem")
+            .config()
+            .unwrap_err();
+
+        assert!(matches!(actual, DecCreateError::TlsClientIdentity(_)), "Unexpected error: {}", actual);
+    }
+}
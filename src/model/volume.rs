@@ -1,15 +1,18 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 
-use crate::imp::serde::dz_hashmap;
+use crate::imp::serde::{dz_hashmap, dz_vec};
+use crate::imp::timestamp::parse_engine_time;
+use crate::model::VolumeName;
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Volume/operation/VolumeInspect
 /// and https://docs.docker.com/engine/api/v1.41/#tag/Volume/operation/VolumeList
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Volume {
     #[serde(rename = "Name")]
-    pub name: String,
+    pub name: VolumeName,
 
     #[serde(rename = "Driver")]
     pub driver: String,
@@ -34,10 +37,22 @@ pub struct Volume {
 
     #[serde(rename = "UsageData")]
     pub usage_data: Option<VolumeUsage>,
+
+    #[serde(rename = "ClusterVolume", default)]
+    pub cluster_volume: Option<ClusterVolume>,
+}
+
+impl Volume {
+
+    /// When the volume was created, parsed from created_at.
+    pub fn created_time(&self) -> Option<OffsetDateTime> {
+        parse_engine_time(&self.created_at)
+    }
+
 }
 
 // https://docs.docker.com/engine/api/v1.41/#tag/Volume/operation/VolumeInspect
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct VolumeUsage {
     // Warning - based on the above linked docs, this value can be negative. Do not retype as u64
     #[serde(rename = "Size")]
@@ -47,3 +62,170 @@ pub struct VolumeUsage {
     #[serde(rename = "RefCount")]
     pub ref_count: i64,
 }
+
+/// Swarm cluster volume details. Only present for volumes created with a
+/// cluster volume spec, on a Docker Engine that is a swarm manager.
+///
+/// Requires Docker Engine api 1.42 or newer.
+///
+/// See https://docs.docker.com/engine/api/v1.42/#tag/Volume/operation/VolumeInspect
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ClusterVolume {
+    #[serde(rename = "ID")]
+    pub id: String,
+
+    #[serde(rename = "Version")]
+    pub version: ClusterVolumeVersion,
+
+    #[serde(rename = "CreatedAt", default)]
+    pub created_at: String,
+
+    #[serde(rename = "UpdatedAt", default)]
+    pub updated_at: String,
+
+    #[serde(rename = "Spec")]
+    pub spec: ClusterVolumeSpec,
+}
+
+impl ClusterVolume {
+
+    /// When the cluster volume was created, parsed from created_at.
+    pub fn created_time(&self) -> Option<OffsetDateTime> {
+        parse_engine_time(&self.created_at)
+    }
+
+    /// When the cluster volume was last updated, parsed from updated_at.
+    pub fn updated_time(&self) -> Option<OffsetDateTime> {
+        parse_engine_time(&self.updated_at)
+    }
+
+}
+
+/// See https://docs.docker.com/engine/api/v1.42/#tag/Volume/operation/VolumeInspect
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ClusterVolumeVersion {
+    /// Must be sent back when updating the volume, to detect conflicting updates.
+    #[serde(rename = "Index")]
+    pub index: u64,
+}
+
+/// Cluster-specific options used to create or update a cluster volume.
+///
+/// See https://docs.docker.com/engine/api/v1.42/#tag/Volume/operation/VolumeUpdate
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ClusterVolumeSpec {
+    /// Volumes in the same group are scheduled together.
+    #[serde(rename = "Group", default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+
+    #[serde(rename = "AccessMode", default, skip_serializing_if = "Option::is_none")]
+    pub access_mode: Option<ClusterVolumeAccessMode>,
+}
+
+impl ClusterVolumeSpec {
+
+    pub fn group<V: Into<String>>(mut self, v: V) -> Self {
+        self.group = Some(v.into());
+        self
+    }
+
+    pub fn access_mode(mut self, v: ClusterVolumeAccessMode) -> Self {
+        self.access_mode = Some(v);
+        self
+    }
+
+}
+
+/// See https://docs.docker.com/engine/api/v1.42/#tag/Volume/operation/VolumeUpdate
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ClusterVolumeAccessMode {
+    /// Either "single" (one node at a time) or "multi".
+    #[serde(rename = "Scope", default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+
+    /// One of "none", "readonly", "onewriter", or "all".
+    #[serde(rename = "Sharing", default, skip_serializing_if = "Option::is_none")]
+    pub sharing: Option<String>,
+
+    #[serde(rename = "MountVolume", default, skip_serializing_if = "Option::is_none")]
+    pub mount_volume: Option<ClusterVolumeMount>,
+
+    #[serde(rename = "Secrets", default, deserialize_with = "dz_vec", skip_serializing_if = "Vec::is_empty")]
+    pub secrets: Vec<ClusterVolumeSecret>,
+
+    #[serde(rename = "CapacityRange", default, skip_serializing_if = "Option::is_none")]
+    pub capacity_range: Option<ClusterVolumeCapacityRange>,
+
+    /// One of "active", "pause", or "drain". Only this field, and secrets, may be
+    /// changed when updating an existing volume.
+    #[serde(rename = "Availability", default, skip_serializing_if = "Option::is_none")]
+    pub availability: Option<String>,
+}
+
+impl ClusterVolumeAccessMode {
+
+    pub fn scope<V: Into<String>>(mut self, v: V) -> Self {
+        self.scope = Some(v.into());
+        self
+    }
+
+    pub fn sharing<V: Into<String>>(mut self, v: V) -> Self {
+        self.sharing = Some(v.into());
+        self
+    }
+
+    pub fn mount_volume(mut self, v: ClusterVolumeMount) -> Self {
+        self.mount_volume = Some(v);
+        self
+    }
+
+    /// Add a secret to pass to the volume's CSI plugin. Can be called multiple times.
+    pub fn secret<K: Into<String>, S: Into<String>>(mut self, key: K, secret: S) -> Self {
+        self.secrets.push(ClusterVolumeSecret {
+            key: key.into(),
+            secret: secret.into()
+        });
+        self
+    }
+
+    pub fn capacity_range(mut self, v: ClusterVolumeCapacityRange) -> Self {
+        self.capacity_range = Some(v);
+        self
+    }
+
+    pub fn availability<V: Into<String>>(mut self, v: V) -> Self {
+        self.availability = Some(v.into());
+        self
+    }
+
+}
+
+/// Use the cluster volume as a mounted file system, rather than as a block device.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ClusterVolumeMount {
+    #[serde(rename = "FsType", default, skip_serializing_if = "Option::is_none")]
+    pub fs_type: Option<String>,
+
+    #[serde(rename = "MountFlags", default, deserialize_with = "dz_vec", skip_serializing_if = "Vec::is_empty")]
+    pub mount_flags: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ClusterVolumeSecret {
+    /// Name of the key of the key/value pair passed to the CSI plugin.
+    #[serde(rename = "Key")]
+    pub key: String,
+
+    /// Name of the swarm secret whose value is passed to the CSI plugin.
+    #[serde(rename = "Secret")]
+    pub secret: String,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ClusterVolumeCapacityRange {
+    #[serde(rename = "RequiredBytes", default, skip_serializing_if = "Option::is_none")]
+    pub required_bytes: Option<i64>,
+
+    #[serde(rename = "LimitBytes", default, skip_serializing_if = "Option::is_none")]
+    pub limit_bytes: Option<i64>,
+}
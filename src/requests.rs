@@ -1,4 +1,7 @@
 mod build_image;
+mod commit;
+mod connect_network;
+mod container_config;
 mod create_container;
 mod create_exec;
 mod create_image;
@@ -10,9 +13,15 @@ mod inspect_network;
 mod list_containers;
 mod logs;
 mod remove_container;
+mod run_container;
+mod search_images;
+mod update_volume;
 mod wait;
 
 pub use build_image::*;
+pub use commit::*;
+pub use connect_network::*;
+pub use container_config::*;
 pub use create_container::*;
 pub use create_exec::*;
 pub use create_image::*;
@@ -24,4 +33,7 @@ pub use inspect_network::*;
 pub use list_containers::*;
 pub(crate) use logs::*;
 pub use remove_container::*;
+pub use run_container::*;
+pub use search_images::*;
+pub use update_volume::*;
 pub use wait::*;
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::model::Platform;
+
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Image/operation/ImageBuild
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct BuildImageRequest {
@@ -57,7 +59,8 @@ pub struct BuildImageRequest {
 
     pub network_mode: Option<String>,
 
-    pub platform: Option<String>,
+    /// Platform of the built image, if different than the platform of the Docker Engine.
+    pub platform: Option<Platform>,
 
     pub target: Option<String>,
 
@@ -180,8 +183,8 @@ impl BuildImageRequest {
         self
     }
 
-    pub fn platform<V: Into<String>>(mut self, v: V) -> Self {
-        self.platform = Some(v.into());
+    pub fn platform(mut self, v: Platform) -> Self {
+        self.platform = Some(v);
         self
     }
 
@@ -0,0 +1,84 @@
+use crate::requests::ContainerConfig;
+
+/// Create a new image from a container.
+///
+/// See https://docs.docker.com/engine/api/v1.41/#tag/Image/operation/ImageCommit
+#[derive(Clone, Debug, Default)]
+pub struct CommitRequest {
+    pub repo: Option<String>,
+    pub tag: Option<String>,
+    pub comment: Option<String>,
+    pub author: Option<String>,
+
+    /// Whether to pause the container while committing. Docker Engine defaults to true.
+    pub pause: Option<bool>,
+
+    /// Dockerfile instructions to apply to the new image, e.g. "CMD [\"postgres\"]"
+    pub changes: Vec<String>,
+
+    /// Container configuration of the new image, overriding the configuration
+    /// of the container being committed.
+    pub config: Option<ContainerConfig>
+}
+
+impl CommitRequest {
+
+    pub fn repo<V>(mut self, v: V) -> Self
+        where V: Into<String>
+    {
+        self.repo = Some(v.into());
+        self
+    }
+
+    pub fn tag<V>(mut self, v: V) -> Self
+        where V: Into<String>
+    {
+        self.tag = Some(v.into());
+        self
+    }
+
+    pub fn comment<V>(mut self, v: V) -> Self
+        where V: Into<String>
+    {
+        self.comment = Some(v.into());
+        self
+    }
+
+    pub fn author<V>(mut self, v: V) -> Self
+        where V: Into<String>
+    {
+        self.author = Some(v.into());
+        self
+    }
+
+    pub fn pause(mut self, v: bool) -> Self {
+        self.pause = Some(v);
+        self
+    }
+
+    /// Add a Dockerfile instruction to apply to the new image.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::requests::CommitRequest;
+    ///
+    /// let request = CommitRequest::default()
+    ///     .repo("seeded-db")
+    ///     .tag("latest")
+    ///     .change("ENV SEEDED=true")
+    ///     .change("EXPOSE 5432");
+    /// ```
+    pub fn change<V: Into<String>>(mut self, v: V) -> Self {
+        self.changes.push(v.into());
+        self
+    }
+
+    /// Set the container configuration of the new image. A CreateContainerRequest
+    /// is accepted too; its host and networking configuration are not sent.
+    pub fn config<V: Into<ContainerConfig>>(mut self, v: V) -> Self {
+        self.config = Some(v.into());
+        self
+    }
+
+}
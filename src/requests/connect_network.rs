@@ -0,0 +1,30 @@
+use serde::Serialize;
+
+use crate::requests::EndpointConfig;
+
+/// See https://docs.docker.com/engine/api/v1.41/#tag/Network/operation/NetworkConnect
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ConnectNetworkRequest {
+
+    #[serde(rename = "Container")]
+    pub container: String,
+
+    #[serde(rename = "EndpointConfig", skip_serializing_if = "Option::is_none")]
+    pub endpoint_config: Option<EndpointConfig>
+
+}
+
+impl ConnectNetworkRequest {
+
+    /// ID or name of the container to connect.
+    pub fn container<V: Into<String>>(mut self, v: V) -> Self {
+        self.container = v.into();
+        self
+    }
+
+    pub fn endpoint_config(mut self, v: EndpointConfig) -> Self {
+        self.endpoint_config = Some(v);
+        self
+    }
+
+}
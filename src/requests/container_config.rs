@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::model::{HealthCheck, Unit};
+use crate::requests::CreateContainerRequest;
+
+/// Container configuration of an image, without the host and networking
+/// configuration of a container.
+///
+/// See https://docs.docker.com/engine/api/v1.41/#tag/Image/operation/ImageCommit
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ContainerConfig {
+
+    #[serde(rename = "Hostname", skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+
+    #[serde(rename = "Domainname", skip_serializing_if = "Option::is_none")]
+    pub domain_name: Option<String>,
+
+    #[serde(rename = "User", skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+
+    #[serde(rename = "AttachStdin", skip_serializing_if = "Option::is_none")]
+    pub attach_stdin: Option<bool>,
+
+    #[serde(rename = "AttachStdout", skip_serializing_if = "Option::is_none")]
+    pub attach_stdout: Option<bool>,
+
+    #[serde(rename = "AttachStderr", skip_serializing_if = "Option::is_none")]
+    pub attach_stderr: Option<bool>,
+
+    #[serde(rename = "ExposedPorts", skip_serializing_if = "HashMap::is_empty")]
+    pub exposed_ports: HashMap<String, Unit>,
+
+    #[serde(rename = "Tty", skip_serializing_if = "Option::is_none")]
+    pub tty: Option<bool>,
+
+    #[serde(rename = "OpenStdin", skip_serializing_if = "Option::is_none")]
+    pub open_stdin: Option<bool>,
+
+    #[serde(rename = "StdinOnce", skip_serializing_if = "Option::is_none")]
+    pub stdin_once: Option<bool>,
+
+    #[serde(rename = "Env", skip_serializing_if = "Vec::is_empty")]
+    pub env: Vec<String>,
+
+    #[serde(rename = "Cmd", skip_serializing_if = "Vec::is_empty")]
+    pub cmd: Vec<String>,
+
+    #[serde(rename = "Healthcheck", skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheck>,
+
+    #[serde(rename = "ArgsEscaped", skip_serializing_if = "Option::is_none")]
+    pub args_escaped: Option<bool>,
+
+    #[serde(rename = "Image", skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+
+    #[serde(rename = "Volumes", skip_serializing_if = "HashMap::is_empty")]
+    pub volumes: HashMap<String, Unit>,
+
+    #[serde(rename = "WorkingDir", skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+
+    #[serde(rename = "Entrypoint", skip_serializing_if = "Vec::is_empty")]
+    pub entry_point: Vec<String>,
+
+    #[serde(rename = "NetworkDisabled", skip_serializing_if = "Option::is_none")]
+    pub network_disabled: Option<bool>,
+
+    #[serde(rename = "MacAddress", skip_serializing_if = "Option::is_none")]
+    pub mac_address: Option<String>,
+
+    #[serde(rename = "OnBuild", skip_serializing_if = "Vec::is_empty")]
+    pub on_build: Vec<String>,
+
+    #[serde(rename = "Labels", skip_serializing_if = "HashMap::is_empty")]
+    pub labels: HashMap<String, String>,
+
+    #[serde(rename = "StopSignal", skip_serializing_if = "Option::is_none")]
+    pub stop_signal: Option<String>,
+
+    #[serde(rename = "StopTimeout", skip_serializing_if = "Option::is_none")]
+    pub stop_timeout_seconds: Option<u64>,
+
+    #[serde(rename = "Shell", skip_serializing_if = "Vec::is_empty")]
+    pub shell: Vec<String>,
+}
+
+impl ContainerConfig {
+
+    pub fn user<V>(mut self, v: V) -> Self
+        where V: Into<String>
+    {
+        self.user = Some(v.into());
+        self
+    }
+
+    pub fn expose_port<V>(mut self, v: V) -> Self
+        where V: Into<String>
+    {
+        self.exposed_ports.insert(v.into(), Unit {});
+        self
+    }
+
+    /// Append environment variable for the image.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::requests::ContainerConfig;
+    ///
+    /// let config = ContainerConfig::default()
+    ///     .env("SEEDED=true");
+    /// ```
+    pub fn env<V>(mut self, v: V) -> Self
+        where V: Into<String>
+    {
+        self.env.push(v.into());
+        self
+    }
+
+    pub fn cmd<V: ToString>(mut self, v: Vec<V>) -> Self {
+        self.cmd = v
+            .iter()
+            .map(|item| item.to_string())
+            .collect();
+        self
+    }
+
+    pub fn health_check(mut self, v: HealthCheck) -> Self {
+        self.health_check = Some(v);
+        self
+    }
+
+    pub fn volume<V>(mut self, v: V) -> Self
+        where V: Into<String>
+    {
+        self.volumes.insert(v.into(), Unit {});
+        self
+    }
+
+    pub fn working_dir<V>(mut self, v: V) -> Self
+        where V: Into<String>
+    {
+        self.working_dir = Some(v.into());
+        self
+    }
+
+    pub fn entry_point<V>(mut self, v: Vec<V>) -> Self
+        where V: ToString
+    {
+        self.entry_point = v
+            .iter()
+            .map(|item| item.to_string())
+            .collect();
+        self
+    }
+
+    pub fn label<K, V>(mut self, k: K, v: V) -> Self
+        where
+            K: Into<String>,
+            V: Into<String>
+    {
+        self.labels.insert(k.into(), v.into());
+        self
+    }
+
+    pub fn stop_signal<V>(mut self, v: V) -> Self
+        where V: Into<String>
+    {
+        self.stop_signal = Some(v.into());
+        self
+    }
+
+    pub fn stop_timeout(mut self, v: Duration) -> Self {
+        self.stop_timeout_seconds = Some(v.as_secs());
+        self
+    }
+
+}
+
+/// Keep the container configuration of a request, dropping its name, platform,
+/// host configuration and networking configuration.
+impl From<CreateContainerRequest> for ContainerConfig {
+    fn from(other: CreateContainerRequest) -> Self {
+        Self {
+            hostname: other.hostname,
+            domain_name: other.domain_name,
+            user: other.user,
+            attach_stdin: other.attach_stdin,
+            attach_stdout: other.attach_stdout,
+            attach_stderr: other.attach_stderr,
+            exposed_ports: other.exposed_ports,
+            tty: other.tty,
+            open_stdin: other.open_stdin,
+            stdin_once: other.stdin_once,
+            env: other.env,
+            cmd: other.cmd,
+            health_check: other.health_check,
+            args_escaped: other.args_escaped,
+            image: other.image,
+            volumes: other.volumes,
+            working_dir: other.working_dir,
+            entry_point: other.entry_point,
+            network_disabled: other.network_disabled,
+            mac_address: other.mac_address,
+            on_build: other.on_build,
+            labels: other.labels,
+            stop_signal: other.stop_signal,
+            stop_timeout_seconds: other.stop_timeout_seconds,
+            shell: other.shell
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_container_config {
+
+    mod from_create_container_request {
+        use crate::requests::{ContainerConfig, CreateContainerRequest, HostConfig};
+
+        #[test]
+        fn drops_host_and_networking_config() {
+            let request = CreateContainerRequest::default()
+                .name("web")
+                .image("nginx")
+                .env("A=1")
+                .host_config(HostConfig::default().auto_remove());
+
+            let actual = serde_json::to_value(ContainerConfig::from(request))
+                .unwrap();
+
+            assert_eq!(serde_json::json!({ "Image": "nginx", "Env": ["A=1"] }), actual);
+        }
+    }
+}
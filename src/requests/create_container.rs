@@ -4,7 +4,7 @@ use std::time::Duration;
 
 use serde::Serialize;
 
-use crate::model::{HealthCheck, ContainerIpamConfig, Unit, MountMode, PortBinding};
+use crate::model::{HealthCheck, ContainerIpamConfig, Unit, MountMode, Platform, PortBinding};
 
 // See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerCreate
 #[derive(Clone, Debug, Default, Serialize)]
@@ -14,6 +14,10 @@ pub struct CreateContainerRequest {
     #[serde(skip_serializing)]
     pub name: Option<String>,
 
+    // Provided via URL query string, not via POST request body.
+    #[serde(skip_serializing)]
+    pub platform: Option<Platform>,
+
     #[serde(rename = "Hostname", skip_serializing_if = "Option::is_none")]
     pub hostname: Option<String>,
 
@@ -103,6 +107,24 @@ impl CreateContainerRequest {
         self
     }
 
+    /// Platform of the image to create the container from. When set, the
+    /// image must already be present in the Docker Engine for that platform.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::model::Platform;
+    /// use passivized_docker_engine_client::requests::CreateContainerRequest;
+    ///
+    /// let config = CreateContainerRequest::default()
+    ///     .image("busybox")
+    ///     .platform(Platform::new("linux", "arm64"));
+    /// ```
+    pub fn platform(mut self, v: Platform) -> Self {
+        self.platform = Some(v);
+        self
+    }
+
     pub fn host_config(mut self, v: HostConfig) -> Self {
         self.host_config = Some(v);
         self
@@ -412,7 +434,20 @@ impl CreateContainerRequest {
 pub struct EndpointConfig {
 
     #[serde(rename = "IPAMConfig")]
-    pub ipam_config: ContainerIpamConfig
+    pub ipam_config: ContainerIpamConfig,
+
+    /// Additional names, such as a service name, that other containers on the network can use.
+    #[serde(rename = "Aliases", skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>
+
+}
+
+impl EndpointConfig {
+
+    pub fn alias<V: Into<String>>(mut self, v: V) -> Self {
+        self.aliases.push(v.into());
+        self
+    }
 
 }
 
@@ -420,7 +455,8 @@ impl From<Ipv4Addr> for EndpointConfig {
 
     fn from(value: Ipv4Addr) -> EndpointConfig {
         EndpointConfig {
-            ipam_config: value.into()
+            ipam_config: value.into(),
+            aliases: Vec::new()
         }
     }
 
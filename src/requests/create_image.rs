@@ -1,4 +1,6 @@
 
+use crate::model::Platform;
+
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Image/operation/ImageCreate
 #[derive(Clone, Default)]
 pub struct CreateImageRequest {
@@ -7,7 +9,7 @@ pub struct CreateImageRequest {
     pub repo: Option<String>,
     pub tag: Option<String>,
     pub message: Option<String>,
-    pub platform: Option<String>
+    pub platform: Option<Platform>
 }
 
 impl CreateImageRequest {
@@ -49,10 +51,9 @@ impl CreateImageRequest {
         self
     }
 
-    pub fn platform<V>(mut self, v: V) -> Self
-        where V: Into<String>
-    {
-        self.platform = Some(v.into());
+    /// Pull the image for a specific platform, instead of the platform of the Docker Engine.
+    pub fn platform(mut self, v: Platform) -> Self {
+        self.platform = Some(v);
         self
     }
 
@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use serde::Serialize;
 
+use crate::model::ClusterVolumeSpec;
+
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Volume/operation/VolumeCreate
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct CreateVolumeRequest {
@@ -16,6 +18,10 @@ pub struct CreateVolumeRequest {
 
     #[serde(rename = "Labels", skip_serializing_if = "HashMap::is_empty")]
     pub labels: HashMap<String, String>,
+    /// Requires a swarm manager. When set, the volume is created with Docker Engine api version 1.42.
+    /// Requires Docker Engine api 1.42 or newer, and a swarm manager.
+    #[serde(rename = "ClusterVolumeSpec", skip_serializing_if = "Option::is_none")]
+    pub cluster_volume_spec: Option<ClusterVolumeSpec>,
 }
 
 impl CreateVolumeRequest {
@@ -54,4 +60,144 @@ impl CreateVolumeRequest {
         self
     }
 
-}
\ No newline at end of file
+    /// Use the "local" volume driver, with options for what it mounts.
+    ///
+    /// Replaces the "type", "o", and "device" driver options.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use passivized_docker_engine_client::requests::{CreateVolumeRequest, LocalVolumeOpts};
+    ///
+    /// let request = CreateVolumeRequest::default()
+    ///     .name("scratch")
+    ///     .local(LocalVolumeOpts::tmpfs().option("size=100m"));
+    /// ```
+    pub fn local(mut self, v: LocalVolumeOpts) -> Self {
+        self.driver = Some("local".into());
+        self.driver_opts.insert("type".into(), v.mount_type);
+        self.driver_opts.insert("device".into(), v.device);
+
+        if v.options.is_empty() {
+            self.driver_opts.remove("o");
+        }
+        else {
+            self.driver_opts.insert("o".into(), v.options.join(","));
+        }
+
+        self
+    }
+
+    pub fn cluster_volume_spec(mut self, v: ClusterVolumeSpec) -> Self {
+        self.cluster_volume_spec = Some(v);
+        self
+    }
+
+}
+
+/// Driver options for the "local" volume driver, which mounts a file system
+/// the same way the Linux mount command would.
+///
+/// See https://docs.docker.com/engine/reference/commandline/volume_create/#driver-specific-options
+#[derive(Clone, Debug)]
+pub struct LocalVolumeOpts {
+    mount_type: String,
+    device: String,
+    options: Vec<String>
+}
+
+impl LocalVolumeOpts {
+
+    fn new<T: Into<String>, D: Into<String>>(mount_type: T, device: D) -> Self {
+        Self {
+            mount_type: mount_type.into(),
+            device: device.into(),
+            options: Vec::new()
+        }
+    }
+
+    /// Bind mount a directory of the Docker Engine host.
+    pub fn bind<P: Into<String>>(host_path: P) -> Self {
+        Self::new("none", host_path)
+            .option("bind")
+    }
+
+    /// An in-memory file system. Limit its size with an option such as "size=100m".
+    pub fn tmpfs() -> Self {
+        Self::new("tmpfs", "tmpfs")
+    }
+
+    /// An NFS export, such as `LocalVolumeOpts::nfs("10.0.0.10", "/exports/data")`
+    pub fn nfs<A: Into<String>, P: Into<String>>(address: A, remote_path: P) -> Self {
+        Self::new("nfs", format!(":{}", remote_path.into()))
+            .option(format!("addr={}", address.into()))
+    }
+
+    /// A CIFS (SMB) share, such as `LocalVolumeOpts::cifs("fileserver", "backup")`
+    ///
+    /// Credentials can be passed as options, e.g. "username=..." and "password=..."
+    pub fn cifs<A: Into<String>, S: Into<String>>(address: A, share: S) -> Self {
+        let a = address.into();
+        let s = share.into();
+
+        Self::new("cifs", format!("//{}/{}", a, s.trim_start_matches('/')))
+            .option(format!("addr={}", a))
+    }
+
+    /// Add a mount option, such as "rw", "nfsvers=4", or "size=100m". Can be called multiple times.
+    pub fn option<O: Into<String>>(mut self, v: O) -> Self {
+        self.options.push(v.into());
+        self
+    }
+
+}
+
+#[cfg(test)]
+mod test_create_volume_request {
+
+    mod local {
+        use std::collections::HashMap;
+
+        use super::super::{CreateVolumeRequest, LocalVolumeOpts};
+
+        fn opts(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        }
+
+        #[test]
+        fn bind() {
+            let actual = CreateVolumeRequest::default()
+                .local(LocalVolumeOpts::bind("/srv/data"));
+
+            assert_eq!(Some("local".to_string()), actual.driver);
+            assert_eq!(opts(&[("type", "none"), ("o", "bind"), ("device", "/srv/data")]), actual.driver_opts);
+        }
+
+        #[test]
+        fn tmpfs_without_options() {
+            let actual = CreateVolumeRequest::default()
+                .local(LocalVolumeOpts::tmpfs());
+
+            assert_eq!(opts(&[("type", "tmpfs"), ("device", "tmpfs")]), actual.driver_opts);
+        }
+
+        #[test]
+        fn nfs() {
+            let actual = CreateVolumeRequest::default()
+                .local(LocalVolumeOpts::nfs("10.0.0.10", "/exports/data").option("rw").option("nfsvers=4"));
+
+            assert_eq!(opts(&[("type", "nfs"), ("o", "addr=10.0.0.10,rw,nfsvers=4"), ("device", ":/exports/data")]), actual.driver_opts);
+        }
+
+        #[test]
+        fn cifs() {
+            let actual = CreateVolumeRequest::default()
+                .local(LocalVolumeOpts::cifs("fileserver", "/backup").option("username=u"));
+
+            assert_eq!(opts(&[("type", "cifs"), ("o", "addr=fileserver,username=u"), ("device", "//fileserver/backup")]), actual.driver_opts);
+        }
+    }
+}
@@ -1,8 +1,12 @@
+use time::OffsetDateTime;
 
 pub(crate) struct LogsArgs {
     pub stdout: bool,
     pub stderr: bool,
-    pub timestamps: bool
+    pub timestamps: bool,
+
+    /// Only lines logged at or after this time.
+    pub since: Option<OffsetDateTime>
 }
 
 impl Default for LogsArgs {
@@ -10,7 +14,8 @@ impl Default for LogsArgs {
         Self {
             stdout: true,
             stderr: true,
-            timestamps: Default::default()
+            timestamps: Default::default(),
+            since: None
         }
     }
 }
@@ -22,4 +27,9 @@ impl LogsArgs {
         self
     }
 
+    pub fn since(mut self, v: Option<OffsetDateTime>) -> Self {
+        self.since = v;
+        self
+    }
+
 }
\ No newline at end of file
@@ -0,0 +1,53 @@
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::model::{Cancellation, StreamLine};
+
+/// Options for DecContainers::run.
+#[derive(Clone, Debug)]
+pub struct RunOptions {
+    pub cancellation: Cancellation,
+    pub collect_output: bool,
+    pub output_to: Option<UnboundedSender<StreamLine>>,
+    pub pull: bool
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            cancellation: Cancellation::default(),
+            collect_output: true,
+            output_to: None,
+            pull: true
+        }
+    }
+}
+
+impl RunOptions {
+
+    /// Stop pulling, or waiting for the container to exit, at a deadline or when a token
+    /// is cancelled. The container is removed either way.
+    pub fn cancellation(mut self, v: Cancellation) -> Self {
+        self.cancellation = v;
+        self
+    }
+
+    /// Whether to return the container's stdout and stderr in RunOutput. Defaults to true.
+    pub fn collect_output(mut self, v: bool) -> Self {
+        self.collect_output = v;
+        self
+    }
+
+    /// Send lines of stdout and stderr while the container runs. Lines are read by polling,
+    /// each poll reading only those logged since the previous one, so they arrive in batches.
+    pub fn output_to(mut self, v: UnboundedSender<StreamLine>) -> Self {
+        self.output_to = Some(v);
+        self
+    }
+
+    /// Whether to pull the image if it is not present. Defaults to true.
+    pub fn pull(mut self, v: bool) -> Self {
+        self.pull = v;
+        self
+    }
+
+}
@@ -0,0 +1,82 @@
+use serde::{Serialize, Serializer};
+use serde::ser::SerializeSeq;
+
+/// See https://docs.docker.com/engine/api/v1.41/#tag/Image/operation/ImageSearch
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SearchImagesFilters {
+
+    #[serde(rename = "is-automated", skip_serializing_if = "Option::is_none", serialize_with = "sz_single")]
+    pub is_automated: Option<bool>,
+
+    #[serde(rename = "is-official", skip_serializing_if = "Option::is_none", serialize_with = "sz_single")]
+    pub is_official: Option<bool>,
+
+    /// Minimum number of stars.
+    #[serde(rename = "stars", skip_serializing_if = "Option::is_none", serialize_with = "sz_single")]
+    pub stars: Option<u64>,
+
+}
+
+impl SearchImagesFilters {
+
+    /// Return true if no filters are set.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.is_automated.is_none() && self.is_official.is_none() && self.stars.is_none()
+    }
+
+    pub fn is_automated(mut self, v: bool) -> Self {
+        self.is_automated = Some(v);
+        self
+    }
+
+    pub fn is_official(mut self, v: bool) -> Self {
+        self.is_official = Some(v);
+        self
+    }
+
+    /// Only return images with at least this many stars.
+    pub fn stars(mut self, v: u64) -> Self {
+        self.stars = Some(v);
+        self
+    }
+
+}
+
+// Docker Engine expects each filter to be a list of string values.
+fn sz_single<SZ, V>(value: &Option<V>, serializer: SZ) -> Result<SZ::Ok, SZ::Error>
+    where
+        SZ: Serializer,
+        V: ToString
+{
+    let mut sequence_sz = serializer.serialize_seq(Some(1))?;
+
+    if let Some(v) = value {
+        sequence_sz.serialize_element(&v.to_string())?;
+    }
+
+    sequence_sz.end()
+}
+
+#[cfg(test)]
+mod test_serialize_search_images_filters {
+    use super::SearchImagesFilters;
+
+    #[test]
+    fn empty() {
+        let filters = SearchImagesFilters::default();
+
+        assert!(filters.is_empty());
+    }
+
+    #[test]
+    fn official_with_stars() {
+        let filters = SearchImagesFilters::default()
+            .is_official(true)
+            .stars(3);
+
+        let actual = serde_json::to_string(&filters)
+            .unwrap();
+
+        assert_eq!("{\"is-official\":[\"true\"],\"stars\":[\"3\"]}".to_string(), actual);
+    }
+}
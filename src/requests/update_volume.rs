@@ -0,0 +1,30 @@
+use serde::Serialize;
+
+use crate::model::ClusterVolumeSpec;
+
+/// Change the spec of a cluster volume.
+///
+/// Requires Docker Engine api 1.42 or newer, and a swarm manager.
+///
+/// See https://docs.docker.com/engine/api/v1.42/#tag/Volume/operation/VolumeUpdate
+#[derive(Clone, Debug, Serialize)]
+pub struct UpdateVolumeRequest {
+    /// Version of the volume being updated, from `ClusterVolume::version`.
+    /// Updates are rejected if the volume has changed since that version.
+    #[serde(skip_serializing)]
+    pub version: u64,
+
+    #[serde(rename = "Spec")]
+    pub spec: ClusterVolumeSpec,
+}
+
+impl UpdateVolumeRequest {
+
+    pub fn new(version: u64, spec: ClusterVolumeSpec) -> Self {
+        Self {
+            version,
+            spec
+        }
+    }
+
+}
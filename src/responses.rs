@@ -1,39 +1,51 @@
 
 mod build_image;
+mod cleanup_session;
+mod commit;
 mod container_network_settings;
 mod create_container;
 mod create_exec;
 mod create_network;
+mod distribution_inspect;
 mod errors;
 mod exec_inspect;
 mod file_changes;
 mod inspect_container;
 mod inspect_container_detail;
+mod inspect_image;
 mod inspect_network;
 mod list_containers;
 mod list_images;
 mod list_volumes;
 mod mount;
 mod prune_volumes;
+mod run_container;
+mod search_images;
 mod top_response;
 mod version_response;
 mod wait;
 
 pub use build_image::*;
+pub use cleanup_session::*;
+pub use commit::*;
 pub use container_network_settings::*;
 pub use create_container::*;
 pub use create_exec::*;
 pub use create_network::*;
+pub use distribution_inspect::*;
 pub use exec_inspect::*;
 pub use file_changes::*;
 pub use inspect_container::*;
 pub use inspect_container_detail::*;
+pub use inspect_image::*;
 pub use inspect_network::*;
 pub use list_containers::*;
 pub use list_images::*;
 pub use list_volumes::*;
 pub use mount::*;
 pub use prune_volumes::*;
+pub use run_container::*;
+pub use search_images::*;
 pub use top_response::*;
 pub use version_response::*;
 pub use wait::*;
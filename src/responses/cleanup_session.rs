@@ -0,0 +1,29 @@
+use crate::model::{ContainerId, ImageId, NetworkId, VolumeName};
+
+/// Result of DecSession::cleanup and DockerEngineClient::sweep_sessions.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CleanupReport {
+    /// IDs of removed containers.
+    pub containers: Vec<ContainerId>,
+
+    /// IDs of removed networks.
+    pub networks: Vec<NetworkId>,
+
+    /// Names of removed volumes.
+    pub volumes: Vec<VolumeName>,
+
+    /// IDs of removed images.
+    pub images: Vec<ImageId>
+}
+
+impl CleanupReport {
+
+    /// Return true if nothing was removed.
+    pub fn is_empty(&self) -> bool {
+        self.containers.is_empty()
+            && self.networks.is_empty()
+            && self.volumes.is_empty()
+            && self.images.is_empty()
+    }
+
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::ImageId;
+
+/// See https://docs.docker.com/engine/api/v1.41/#tag/Image/operation/ImageCommit
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CommitResponse {
+
+    /// ID of the new image.
+    #[serde(rename = "Id")]
+    pub id: ImageId
+
+}
@@ -1,15 +1,15 @@
 use std::collections::HashMap;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::model::{ContainerIpamConfig, PortBinding};
 use crate::imp::serde::{dz_empty_object_as_none, dz_hashmap_of_nullable};
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerCreate
 /// and https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerInspect
-#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Network {
 
-    #[serde(rename = "IPAMConfig", skip_serializing_if = "Option::is_none", deserialize_with="dz_empty_object_as_none")]
+    #[serde(rename = "IPAMConfig", default, skip_serializing_if = "Option::is_none", deserialize_with = "dz_empty_object_as_none")]
     pub ipam_config: Option<ContainerIpamConfig>,
 
     #[serde(rename = "Links", skip_serializing_if = "Option::is_none")]
@@ -51,7 +51,7 @@ pub struct Network {
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerCreate
 /// and https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerInspect
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct NetworkSettings {
 
     #[serde(rename = "Ports", default, deserialize_with = "dz_hashmap_of_nullable", skip_serializing_if = "HashMap::is_empty")]
@@ -74,6 +74,22 @@ impl NetworkSettings {
             .map(|n| n.ip_address.as_str())
             .find(|ip| !ip.is_empty())
     }
+
+    /// Get the host port that a container port, such as "80" or "53/udp", is published on.
+    /// Without a protocol, tcp is assumed.
+    pub fn host_port(&self, container_port: &str) -> Option<u16> {
+        let key = if container_port.contains('/') {
+            container_port.to_string()
+        }
+        else {
+            format!("{}/tcp", container_port)
+        };
+
+        self.ports
+            .get(&key)?
+            .iter()
+            .find_map(|binding| binding.host_port.parse().ok())
+    }
 }
 
 #[cfg(test)]
@@ -136,4 +152,38 @@ mod test_inspect_container_response {
         }
 
     }
+
+    mod test_host_port {
+        use std::collections::HashMap;
+
+        use crate::model::PortBinding;
+        use crate::responses::NetworkSettings;
+
+        fn published(container_port: &str, host_port: &str) -> NetworkSettings {
+            NetworkSettings {
+                ports: HashMap::from([
+                    (
+                        container_port.into(),
+                        vec![PortBinding { host_ip: None, host_port: host_port.into() }]
+                    )
+                ]),
+                ..NetworkSettings::default()
+            }
+        }
+
+        #[test]
+        fn default_protocol() {
+            let settings = published("80/tcp", "32768");
+
+            assert_eq!(Some(32768), settings.host_port("80"));
+            assert_eq!(Some(32768), settings.host_port("80/tcp"));
+            assert_eq!(None, settings.host_port("80/udp"));
+        }
+
+        #[test]
+        fn not_published() {
+            assert_eq!(None, NetworkSettings::default().host_port("80"));
+        }
+
+    }
 }
\ No newline at end of file
@@ -1,11 +1,13 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::model::ContainerId;
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerCreate
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct CreateContainerResponse {
 
     #[serde(rename = "Id")]
-    pub id: String,
+    pub id: ContainerId,
 
     #[serde(rename = "Warnings")]
     pub warnings: Vec<String>,
@@ -1,10 +1,12 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::model::ExecId;
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Exec/operation/ContainerExec
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct CreateExecResponse {
 
     #[serde(rename = "Id")]
-    pub id: String
+    pub id: ExecId
 
 }
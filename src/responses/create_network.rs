@@ -1,12 +1,14 @@
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::model::NetworkId;
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Network/operation/NetworkCreate
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct CreateNetworkResponse {
 
     #[serde(rename = "Id")]
-    pub id: String,
+    pub id: NetworkId,
 
     #[serde(rename = "Warning")]
     pub warning: String,
@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+
+use crate::imp::serde::dz_vec;
+use crate::model::Platform;
+
+/// See https://docs.docker.com/engine/api/v1.41/#tag/Distribution/operation/DistributionInspect
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct DistributionInspectResponse {
+
+    #[serde(rename = "Descriptor")]
+    pub descriptor: DistributionDescriptor,
+
+    /// Platforms supported by the image. A single-platform image has one entry;
+    /// a multi-platform image (manifest list) has one entry per platform.
+    #[serde(rename = "Platforms", default, deserialize_with = "dz_vec")]
+    pub platforms: Vec<DistributionPlatform>,
+
+}
+
+impl DistributionInspectResponse {
+
+    /// Return true if the image is available for the platform.
+    pub fn supports(&self, platform: &Platform) -> bool {
+        self.platforms
+            .iter()
+            .any(|p| p.platform().matches(platform))
+    }
+
+}
+
+/// An OCI descriptor of the manifest (or manifest list) that the image reference resolves to.
+///
+/// See https://docs.docker.com/engine/api/v1.41/#tag/Distribution/operation/DistributionInspect
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct DistributionDescriptor {
+
+    #[serde(rename = "mediaType")]
+    pub media_type: String,
+
+    /// Content digest of the manifest, e.g. "sha256:c0537ff6a5..."
+    ///
+    /// Changes whenever the tag on the registry is moved to different content.
+    #[serde(rename = "digest")]
+    pub digest: String,
+
+    /// Size of the manifest, in bytes.
+    #[serde(rename = "size")]
+    pub size: i64,
+
+    #[serde(rename = "urls", default, deserialize_with = "dz_vec")]
+    pub urls: Vec<String>,
+
+}
+
+/// See https://docs.docker.com/engine/api/v1.41/#tag/Distribution/operation/DistributionInspect
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct DistributionPlatform {
+
+    #[serde(rename = "architecture")]
+    pub architecture: String,
+
+    #[serde(rename = "os")]
+    pub os: String,
+
+    #[serde(rename = "os.version", default)]
+    pub os_version: Option<String>,
+
+    #[serde(rename = "os.features", default, deserialize_with = "dz_vec")]
+    pub os_features: Vec<String>,
+
+    #[serde(rename = "variant", default)]
+    pub variant: Option<String>,
+
+    #[serde(rename = "features", default, deserialize_with = "dz_vec")]
+    pub features: Vec<String>,
+
+}
+
+impl DistributionPlatform {
+
+    pub fn platform(&self) -> Platform {
+        Platform {
+            os: self.os.clone(),
+            architecture: self.architecture.clone(),
+            variant: self.variant.clone()
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod test_distribution_inspect_response {
+
+    mod supports {
+        use crate::model::Platform;
+        use crate::responses::{DistributionDescriptor, DistributionInspectResponse, DistributionPlatform};
+
+        fn response() -> DistributionInspectResponse {
+            DistributionInspectResponse {
+                descriptor: DistributionDescriptor {
+                    media_type: "x".into(),
+                    digest: "y".into(),
+                    size: 1,
+                    urls: vec![]
+                },
+                platforms: vec![
+                    DistributionPlatform {
+                        os: "linux".into(),
+                        architecture: "amd64".into(),
+                        ..DistributionPlatform::default()
+                    },
+                    DistributionPlatform {
+                        os: "linux".into(),
+                        architecture: "arm64".into(),
+                        variant: Some("v8".into()),
+                        ..DistributionPlatform::default()
+                    }
+                ]
+            }
+        }
+
+        #[test]
+        fn when_listed() {
+            assert!(response().supports(&Platform::new("linux", "arm64")));
+        }
+
+        #[test]
+        fn not_when_absent() {
+            assert!(!response().supports(&Platform::new("windows", "amd64")));
+        }
+    }
+}
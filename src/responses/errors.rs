@@ -1,10 +1,10 @@
 use std::fmt::{Display, Formatter};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Docker Engine returned an error status code, which the client expects
 /// to be accompanied by a JSON response body in this specific layout.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub(crate) struct ErrorResponse {
     pub(crate) message: String,
 }
@@ -1,8 +1,10 @@
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::model::ExecId;
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Exec/operation/ExecInspect
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ExecInspectResponse {
 
     #[serde(rename = "CanRemove")]
@@ -12,7 +14,7 @@ pub struct ExecInspectResponse {
     pub detach_keys: String,
 
     #[serde(rename = "ID")]
-    pub id: String,
+    pub id: ExecId,
 
     #[serde(rename = "Running")]
     pub running: bool,
@@ -1,7 +1,7 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerChanges
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FileSystemChange {
     #[serde(rename = "Path")]
     path: String,
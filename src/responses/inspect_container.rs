@@ -1,18 +1,20 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 
 use crate::imp::serde::{dz_hashmap_keys, dz_vec};
-use crate::model::{HealthCheck, Unit};
+use crate::imp::timestamp::parse_engine_time;
+use crate::model::{ContainerId, HealthCheck, Unit};
 use crate::responses::NetworkSettings;
 use crate::responses::inspect_container_detail::{GraphDriver, MountPoint, State};
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerInspect
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct InspectContainerResponse {
 
     #[serde(rename = "Id")]
-    pub id: String,
+    pub id: ContainerId,
 
     #[serde(rename = "Created")]
     pub created: String,
@@ -97,10 +99,15 @@ impl InspectContainerResponse {
         self.network_settings.first_ip_address()
     }
 
+    /// When the container was created, parsed from created.
+    pub fn created_time(&self) -> Option<OffsetDateTime> {
+        parse_engine_time(&self.created)
+    }
+
 }
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerInspect
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct InspectedContainerConfig {
 
     #[serde(rename = "Hostname")]
@@ -178,7 +185,7 @@ pub struct InspectedContainerConfig {
 }
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerInspect
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct InspectedContainerHostConfig {
 
     #[serde(rename = "NetworkMode")]
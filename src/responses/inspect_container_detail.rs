@@ -1,9 +1,11 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use time::OffsetDateTime;
 use crate::imp::serde::dz_hashmap;
+use crate::imp::timestamp::parse_engine_time;
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerInspect
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct GraphDriver {
 
     #[serde(rename = "Name")]
@@ -14,7 +16,7 @@ pub struct GraphDriver {
 }
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerInspect
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Health {
 
     #[serde(rename = "Status")]
@@ -29,7 +31,7 @@ pub struct Health {
 }
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerInspect
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct HealthCheckResult {
 
     #[serde(rename = "Start")]
@@ -46,8 +48,22 @@ pub struct HealthCheckResult {
 
 }
 
+impl HealthCheckResult {
+
+    /// When the health check started, parsed from start.
+    pub fn start_time(&self) -> Option<OffsetDateTime> {
+        parse_engine_time(&self.start)
+    }
+
+    /// When the health check finished, parsed from end.
+    pub fn end_time(&self) -> Option<OffsetDateTime> {
+        parse_engine_time(&self.end)
+    }
+
+}
+
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerInspect
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct State {
 
     #[serde(rename = "Status")]
@@ -85,8 +101,22 @@ pub struct State {
 
 }
 
+impl State {
+
+    /// When the container last started, parsed from started_at. None if it never started.
+    pub fn started_time(&self) -> Option<OffsetDateTime> {
+        parse_engine_time(&self.started_at)
+    }
+
+    /// When the container last stopped, parsed from finished_at. None if it never stopped.
+    pub fn finished_time(&self) -> Option<OffsetDateTime> {
+        parse_engine_time(&self.finished_at)
+    }
+
+}
+
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerInspect
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct MountPoint {
 
     #[serde(rename = "Type")]
@@ -114,3 +144,20 @@ pub struct MountPoint {
     pub propagation: String
 
 }
+
+#[cfg(test)]
+mod test_state {
+    use crate::responses::State;
+
+    #[test]
+    fn running_container_has_not_finished() {
+        let state = State {
+            started_at: "2022-11-28T00:34:45.107901180Z".into(),
+            finished_at: "0001-01-01T00:00:00Z".into(),
+            ..State::default()
+        };
+
+        assert_eq!(1669595685, state.started_time().unwrap().unix_timestamp());
+        assert_eq!(None, state.finished_time());
+    }
+}
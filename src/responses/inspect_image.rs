@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::imp::serde::{dz_empty_as_none, sz_none_as_empty, dz_vec};
+use crate::imp::timestamp::parse_engine_time;
+use crate::model::{ImageId, Platform};
+use crate::responses::GraphDriver;
+
+/// See https://docs.docker.com/engine/api/v1.41/#tag/Image/operation/ImageInspect
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct InspectImageResponse {
+
+    #[serde(rename = "Id")]
+    pub id: ImageId,
+
+    #[serde(rename = "RepoTags", deserialize_with = "dz_vec")]
+    pub repo_tags: Vec<String>,
+
+    #[serde(rename = "RepoDigests", deserialize_with = "dz_vec")]
+    pub repo_digests: Vec<String>,
+
+    #[serde(rename = "Parent", default)]
+    pub parent: String,
+
+    #[serde(rename = "Comment", default)]
+    pub comment: String,
+
+    #[serde(rename = "Created")]
+    pub created: String,
+
+    #[serde(rename = "DockerVersion", default)]
+    pub docker_version: String,
+
+    #[serde(rename = "Author", default)]
+    pub author: String,
+
+    #[serde(rename = "Architecture")]
+    pub architecture: String,
+
+    #[serde(rename = "Variant", default, deserialize_with = "dz_empty_as_none", serialize_with = "sz_none_as_empty")]
+    pub variant: Option<String>,
+
+    #[serde(rename = "Os")]
+    pub os: String,
+
+    #[serde(rename = "OsVersion", default, deserialize_with = "dz_empty_as_none", serialize_with = "sz_none_as_empty")]
+    pub os_version: Option<String>,
+
+    #[serde(rename = "Size")]
+    pub size: i64,
+
+    #[serde(rename = "VirtualSize", default)]
+    pub virtual_size: i64,
+
+    #[serde(rename = "GraphDriver")]
+    pub graph_driver: GraphDriver,
+
+    #[serde(rename = "RootFS")]
+    pub root_fs: ImageRootFs,
+
+}
+
+impl InspectImageResponse {
+
+    /// The platform the image was built for.
+    pub fn platform(&self) -> Platform {
+        Platform {
+            os: self.os.clone(),
+            architecture: self.architecture.clone(),
+            variant: self.variant.clone()
+        }
+    }
+
+    /// When the image was created, parsed from created.
+    pub fn created_time(&self) -> Option<OffsetDateTime> {
+        parse_engine_time(&self.created)
+    }
+
+}
+
+/// See https://docs.docker.com/engine/api/v1.41/#tag/Image/operation/ImageInspect
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ImageRootFs {
+
+    #[serde(rename = "Type")]
+    pub root_fs_type: String,
+
+    #[serde(rename = "Layers", default, deserialize_with = "dz_vec")]
+    pub layers: Vec<String>,
+
+}
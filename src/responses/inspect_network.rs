@@ -1,19 +1,19 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::model::NetworkIpam;
-use crate::imp::serde::dz_empty_as_none;
+use crate::model::{NetworkId, NetworkIpam};
+use crate::imp::serde::{dz_empty_as_none, sz_none_as_empty};
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Network/operation/NetworkInspect
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct InspectNetworkResponse {
 
     #[serde(rename = "Name")]
     pub name: String,
 
     #[serde(rename = "Id")]
-    pub id: String,
+    pub id: NetworkId,
 
     #[serde(rename = "Scope")]
     pub scope: String,
@@ -44,7 +44,7 @@ pub struct InspectNetworkResponse {
 }
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Network/operation/NetworkInspect
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct InspectNetworkResponseContainer {
 
     #[serde(rename = "Name")]
@@ -56,10 +56,10 @@ pub struct InspectNetworkResponseContainer {
     #[serde(rename = "MacAddress")]
     pub mac_address: String,
 
-    #[serde(rename = "IPv4Address", deserialize_with="dz_empty_as_none")]
+    #[serde(rename = "IPv4Address", deserialize_with="dz_empty_as_none", serialize_with="sz_none_as_empty")]
     pub ipv4_address: Option<String>,
 
-    #[serde(rename = "IPv6Address", deserialize_with="dz_empty_as_none")]
+    #[serde(rename = "IPv6Address", deserialize_with="dz_empty_as_none", serialize_with="sz_none_as_empty")]
     pub ipv6_address: Option<String>,
 
 }
\ No newline at end of file
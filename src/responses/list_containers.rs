@@ -1,13 +1,16 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use time::OffsetDateTime;
+use crate::imp::timestamp::from_unix_seconds;
+use crate::model::ContainerId;
 use crate::responses::{Mount, NetworkSettings};
 
 // See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerList
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ListedContainer {
 
     #[serde(rename = "Id")]
-    pub id: String,
+    pub id: ContainerId,
 
     /// Names will start with a forward slash
     #[serde(rename = "Names")]
@@ -53,8 +56,19 @@ pub struct ListedContainer {
     pub mounts: Vec<Mount>,
 }
 
+impl ListedContainer {
+
+    /// When the container was created, converted from created.
+    pub fn created_time(&self) -> Option<OffsetDateTime> {
+        i64::try_from(self.created)
+            .ok()
+            .and_then(from_unix_seconds)
+    }
+
+}
+
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerList
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ListedContainerHostConfig {
 
     #[serde(rename = "NetworkMode")]
@@ -63,7 +77,7 @@ pub struct ListedContainerHostConfig {
 }
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerList
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct PortMapping {
 
     #[serde(rename = "IP")]
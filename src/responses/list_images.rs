@@ -1,15 +1,18 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 
 use crate::imp::serde::{dz_hashmap, dz_vec};
+use crate::imp::timestamp::from_unix_seconds;
+use crate::model::ImageId;
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Image/operation/ImageList
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ListedImage {
 
     #[serde(rename = "Id")]
-    pub id: String,
+    pub id: ImageId,
 
     #[serde(rename = "ParentId")]
     pub parent_id: String,
@@ -40,3 +43,12 @@ pub struct ListedImage {
     pub containers: i64,
 
 }
+
+impl ListedImage {
+
+    /// When the image was created, converted from created.
+    pub fn created_time(&self) -> Option<OffsetDateTime> {
+        from_unix_seconds(self.created)
+    }
+
+}
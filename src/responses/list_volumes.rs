@@ -1,10 +1,10 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::model::Volume;
 use crate::imp::serde::dz_vec;
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Volume/operation/VolumeList
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ListVolumesResponse {
 
     #[serde(rename = "Volumes", deserialize_with = "dz_vec")]
@@ -1,8 +1,8 @@
 use std::collections::HashMap;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerList
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct BindOptions {
 
     #[serde(rename = "Propagation")]
@@ -14,7 +14,7 @@ pub struct BindOptions {
 }
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerList
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct DriverConfig {
 
     #[serde(rename = "Name")]
@@ -26,7 +26,7 @@ pub struct DriverConfig {
 }
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerList
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Mount {
 
     #[serde(rename = "Target")]
@@ -56,7 +56,7 @@ pub struct Mount {
 }
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerList
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct TmpfsOptions {
 
     #[serde(rename = "SizeBytes")]
@@ -68,7 +68,7 @@ pub struct TmpfsOptions {
 }
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerList
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct VolumeOptions {
 
     #[serde(rename = "NoCopy")]
@@ -1,8 +1,8 @@
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Volume/operation/VolumePrune
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct PruneVolumesResponse {
 
     #[serde(rename = "VolumesDeleted")]
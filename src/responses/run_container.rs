@@ -0,0 +1,56 @@
+use crate::model::{StreamKind, StreamLine};
+
+/// Result of DecContainers::run.
+#[derive(Clone, Debug)]
+pub struct RunOutput {
+    /// Process exit code of the container.
+    pub exit_code: i64,
+
+    /// Lines of stdout and stderr, in order. Empty unless RunOptions::collect_output is set.
+    pub output: Vec<StreamLine>
+}
+
+impl RunOutput {
+
+    /// Text written to stderr.
+    pub fn stderr(&self) -> String {
+        self.text_of(StreamKind::StdErr)
+    }
+
+    /// Text written to stdout.
+    pub fn stdout(&self) -> String {
+        self.text_of(StreamKind::StdOut)
+    }
+
+    fn text_of(&self, kind: StreamKind) -> String {
+        self.output
+            .iter()
+            .filter(|line| line.kind == kind)
+            .map(|line| line.text.as_str())
+            .collect()
+    }
+
+}
+
+#[cfg(test)]
+mod test_run_output {
+    use crate::model::{StreamKind, StreamLine};
+    use crate::responses::RunOutput;
+
+    #[test]
+    fn splits_streams() {
+        let line = |kind, text: &str| StreamLine { kind, text: text.into() };
+
+        let output = RunOutput {
+            exit_code: 0,
+            output: vec![
+                line(StreamKind::StdOut, "a\n"),
+                line(StreamKind::StdErr, "b\n"),
+                line(StreamKind::StdOut, "c\n")
+            ]
+        };
+
+        assert_eq!("a\nc\n", output.stdout());
+        assert_eq!("b\n", output.stderr());
+    }
+}
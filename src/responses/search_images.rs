@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// See https://docs.docker.com/engine/api/v1.41/#tag/Image/operation/ImageSearch
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SearchedImage {
+
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "description", default)]
+    pub description: String,
+
+    #[serde(rename = "star_count", default)]
+    pub star_count: u64,
+
+    #[serde(rename = "is_official", default)]
+    pub is_official: bool,
+
+    #[serde(rename = "is_automated", default)]
+    pub is_automated: bool,
+
+}
@@ -1,7 +1,7 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerTop
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct TopResponse {
 
     #[serde(rename = "Titles")]
@@ -1,8 +1,8 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 // See https://docs.docker.com/engine/api/v1.41/#tag/System/operation/SystemVersion
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct VersionResponse {
 
     #[serde(rename = "Platform")]
@@ -42,7 +42,7 @@ pub struct VersionResponse {
     pub build_time: String,
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Component {
 
     #[serde(rename = "Name")]
@@ -56,7 +56,7 @@ pub struct Component {
 
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Platform {
 
     #[serde(rename = "Name")]
@@ -1,8 +1,8 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use crate::responses::ErrorResponse;
 
 /// See https://docs.docker.com/engine/api/v1.41/#tag/Container/operation/ContainerWait
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct WaitResponse {
 
     /// Process exit code of the container.
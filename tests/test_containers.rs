@@ -8,7 +8,6 @@ use std::collections::HashMap;
 use std::time::Duration;
 
 use const_str::concat;
-use hyper::StatusCode;
 
 #[cfg(not(target_os = "macos"))]
 use log::{info, warn};
@@ -19,7 +18,7 @@ use test_utils::random_name;
 use passivized_docker_engine_client::DockerEngineClient;
 use passivized_docker_engine_client::errors::DecUseError;
 use passivized_docker_engine_client::model::{StreamKind, Unit};
-use passivized_docker_engine_client::requests::{CreateContainerRequest, Filters, HostConfig, InspectContainerArgs, ListContainersRequest};
+use passivized_docker_engine_client::requests::{CommitRequest, CreateContainerRequest, Filters, HostConfig, InspectContainerArgs, ListContainersRequest};
 
 #[cfg(not(target_os = "macos"))]
 use passivized_docker_engine_client::responses::FileSystemChangeKind;
@@ -38,6 +37,52 @@ use passivized_test_support::waiter::wait_for_http_server;
 #[cfg(not(windows))]
 use passivized_docker_engine_client::requests::WaitCondition;
 
+#[tokio::test]
+async fn test_commit_container() {
+    const FN: &str = "test_commit_container";
+
+    let dec = DockerEngineClient::new()
+        .unwrap();
+
+    dec.images().pull_if_not_present(hello::IMAGE, hello::TAG)
+        .await
+        .unwrap();
+
+    let request: CreateContainerRequest = CreateContainerRequest::default()
+        .name(random_name(FN))
+        .image(format!("{}:{}", hello::IMAGE, hello::TAG));
+
+    let container = dec.containers().create(request)
+        .await
+        .unwrap();
+
+    let commit = CommitRequest::default()
+        .repo(FN)
+        .tag("snapshot")
+        .comment("committed by test")
+        .change("ENV SNAPSHOT=true");
+
+    let committed = dec.container(&container.id).commit(commit)
+        .await
+        .unwrap();
+
+    let inspected = dec.images().inspect(&committed.id)
+        .await
+        .unwrap();
+
+    assert_eq!(committed.id, inspected.id);
+    assert_eq!(vec![format!("{}:snapshot", FN)], inspected.repo_tags);
+    assert_eq!("committed by test", inspected.comment);
+
+    dec.container(container.id).remove()
+        .await
+        .unwrap();
+
+    dec.images().untag(format!("{}:snapshot", FN))
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn test_find_containers_by_label() {
     const FN: &str = "test_find_containers_by_label";
@@ -563,8 +608,7 @@ async fn test_remove_running_container() {
         .await
         .unwrap_err();
 
-    if let DecUseError::Rejected { status, message } = removal_error {
-        assert_eq!(StatusCode::CONFLICT, status);
+    if let DecUseError::Conflict { message, .. } = removal_error {
         assert!(
             message.contains("cannot remove a running container")
             ||
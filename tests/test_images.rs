@@ -4,16 +4,15 @@ mod test_utils;
 use std::str::FromStr;
 
 use http::Uri;
-use hyper::StatusCode;
 use mockito::{Server, ServerGuard};
 use tar::{Header, Builder};
-use test_utils::images::web;
+use test_utils::images::{EXPECTED_PLATFORM, web};
 use test_utils::{content_type, random_name};
 use passivized_docker_engine_client::client::DOCKER_ENGINE_VERSION;
 use passivized_docker_engine_client::DockerEngineClient;
 use passivized_docker_engine_client::errors::DecUseError;
 use passivized_docker_engine_client::model::Tar;
-use passivized_docker_engine_client::requests::BuildImageRequest;
+use passivized_docker_engine_client::requests::{BuildImageRequest, SearchImagesFilters};
 
 #[cfg(not(windows))]
 use passivized_docker_engine_client::model::TsStreamLine;
@@ -295,8 +294,7 @@ async fn test_build_fails_invalid_syntax() {
         .await
         .unwrap_err();
 
-    if let DecUseError::Rejected { status, message } = actual {
-        assert_eq!(StatusCode::BAD_REQUEST, status);
+    if let DecUseError::BadParameter { message, .. } = actual {
         assert!(message.contains("dockerfile parse error"), "Message contains 'dockerfile parse error': {}", message);
     }
     else {
@@ -304,6 +302,20 @@ async fn test_build_fails_invalid_syntax() {
     }
 }
 
+#[tokio::test]
+async fn test_distribution_inspect() {
+    let dec = DockerEngineClient::new()
+        .unwrap();
+
+    let inspected = dec.images().distribution_inspect(format!("{}:{}", web::IMAGE, web::TAG))
+        .await
+        .unwrap();
+
+    assert!(inspected.descriptor.digest.starts_with("sha256:"));
+    assert!(inspected.descriptor.size > 0);
+    assert!(inspected.platforms.iter().any(|p| p.os == EXPECTED_PLATFORM));
+}
+
 #[tokio::test]
 async fn test_pull_and_list_images() {
     let dec = DockerEngineClient::new()
@@ -344,6 +356,24 @@ async fn test_pull_not_found() {
     }
 }
 
+#[tokio::test]
+async fn test_search_official() {
+    let dec = DockerEngineClient::new()
+        .unwrap();
+
+    let filters = SearchImagesFilters::default()
+        .is_official(true);
+
+    let found = dec.images().search(web::IMAGE, Some(5), filters)
+        .await
+        .unwrap();
+
+    assert!(!found.is_empty());
+    assert!(found.len() <= 5);
+    assert!(found.iter().all(|image| image.is_official));
+    assert!(found.iter().any(|image| image.name == web::IMAGE));
+}
+
 #[tokio::test]
 async fn test_tag_and_untag() {
     let dec = DockerEngineClient::new()
@@ -137,6 +137,103 @@ pub mod containers {
 
 }
 
+pub mod distribution_inspect_response {
+    use passivized_docker_engine_client::responses::{DistributionInspectResponse, DistributionPlatform};
+
+    #[test]
+    pub fn parses() {
+        let text = super::fixtures::json("distribution-inspect-response.json");
+        let actual: DistributionInspectResponse = serde_json::from_str(&text)
+            .unwrap();
+
+        assert_eq!("application/vnd.docker.distribution.manifest.list.v2+json", actual.descriptor.media_type);
+        assert_eq!("sha256:c0537ff6a5218ef531ece93d4984efc99bbf3f7497c0a7726c88e2bb7584dc96", actual.descriptor.digest);
+        assert_eq!(1863, actual.descriptor.size);
+        assert!(actual.descriptor.urls.is_empty());
+
+        assert_eq!(4, actual.platforms.len());
+
+        assert_eq!(
+            &DistributionPlatform {
+                architecture: "arm64".into(),
+                os: "linux".into(),
+                variant: Some("v8".into()),
+                ..DistributionPlatform::default()
+            },
+            &actual.platforms[2]
+        );
+
+        assert_eq!(Some("10.0.17763.3406".into()), actual.platforms[3].os_version);
+        assert_eq!(vec!["win32k".to_string()], actual.platforms[3].os_features);
+    }
+}
+
+pub mod image_inspect_response {
+    use passivized_docker_engine_client::model::Platform;
+    use passivized_docker_engine_client::responses::InspectImageResponse;
+
+    #[test]
+    pub fn parses() {
+        let text = super::fixtures::json("image-inspect-response.json");
+        let actual: InspectImageResponse = serde_json::from_str(&text)
+            .unwrap();
+
+        assert_eq!("sha256:9c7a54a9a43cca047013b82af109fe963fde787f63f9e016fdc3384500c2823d", actual.id);
+        assert_eq!(vec!["busybox:latest".to_string()], actual.repo_tags);
+        assert_eq!(None, actual.os_version);
+        assert_eq!("overlay2", actual.graph_driver.name);
+        assert_eq!(1, actual.root_fs.layers.len());
+
+        assert_eq!(Platform::new("linux", "arm64").variant("v8"), actual.platform());
+    }
+
+    #[test]
+    pub fn parses_without_parent_comment_or_author() {
+        let text = super::fixtures::json("image-inspect-response.json");
+        let mut value: serde_json::Value = serde_json::from_str(&text)
+            .unwrap();
+
+        let fields = value.as_object_mut()
+            .unwrap();
+
+        for field in ["Parent", "Comment", "Author"] {
+            fields.remove(field);
+        }
+
+        let actual: InspectImageResponse = serde_json::from_value(value)
+            .unwrap();
+
+        assert_eq!("", actual.parent);
+        assert_eq!("", actual.comment);
+        assert_eq!("", actual.author);
+    }
+}
+
+pub mod image_search_response {
+    use passivized_docker_engine_client::responses::SearchedImage;
+
+    #[test]
+    pub fn parses() {
+        let text = super::fixtures::json("image-search-response.json");
+        let actual: Vec<SearchedImage> = serde_json::from_str(&text)
+            .unwrap();
+
+        assert_eq!(2, actual.len());
+
+        let official = &actual[0];
+
+        assert_eq!("postgres", official.name);
+        assert_eq!(12345, official.star_count);
+        assert!(official.is_official);
+        assert!(!official.is_automated);
+
+        let automated = &actual[1];
+
+        assert_eq!("", automated.description);
+        assert!(automated.is_automated);
+    }
+}
+
 pub mod ipam_config {
     use passivized_docker_engine_client::model::ContainerIpamConfig;
 
@@ -267,4 +364,166 @@ pub mod portinfo {
             actual
         )
     }
-}
\ No newline at end of file
+}
+
+pub mod volume_inspect_response {
+    use passivized_docker_engine_client::model::Volume;
+
+    #[test]
+    pub fn parses_cluster_volume() {
+        let text = super::fixtures::json("volume-inspect-cluster-response.json");
+        let actual: Volume = serde_json::from_str(&text)
+            .unwrap();
+
+        assert_eq!("shared-data", actual.name);
+
+        let cluster_volume = actual.cluster_volume
+            .unwrap();
+
+        assert_eq!(42, cluster_volume.version.index);
+        assert_eq!(Some("db".to_string()), cluster_volume.spec.group);
+
+        let access_mode = cluster_volume.spec.access_mode
+            .unwrap();
+
+        assert_eq!(Some("all".to_string()), access_mode.sharing);
+        assert_eq!(Some("active".to_string()), access_mode.availability);
+        assert_eq!("csi-token", access_mode.secrets[0].secret);
+        assert_eq!(Some(1073741824), access_mode.capacity_range.unwrap().required_bytes);
+    }
+}
+
+pub mod round_trip {
+    use serde::de::DeserializeOwned;
+    use serde::Serialize;
+    use serde_json::Value;
+    use passivized_docker_engine_client::model::Volume;
+    use passivized_docker_engine_client::responses::{DistributionInspectResponse, InspectContainerResponse, InspectImageResponse, InspectNetworkResponse, ListedContainer, NetworkSettings, SearchedImage, VersionResponse};
+
+    /// Parse a fixture, serialize it, and check that every serialized field has the same value
+    /// as in the fixture. Fields of the fixture that are not modeled are ignored.
+    fn round_trip<T: DeserializeOwned + Serialize>(fixture: &str) -> Value {
+        let text = super::fixtures::json(fixture);
+
+        let original: Value = serde_json::from_str(&text)
+            .unwrap();
+
+        let parsed: T = serde_json::from_value(original.clone())
+            .unwrap();
+
+        let serialized = serde_json::to_value(&parsed)
+            .unwrap();
+
+        assert_subset(fixture, "", &original, &serialized);
+
+        serialized
+    }
+
+    fn assert_subset(fixture: &str, path: &str, original: &Value, serialized: &Value) {
+        match (original, serialized) {
+            (Value::Object(original), Value::Object(serialized)) => {
+                for (key, value) in serialized {
+                    let path = format!("{}/{}", path, key);
+
+                    match original.get(key) {
+                        Some(original_value) => assert_subset(fixture, &path, original_value, value),
+                        // Absent from the fixture, so the model's default is expected.
+                        None => assert!(is_default(value), "{} at {}: {}", fixture, path, value)
+                    }
+                }
+            },
+            (Value::Array(original), Value::Array(serialized)) => {
+                assert_eq!(original.len(), serialized.len(), "{} at {}", fixture, path);
+
+                for (i, (o, s)) in original.iter().zip(serialized).enumerate() {
+                    assert_subset(fixture, &format!("{}/{}", path, i), o, s);
+                }
+            },
+            // Null lists and maps are parsed as empty ones.
+            (Value::Null, Value::Array(serialized)) if serialized.is_empty() => {},
+            (Value::Null, Value::Object(serialized)) if serialized.is_empty() => {},
+            (original, serialized) => {
+                assert_eq!(original, serialized, "{} at {}", fixture, path);
+            }
+        }
+    }
+
+    fn is_default(value: &Value) -> bool {
+        match value {
+            Value::Null => true,
+            Value::Bool(b) => !b,
+            Value::Number(n) => n.as_f64() == Some(0.0),
+            Value::String(s) => s.is_empty(),
+            Value::Array(a) => a.is_empty(),
+            Value::Object(o) => o.values().all(is_default)
+        }
+    }
+
+    #[test]
+    pub fn container_inspect() {
+        let actual = round_trip::<InspectContainerResponse>("fedora-inspect-running-container-response.json");
+
+        assert!(actual["Id"].is_string());
+        assert_eq!(Value::Bool(true), actual["State"]["Running"]);
+        assert!(actual["State"]["StartedAt"].is_string());
+        assert!(actual["Config"]["ExposedPorts"].is_object());
+
+        round_trip::<InspectContainerResponse>("container-create-response.json");
+    }
+
+    #[test]
+    pub fn container_list() {
+        let actual = round_trip::<Vec<ListedContainer>>("container_list_without_ipv4.json");
+
+        assert!(actual[0]["Id"].is_string());
+        assert!(actual[0]["Labels"].is_object());
+    }
+
+    #[test]
+    pub fn distribution_inspect() {
+        round_trip::<DistributionInspectResponse>("distribution-inspect-response.json");
+    }
+
+    #[test]
+    pub fn image_inspect() {
+        let actual = round_trip::<InspectImageResponse>("image-inspect-response.json");
+
+        assert!(actual["RepoTags"].is_array());
+    }
+
+    #[test]
+    pub fn image_search() {
+        round_trip::<Vec<SearchedImage>>("image-search-response.json");
+    }
+
+    #[test]
+    pub fn network_inspect() {
+        round_trip::<InspectNetworkResponse>("network-inspect-response.json");
+        round_trip::<InspectNetworkResponse>("network-inspect-response-containers.json");
+    }
+
+    #[test]
+    pub fn network_settings() {
+        round_trip::<NetworkSettings>("networksettings-ports-wo-portbindings.json");
+    }
+
+    #[test]
+    pub fn version() {
+        let text = super::fixtures::json("version-response.json");
+        let expected: VersionResponse = serde_json::from_str(&text)
+            .unwrap();
+
+        let actual = round_trip::<VersionResponse>("version-response.json");
+
+        assert_eq!("1.40", actual["ApiVersion"]);
+        assert_eq!(expected, serde_json::from_value(actual).unwrap());
+    }
+
+    #[test]
+    pub fn volume_inspect() {
+        let actual = round_trip::<Volume>("volume-inspect-cluster-response.json");
+
+        assert_eq!("shared-data", actual["Name"]);
+        assert!(actual["ClusterVolume"]["Spec"].is_object());
+    }
+}
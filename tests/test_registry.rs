@@ -26,7 +26,7 @@ async fn test_push_to_authenticated_registry() {
     use std::path::PathBuf;
 
     use hyper::StatusCode;
-    use hyper_tls::native_tls::{Certificate, Identity, TlsConnector};
+    use native_tls::{Certificate, Identity, TlsConnector};
     use itertools::Itertools;
     use log::info;
     use openssl::pkey::{PKey, Private};
@@ -91,7 +91,7 @@ async fn test_push_to_authenticated_registry() {
         // A prior run of the test created the network, but failed at a later step. Reuse the network.
         // However, if a container is still running from a failed test, an Address In Use error will
         // be generated when creating a container.
-        Err(DecUseError::Rejected { status: StatusCode::CONFLICT, .. }) => {},
+        Err(DecUseError::Conflict { .. }) => {},
 
         // Test setup failed
         _ => {
@@ -364,7 +364,7 @@ async fn test_push_to_authenticated_registry() {
         .await
         .unwrap_err();
 
-    if let DecUseError::Rejected { status: StatusCode::INTERNAL_SERVER_ERROR, message } = pull_anonymous_failure {
+    if let DecUseError::ServerError { status: StatusCode::INTERNAL_SERVER_ERROR, message, .. } = pull_anonymous_failure {
         assert!(message.contains("no basic auth credentials"));
     }
     else {
@@ -7,6 +7,9 @@ use passivized_docker_engine_client::DockerEngineClient;
 use passivized_docker_engine_client::errors::DecUseError;
 use passivized_docker_engine_client::requests::CreateVolumeRequest;
 
+#[cfg(target_os = "linux")]
+use passivized_docker_engine_client::requests::LocalVolumeOpts;
+
 #[tokio::test]
 async fn test_create_list_and_delete_volume() {
     const FN: &str = "test_create_list_and_delete_volume";
@@ -19,10 +22,12 @@ async fn test_create_list_and_delete_volume() {
     let request = CreateVolumeRequest::default()
         .name(&volume_name);
 
-    dec.volumes().create(request)
+    let created = dec.volumes().create(request)
         .await
         .unwrap();
 
+    assert_eq!(volume_name, created.name);
+
     let volumes = dec.volumes().list()
         .await
         .unwrap()
@@ -108,3 +113,50 @@ async fn test_create_inspect_list_and_remove_volume() {
     }
 
 }
+
+#[tokio::test]
+async fn test_create_anonymous_volume() {
+    let dec = DockerEngineClient::new()
+        .unwrap();
+
+    let created = dec.volumes().create(CreateVolumeRequest::default())
+        .await
+        .unwrap();
+
+    assert!(!created.name.is_empty());
+
+    let inspected = dec.volume(&created.name).inspect()
+        .await
+        .unwrap();
+
+    assert_eq!(created.mountpoint, inspected.mountpoint);
+
+    dec.volume(created.name).remove(false)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[cfg(target_os = "linux")]
+async fn test_create_tmpfs_volume() {
+    const FN: &str = "test_create_tmpfs_volume";
+
+    let dec = DockerEngineClient::new()
+        .unwrap();
+
+    let request = CreateVolumeRequest::default()
+        .name(random_name(FN))
+        .local(LocalVolumeOpts::tmpfs().option("size=10m"));
+
+    let created = dec.volumes().create(request)
+        .await
+        .unwrap();
+
+    assert_eq!("local", created.driver);
+    assert_eq!(Some(&"tmpfs".to_string()), created.options.get("type"));
+    assert_eq!(Some(&"size=10m".to_string()), created.options.get("o"));
+
+    dec.volume(created.name).remove(false)
+        .await
+        .unwrap();
+}